@@ -0,0 +1,237 @@
+// Copyright 2024 Esteve Fernandez
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A bounded, backpressured alternative to `deadqueue::unlimited::Queue`.
+//!
+//! `deadqueue::unlimited::Queue` has no capacity limit, so a slow consumer (or a gossipsub
+//! IWANT burst replaying many buffered messages at once) can grow memory without bound. A
+//! `BoundedQueue` is fixed at a capacity chosen at construction time and picks one of two
+//! policies for what happens when a producer pushes into a full queue.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use tokio::sync::{Mutex, Notify};
+
+/// What a [`BoundedQueue`] does when `push` is called while the queue is already at capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OverflowPolicy {
+    /// `push` waits until the consumer makes room, exerting backpressure on the producer.
+    Block,
+    /// The oldest queued item is evicted to make room for the new one, and the eviction is
+    /// counted so a consumer can detect that it fell behind.
+    DropOldest,
+}
+
+/// A fixed-capacity FIFO queue shared between an async producer and consumer, with an explicit
+/// [`OverflowPolicy`] instead of unbounded growth.
+pub(crate) struct BoundedQueue<T> {
+    capacity: usize,
+    policy: OverflowPolicy,
+    items: Mutex<VecDeque<T>>,
+    item_pushed: Notify,
+    room_available: Notify,
+    dropped_count: AtomicU64,
+    high_water_mark: AtomicUsize,
+}
+
+impl<T> BoundedQueue<T> {
+    /// Creates a new queue holding at most `capacity` items. `capacity` of `0` is treated as `1`,
+    /// since a queue that can never hold anything isn't useful.
+    pub(crate) fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            policy,
+            items: Mutex::new(VecDeque::new()),
+            item_pushed: Notify::new(),
+            room_available: Notify::new(),
+            dropped_count: AtomicU64::new(0),
+            high_water_mark: AtomicUsize::new(0),
+        }
+    }
+
+    /// Pushes `item` onto the queue, applying this queue's [`OverflowPolicy`] if it is full.
+    pub(crate) async fn push(&self, item: T) {
+        match self.policy {
+            OverflowPolicy::Block => {
+                loop {
+                    {
+                        let mut items = self.items.lock().await;
+                        if items.len() < self.capacity {
+                            items.push_back(item);
+                            self.record_high_water_mark(items.len());
+                            break;
+                        }
+                    }
+                    self.room_available.notified().await;
+                }
+            }
+            OverflowPolicy::DropOldest => {
+                let mut items = self.items.lock().await;
+                if items.len() >= self.capacity {
+                    items.pop_front();
+                    self.dropped_count.fetch_add(1, Ordering::Relaxed);
+                }
+                items.push_back(item);
+                self.record_high_water_mark(items.len());
+            }
+        }
+        self.item_pushed.notify_one();
+    }
+
+    /// Pops the oldest item, waiting until one is available.
+    pub(crate) async fn pop(&self) -> T {
+        loop {
+            {
+                let mut items = self.items.lock().await;
+                if let Some(item) = items.pop_front() {
+                    self.room_available.notify_one();
+                    return item;
+                }
+            }
+            self.item_pushed.notified().await;
+        }
+    }
+
+    /// Pops the oldest item if one is already queued, without waiting. Returns `None` if the
+    /// queue is currently empty, for callers (e.g. a poll-based FFI `take_message`) that must
+    /// not block.
+    pub(crate) async fn try_pop(&self) -> Option<T> {
+        let mut items = self.items.lock().await;
+        let item = items.pop_front();
+        if item.is_some() {
+            self.room_available.notify_one();
+        }
+        item
+    }
+
+    /// Waits until at least one item is queued, without consuming it, or until `timeout`
+    /// elapses. Returns `true` if an item became available, `false` on timeout.
+    pub(crate) async fn wait_for_item(&self, timeout: std::time::Duration) -> bool {
+        tokio::time::timeout(timeout, async {
+            loop {
+                if !self.items.lock().await.is_empty() {
+                    return;
+                }
+                self.item_pushed.notified().await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+
+    /// The maximum number of items this queue will hold before applying its overflow policy.
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of items currently queued.
+    pub(crate) async fn len(&self) -> usize {
+        self.items.lock().await.len()
+    }
+
+    /// The largest number of items this queue has held at once, for monitoring lag.
+    pub(crate) fn high_water_mark(&self) -> usize {
+        self.high_water_mark.load(Ordering::Relaxed)
+    }
+
+    /// The number of items this queue has evicted under [`OverflowPolicy::DropOldest`]. Always
+    /// `0` for a [`OverflowPolicy::Block`] queue, which never drops anything.
+    pub(crate) fn dropped_count(&self) -> u64 {
+        self.dropped_count.load(Ordering::Relaxed)
+    }
+
+    fn record_high_water_mark(&self, len: usize) {
+        self.high_water_mark.fetch_max(len, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pop_returns_items_in_fifo_order() {
+        let queue = BoundedQueue::new(4, OverflowPolicy::Block);
+        queue.push(1).await;
+        queue.push(2).await;
+        queue.push(3).await;
+        assert_eq!(queue.pop().await, 1);
+        assert_eq!(queue.pop().await, 2);
+        assert_eq!(queue.pop().await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest_evicts_front_and_counts_it() {
+        let queue = BoundedQueue::new(2, OverflowPolicy::DropOldest);
+        queue.push(1).await;
+        queue.push(2).await;
+        queue.push(3).await; // evicts 1
+
+        assert_eq!(queue.dropped_count(), 1);
+        assert_eq!(queue.pop().await, 2);
+        assert_eq!(queue.pop().await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_high_water_mark_tracks_peak_depth() {
+        let queue = BoundedQueue::new(8, OverflowPolicy::Block);
+        queue.push(1).await;
+        queue.push(2).await;
+        queue.push(3).await;
+        queue.pop().await;
+        assert_eq!(queue.high_water_mark(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_block_policy_waits_for_room() {
+        use std::sync::Arc;
+
+        let queue = Arc::new(BoundedQueue::new(1, OverflowPolicy::Block));
+        queue.push(1).await;
+
+        let queue_clone = Arc::clone(&queue);
+        let push_task = tokio::spawn(async move {
+            queue_clone.push(2).await;
+        });
+
+        tokio::task::yield_now().await;
+        assert!(!push_task.is_finished());
+
+        assert_eq!(queue.pop().await, 1);
+        push_task.await.unwrap();
+        assert_eq!(queue.pop().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_try_pop_returns_none_when_empty() {
+        let queue: BoundedQueue<i32> = BoundedQueue::new(4, OverflowPolicy::Block);
+        assert_eq!(queue.try_pop().await, None);
+
+        queue.push(1).await;
+        assert_eq!(queue.try_pop().await, Some(1));
+        assert_eq!(queue.try_pop().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_item_times_out_when_empty() {
+        let queue: BoundedQueue<i32> = BoundedQueue::new(4, OverflowPolicy::Block);
+        assert!(!queue.wait_for_item(std::time::Duration::from_millis(10)).await);
+
+        queue.push(1).await;
+        assert!(queue.wait_for_item(std::time::Duration::from_millis(10)).await);
+        // wait_for_item doesn't consume the item.
+        assert_eq!(queue.try_pop().await, Some(1));
+    }
+}
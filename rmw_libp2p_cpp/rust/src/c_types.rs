@@ -0,0 +1,82 @@
+// Copyright 2024 Esteve Fernandez
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! C-facing types shared across the FFI surface.
+//!
+//! Mirrors the `rmw_ret_t` pattern used throughout the `rmw` C APIs: fallible entry points
+//! return one of these codes instead of `assert!`/`.unwrap()`-ing on bad input, which would
+//! otherwise abort the whole ROS process across the language boundary.
+
+use std::os::raw::c_int;
+
+/// Status code returned by fallible `rs_libp2p_*` FFI entry points, analogous to `rmw_ret_t`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Libp2pRetT {
+    /// The call completed successfully.
+    Ok = 0,
+    /// A null pointer, invalid UTF-8 string, or otherwise malformed argument was passed.
+    InvalidArgument = 1,
+    /// The underlying libp2p operation failed.
+    Error = 2,
+}
+
+impl From<Libp2pRetT> for c_int {
+    fn from(ret: Libp2pRetT) -> Self {
+        ret as c_int
+    }
+}
+
+/// Dereferences a `*const T` coming from C, turning a null pointer into
+/// `Libp2pRetT::InvalidArgument` instead of panicking.
+///
+/// # Safety
+///
+/// `ptr`, if non-null, must point to a valid, initialized `T` that outlives the returned
+/// reference.
+pub(crate) unsafe fn checked_ref<'a, T>(ptr: *const T) -> Result<&'a T, Libp2pRetT> {
+    if ptr.is_null() {
+        return Err(Libp2pRetT::InvalidArgument);
+    }
+    Ok(&*ptr)
+}
+
+/// Dereferences a `*mut T` coming from C, turning a null pointer into
+/// `Libp2pRetT::InvalidArgument` instead of panicking.
+///
+/// # Safety
+///
+/// `ptr`, if non-null, must point to a valid, initialized `T` that outlives the returned
+/// reference and is not aliased elsewhere.
+pub(crate) unsafe fn checked_mut<'a, T>(ptr: *mut T) -> Result<&'a mut T, Libp2pRetT> {
+    if ptr.is_null() {
+        return Err(Libp2pRetT::InvalidArgument);
+    }
+    Ok(&mut *ptr)
+}
+
+/// Parses a C string coming from FFI into a `&str`, turning a null pointer or invalid UTF-8
+/// into `Libp2pRetT::InvalidArgument` instead of panicking.
+///
+/// # Safety
+///
+/// `ptr`, if non-null, must point to a valid null-terminated C string.
+pub(crate) unsafe fn checked_str<'a>(ptr: *const std::os::raw::c_char) -> Result<&'a str, Libp2pRetT> {
+    if ptr.is_null() {
+        return Err(Libp2pRetT::InvalidArgument);
+    }
+    std::ffi::CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| Libp2pRetT::InvalidArgument)
+}
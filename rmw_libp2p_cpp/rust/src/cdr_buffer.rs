@@ -13,9 +13,56 @@
 // limitations under the License.
 
 use core::slice;
+use std::cell::RefCell;
 use std::ffi::{CStr, CString};
 use std::io::Cursor;
 use std::os::raw::c_char;
+use std::rc::Rc;
+
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+use snap::raw::{Decoder as SnapDecoder, Encoder as SnapEncoder};
+
+thread_local! {
+    /// The most recent failure from any `rs_libp2p_cdr_buffer_read_*`/`write_*` call on this
+    /// thread, surfaced to C callers via [`rs_libp2p_cdr_last_error_message`] since these
+    /// functions return a bare status code rather than an error type that could carry context.
+    static LAST_ERROR_MESSAGE: RefCell<CString> = RefCell::new(CString::new("").unwrap());
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message).unwrap_or_else(|_| CString::new("<error message contained an interior NUL>").unwrap());
+    LAST_ERROR_MESSAGE.with(|cell| *cell.borrow_mut() = message);
+}
+
+/// Returns a pointer to a thread-local, NUL-terminated description of the most recent
+/// `rs_libp2p_cdr_buffer_*` failure on the calling thread, or an empty string if none has
+/// occurred yet. The pointer is valid until the next failing call on the same thread; callers
+/// that need to retain it should copy it out immediately.
+#[unsafe(no_mangle)]
+pub extern "C" fn rs_libp2p_cdr_last_error_message() -> *const c_char {
+    LAST_ERROR_MESSAGE.with(|cell| cell.borrow().as_ptr())
+}
+
+/// Status codes returned by the `rs_libp2p_cdr_buffer_read_*`/`write_*` FFI in place of
+/// panicking, since a truncated or malformed buffer arriving over the libp2p transport must not
+/// abort the process across the C boundary.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CdrBufferStatus {
+    /// The operation completed and, for a read, the out-pointer was written.
+    Ok = 0,
+    /// `ptr` or an out-pointer argument was null.
+    NullPointer = 1,
+    /// The buffer ended before the requested value could be fully decoded.
+    Underrun = 2,
+    /// A `read_string` call decoded bytes that are not valid UTF-8.
+    InvalidUtf8 = 3,
+    /// Allocating storage for the decoded value failed.
+    AllocFailed = 4,
+    /// A `write_string` call was given bytes containing an interior NUL, which `CString` cannot
+    /// represent.
+    InteriorNul = 5,
+}
 
 /// Frees a `Cursor<Vec<u8>>` from memory.
 ///
@@ -61,6 +108,115 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_new(
     Box::into_raw(Box::new(libp2p_cdr_buffer))
 }
 
+/// Seeks within a `Cursor<Vec<u8>>` for callers that need to peek ahead, skip an optional field,
+/// or re-read a sub-message, rather than only consuming the buffer strictly sequentially.
+/// `whence` selects the origin: `0` = `Start(offset)`, which clamps to `min(size, offset)`;
+/// `2` = `End(offset)`, which requires `offset <= 0` and rejects a seek past the start with
+/// `Underrun`; `1` = `Current(offset)`, which adds `offset` to the current position, rejecting
+/// an underflow past the start with `Underrun` and clamping an overflow past the end to `size`.
+/// The resulting absolute position is written through `out_pos`. Returns a [`CdrBufferStatus`]
+/// so callers can distinguish a valid seek from an out-of-range request.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_seek(
+    ptr: *mut Cursor<Vec<u8>>,
+    whence: i32,
+    offset: i64,
+    out_pos: *mut u64,
+) -> i32 {
+    if ptr.is_null() || out_pos.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_seek".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let size = buffer.get_ref().len() as u64;
+    let new_pos = match whence {
+        0 => {
+            if offset < 0 {
+                set_last_error("rs_libp2p_cdr_buffer_seek: negative Start offset".to_string());
+                return CdrBufferStatus::Underrun as i32;
+            }
+            (offset as u64).min(size)
+        }
+        2 => {
+            if offset > 0 {
+                set_last_error("rs_libp2p_cdr_buffer_seek: positive End offset".to_string());
+                return CdrBufferStatus::Underrun as i32;
+            }
+            match size.checked_sub(offset.unsigned_abs()) {
+                Some(pos) => pos,
+                None => {
+                    set_last_error(
+                        "rs_libp2p_cdr_buffer_seek: seek before start of buffer".to_string(),
+                    );
+                    return CdrBufferStatus::Underrun as i32;
+                }
+            }
+        }
+        1 => {
+            let target = buffer.position() as i64 + offset;
+            if target < 0 {
+                set_last_error(
+                    "rs_libp2p_cdr_buffer_seek: seek before start of buffer".to_string(),
+                );
+                return CdrBufferStatus::Underrun as i32;
+            }
+            (target as u64).min(size)
+        }
+        _ => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_seek: invalid whence {whence}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    buffer.set_position(new_pos);
+    unsafe { *out_pos = new_pos };
+    CdrBufferStatus::Ok as i32
+}
+
+/// Writes the current byte position of a `Cursor<Vec<u8>>` through `out_pos`. Returns a
+/// [`CdrBufferStatus`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_tell(
+    ptr: *mut Cursor<Vec<u8>>,
+    out_pos: *mut u64,
+) -> i32 {
+    if ptr.is_null() || out_pos.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_tell".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &*ptr };
+    unsafe { *out_pos = buffer.position() };
+    CdrBufferStatus::Ok as i32
+}
+
+/// Writes the number of bytes remaining after the current position of a `Cursor<Vec<u8>>`
+/// through `out_len`. Returns a [`CdrBufferStatus`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_remaining(
+    ptr: *mut Cursor<Vec<u8>>,
+    out_len: *mut u64,
+) -> i32 {
+    if ptr.is_null() || out_len.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_remaining".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &*ptr };
+    let size = buffer.get_ref().len() as u64;
+    unsafe { *out_len = size.saturating_sub(buffer.position()) };
+    CdrBufferStatus::Ok as i32
+}
+
 /// Reads a `u64` from a `Cursor<Vec<u8>>`.
 ///
 /// # Safety
@@ -76,13 +232,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_new(
 ///
 /// This function will panic if the provided pointer is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint64(ptr: *mut Cursor<Vec<u8>>, n: *mut u64) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    let x = cdr::deserialize_from::<_, u64, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap();
-    unsafe { *n = x };
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint64(ptr: *mut Cursor<Vec<u8>>, n: *mut u64) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_uint64".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, u64, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_uint64: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Reads a `u32` from a `Cursor<Vec<u8>>`.
@@ -100,12 +265,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint64(ptr: *mut Cursor<Vec<u
 ///
 /// This function will panic if the provided pointer is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint32(ptr: *mut Cursor<Vec<u8>>, n: *mut u32) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    unsafe { *n = cdr::deserialize_from::<_, u32, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap() };
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint32(ptr: *mut Cursor<Vec<u8>>, n: *mut u32) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_uint32".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, u32, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_uint32: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Reads a `u16` from a `Cursor<Vec<u8>>`.
@@ -123,12 +298,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint32(ptr: *mut Cursor<Vec<u
 ///
 /// This function will panic if the provided pointer is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint16(ptr: *mut Cursor<Vec<u8>>, n: *mut u16) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    unsafe { *n = cdr::deserialize_from::<_, u16, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap() };
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint16(ptr: *mut Cursor<Vec<u8>>, n: *mut u16) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_uint16".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, u16, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_uint16: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Reads a `u8` from a `Cursor<Vec<u8>>`.
@@ -146,12 +331,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint16(ptr: *mut Cursor<Vec<u
 ///
 /// This function will panic if the provided pointer is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint8(ptr: *mut Cursor<Vec<u8>>, n: *mut u8) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    unsafe { *n = cdr::deserialize_from::<_, u8, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap() };
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint8(ptr: *mut Cursor<Vec<u8>>, n: *mut u8) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_uint8".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, u8, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_uint8: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Reads a `i64` from a `Cursor<Vec<u8>>`.
@@ -169,12 +364,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint8(ptr: *mut Cursor<Vec<u8
 ///
 /// This function will panic if the provided pointer is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int64(ptr: *mut Cursor<Vec<u8>>, n: *mut i64) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    unsafe { *n = cdr::deserialize_from::<_, i64, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap() };
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int64(ptr: *mut Cursor<Vec<u8>>, n: *mut i64) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_int64".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, i64, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_int64: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Reads a `i32` from a `Cursor<Vec<u8>>`.
@@ -192,12 +397,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int64(ptr: *mut Cursor<Vec<u8
 ///
 /// This function will panic if the provided pointer is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int32(ptr: *mut Cursor<Vec<u8>>, n: *mut i32) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    unsafe { *n = cdr::deserialize_from::<_, i32, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap() };
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int32(ptr: *mut Cursor<Vec<u8>>, n: *mut i32) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_int32".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, i32, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_int32: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Reads a `i16` from a `Cursor<Vec<u8>>`.
@@ -215,12 +430,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int32(ptr: *mut Cursor<Vec<u8
 ///
 /// This function will panic if the provided pointer is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int16(ptr: *mut Cursor<Vec<u8>>, n: *mut i16) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    unsafe { *n = cdr::deserialize_from::<_, i16, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap() };
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int16(ptr: *mut Cursor<Vec<u8>>, n: *mut i16) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_int16".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, i16, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_int16: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Reads a `i8` from a `Cursor<Vec<u8>>`.
@@ -238,12 +463,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int16(ptr: *mut Cursor<Vec<u8
 ///
 /// This function will panic if the provided pointer is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int8(ptr: *mut Cursor<Vec<u8>>, n: *mut i8) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    unsafe { *n = cdr::deserialize_from::<_, i8, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap() };
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int8(ptr: *mut Cursor<Vec<u8>>, n: *mut i8) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_int8".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, i8, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_int8: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Reads a `c_char` from a `Cursor<Vec<u8>>`.
@@ -261,14 +496,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int8(ptr: *mut Cursor<Vec<u8>
 ///
 /// This function will panic if the provided pointer is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_char(ptr: *mut Cursor<Vec<u8>>, n: *mut c_char) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    unsafe {
-        *n = cdr::deserialize_from::<_, c_char, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap()
-    };
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_char(ptr: *mut Cursor<Vec<u8>>, n: *mut c_char) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_char".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, c_char, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_char: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Reads a 16-bit `c_char` from a `Cursor<Vec<u8>>`.
@@ -286,12 +529,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_char(ptr: *mut Cursor<Vec<u8>
 ///
 /// This function will panic if the provided pointer is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_char16(ptr: *mut Cursor<Vec<u8>>, n: *mut u16) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    unsafe { *n = cdr::deserialize_from::<_, u16, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap() };
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_char16(ptr: *mut Cursor<Vec<u8>>, n: *mut u16) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_char16".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, u16, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_char16: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Reads a `float` from a `Cursor<Vec<u8>>`.
@@ -309,12 +562,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_char16(ptr: *mut Cursor<Vec<u
 ///
 /// This function will panic if the provided pointer is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_float(ptr: *mut Cursor<Vec<u8>>, n: *mut f32) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    unsafe { *n = cdr::deserialize_from::<_, f32, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap() };
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_float(ptr: *mut Cursor<Vec<u8>>, n: *mut f32) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_float".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, f32, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_float: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Reads a `double` from a `Cursor<Vec<u8>>`.
@@ -332,12 +595,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_float(ptr: *mut Cursor<Vec<u8
 ///
 /// This function will panic if the provided pointer is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_double(ptr: *mut Cursor<Vec<u8>>, n: *mut f64) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    unsafe { *n = cdr::deserialize_from::<_, f64, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap() };
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_double(ptr: *mut Cursor<Vec<u8>>, n: *mut f64) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_double".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, f64, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_double: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Reads a `bool` from a `Cursor<Vec<u8>>`.
@@ -355,12 +628,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_double(ptr: *mut Cursor<Vec<u
 ///
 /// This function will panic if the provided pointer is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_bool(ptr: *mut Cursor<Vec<u8>>, n: *mut bool) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    unsafe { *n = cdr::deserialize_from::<_, bool, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap() };
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_bool(ptr: *mut Cursor<Vec<u8>>, n: *mut bool) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_bool".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, bool, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_bool: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Deserializes a `CString` from a `Cursor<Vec<u8>>` and stores the raw pointer and length of the string.
@@ -375,20 +658,29 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_bool(ptr: *mut Cursor<Vec<u8>
 /// * `s` - A raw pointer to store the raw pointer of the string.
 /// * `size` - A raw pointer to store the length of the string.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `s`/`size` are left untouched.
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_string(
     ptr: *mut Cursor<Vec<u8>>,
     s: *mut *const c_char,
     size: *mut usize,
-) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
+) -> i32 {
+    if ptr.is_null() || s.is_null() || size.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_string".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let cs = match cdr::deserialize_from::<_, CString, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(cs) => cs,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_string: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
     };
-    let cs = cdr::deserialize_from::<_, CString, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap();
+    if cs.to_str().is_err() {
+        set_last_error("rs_libp2p_cdr_buffer_read_string: string is not valid UTF-8".to_string());
+        return CdrBufferStatus::InvalidUtf8 as i32;
+    }
     let len = cs.as_bytes().len();
 
     unsafe {
@@ -397,6 +689,127 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_string(
             *s = cs.into_raw();
         }
     }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Deserializes the raw bytes of a CDR string from a `Cursor<Vec<u8>>` without routing them
+/// through `CString`, so a DDS `string<>` field containing interior NUL bytes (legal on the
+/// wire, but fatal to `CString::new`/`CString::into_raw`) is decoded intact rather than
+/// panicking or silently truncating at the first NUL. The CDR wire encoding's own trailing NUL
+/// terminator is stripped before the bytes are returned.
+///
+/// Writes the byte pointer/length through `s`/`size` regardless of content, and `is_valid_utf8`
+/// to whether those bytes happen to be valid UTF-8 — callers that require UTF-8 should check
+/// this flag, or use [`rs_libp2p_cdr_buffer_read_string_lossy`] instead. Free the result with
+/// [`rs_libp2p_cdr_buffer_free_string_bytes`].
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `s`/`size`/`is_valid_utf8` are
+/// left untouched.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_string_bytes(
+    ptr: *mut Cursor<Vec<u8>>,
+    s: *mut *const u8,
+    size: *mut usize,
+    is_valid_utf8: *mut bool,
+) -> i32 {
+    if ptr.is_null() || s.is_null() || size.is_null() || is_valid_utf8.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_string_bytes".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let mut bytes = match cdr::deserialize_from::<_, Vec<u8>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_string_bytes: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    // CDR strings carry a trailing NUL terminator in their length; drop it so callers see the
+    // content only, matching rs_libp2p_cdr_buffer_read_string's `size`.
+    if bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+    let len = bytes.len();
+    let valid_utf8 = std::str::from_utf8(&bytes).is_ok();
+
+    unsafe {
+        *size = len;
+        *is_valid_utf8 = valid_utf8;
+        if len != 0 {
+            *s = Box::into_raw(bytes.into_boxed_slice()) as *const u8;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Frees bytes allocated by [`rs_libp2p_cdr_buffer_read_string_bytes`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Does nothing if the provided pointer is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_string_bytes(s: *mut u8, size: usize) {
+    if s.is_null() {
+        return;
+    }
+    unsafe {
+        let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(s, size));
+    }
+}
+
+/// Deserializes a CDR string from a `Cursor<Vec<u8>>` into a NUL-terminated, valid-UTF-8
+/// `CString`, replacing any invalid byte sequences with `U+FFFD` (the same strategy as
+/// `String::from_utf8_lossy`) rather than rejecting the read. Unlike
+/// [`rs_libp2p_cdr_buffer_read_string`], this never fails on malformed UTF-8, so C callers that
+/// just want a displayable NUL-terminated buffer don't need to handle an error path for that
+/// case. Free the result with [`rs_libp2p_cdr_buffer_free_string`].
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `s`/`size` are left untouched.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_string_lossy(
+    ptr: *mut Cursor<Vec<u8>>,
+    s: *mut *const c_char,
+    size: *mut usize,
+) -> i32 {
+    if ptr.is_null() || s.is_null() || size.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_string_lossy".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let mut bytes = match cdr::deserialize_from::<_, Vec<u8>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_string_lossy: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    if bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+    let lossy = String::from_utf8_lossy(&bytes).into_owned();
+    // `from_utf8_lossy`'s replacement character is still valid UTF-8 and contains no NUL bytes,
+    // so this can only fail if the caller's own bytes somehow embedded one, which U+FFFD
+    // substitution never introduces.
+    let cstring = CString::new(lossy).unwrap_or_else(|_| CString::new("").unwrap());
+    let len = cstring.as_bytes().len();
+
+    unsafe {
+        *size = len;
+        if len != 0 {
+            *s = cstring.into_raw();
+        }
+    }
+    CdrBufferStatus::Ok as i32
 }
 
 /// Frees a `CString` from memory.
@@ -458,20 +871,25 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_u16string(s: *mut u16, size:
 /// * `s` - A raw pointer to store the raw pointer of the string.
 /// * `size` - A raw pointer to store the length of the string.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `s`/`size` are left untouched.
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_u16string(
     ptr: *mut Cursor<Vec<u8>>,
     s: *mut *const u16,
     size: *mut usize,
-) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
+) -> i32 {
+    if ptr.is_null() || s.is_null() || size.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_u16string".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let cs = match cdr::deserialize_from::<_, Vec<u16>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(cs) => cs,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_u16string: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
     };
-    let cs = cdr::deserialize_from::<_, Vec<u16>, _>(libp2p_cdr_buffer, cdr::Infinite).unwrap();
     let len = cs.len();
 
     unsafe {
@@ -481,6 +899,7 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_u16string(
             *s = Box::into_raw(boxed) as *const u16;
         }
     }
+    CdrBufferStatus::Ok as i32
 }
 
 /// Creates a new `Cursor<Vec<u8>>` to write to.
@@ -512,16 +931,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_new() -> *mut Cursor<Vec<u8>
 /// * `ptr` - A raw pointer to a `Cursor<Vec<u8>>`.
 /// * `n` - The `u64` to write.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint64(ptr: *mut Cursor<Vec<u8>>, n: u64) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite).unwrap();
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint64(ptr: *mut Cursor<Vec<u8>>, n: u64) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_uint64".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_uint64: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Writes a `u32` to a `Cursor<Vec<u8>>`.
@@ -538,16 +963,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint64(ptr: *mut Cursor<Vec<
 /// * `ptr` - A raw pointer to a `Cursor<Vec<u8>>`.
 /// * `n` - The `u32` to write.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint32(ptr: *mut Cursor<Vec<u8>>, n: u32) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite).unwrap();
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint32(ptr: *mut Cursor<Vec<u8>>, n: u32) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_uint32".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_uint32: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Writes a `u16` to a `Cursor<Vec<u8>>`.
@@ -564,16 +995,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint32(ptr: *mut Cursor<Vec<
 /// * `ptr` - A raw pointer to a `Cursor<Vec<u8>>`.
 /// * `n` - The `u16` to write.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint16(ptr: *mut Cursor<Vec<u8>>, n: u16) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite).unwrap();
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint16(ptr: *mut Cursor<Vec<u8>>, n: u16) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_uint16".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_uint16: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Writes a `u8` to a `Cursor<Vec<u8>>`.
@@ -590,16 +1027,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint16(ptr: *mut Cursor<Vec<
 /// * `ptr` - A raw pointer to a `Cursor<Vec<u8>>`.
 /// * `n` - The `u8` to write.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint8(ptr: *mut Cursor<Vec<u8>>, n: u8) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite).unwrap();
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint8(ptr: *mut Cursor<Vec<u8>>, n: u8) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_uint8".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_uint8: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Writes a `i64` to a `Cursor<Vec<u8>>`.
@@ -616,16 +1059,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint8(ptr: *mut Cursor<Vec<u
 /// * `ptr` - A raw pointer to a `Cursor<Vec<u8>>`.
 /// * `n` - The `i64` to write.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int64(ptr: *mut Cursor<Vec<u8>>, n: i64) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite).unwrap();
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int64(ptr: *mut Cursor<Vec<u8>>, n: i64) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_int64".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_int64: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Writes a `i32` to a `Cursor<Vec<u8>>`.
@@ -642,16 +1091,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int64(ptr: *mut Cursor<Vec<u
 /// * `ptr` - A raw pointer to a `Cursor<Vec<u8>>`.
 /// * `n` - The `i32` to write.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int32(ptr: *mut Cursor<Vec<u8>>, n: i32) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite).unwrap();
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int32(ptr: *mut Cursor<Vec<u8>>, n: i32) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_int32".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_int32: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Writes a `i16` to a `Cursor<Vec<u8>>`.
@@ -668,16 +1123,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int32(ptr: *mut Cursor<Vec<u
 /// * `ptr` - A raw pointer to a `Cursor<Vec<u8>>`.
 /// * `n` - The `i16` to write.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int16(ptr: *mut Cursor<Vec<u8>>, n: i16) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite).unwrap();
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int16(ptr: *mut Cursor<Vec<u8>>, n: i16) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_int16".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_int16: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Writes a `i8` to a `Cursor<Vec<u8>>`.
@@ -694,16 +1155,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int16(ptr: *mut Cursor<Vec<u
 /// * `ptr` - A raw pointer to a `Cursor<Vec<u8>>`.
 /// * `n` - The `i8` to write.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int8(ptr: *mut Cursor<Vec<u8>>, n: i8) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite).unwrap();
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int8(ptr: *mut Cursor<Vec<u8>>, n: i8) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_int8".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_int8: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Writes a `c_char` to a `Cursor<Vec<u8>>`.
@@ -720,16 +1187,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int8(ptr: *mut Cursor<Vec<u8
 /// * `ptr` - A raw pointer to a `Cursor<Vec<u8>>`.
 /// * `n` - The `c_char` to write.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_char(ptr: *mut Cursor<Vec<u8>>, n: c_char) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite).unwrap();
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_char(ptr: *mut Cursor<Vec<u8>>, n: c_char) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_char".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_char: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Writes a 16-bit `char` to a `Cursor<Vec<u8>>`.
@@ -746,16 +1219,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_char(ptr: *mut Cursor<Vec<u8
 /// * `ptr` - A raw pointer to a `Cursor<Vec<u8>>`.
 /// * `n` - The `u16` to write.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_char16(ptr: *mut Cursor<Vec<u8>>, n: u16) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite).unwrap();
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_char16(ptr: *mut Cursor<Vec<u8>>, n: u16) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_char16".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_char16: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Writes a `float` to a `Cursor<Vec<u8>>`.
@@ -772,16 +1251,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_char16(ptr: *mut Cursor<Vec<
 /// * `ptr` - A raw pointer to a `Cursor<Vec<u8>>`.
 /// * `n` - The `f32` to write.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_float(ptr: *mut Cursor<Vec<u8>>, n: f32) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite).unwrap();
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_float(ptr: *mut Cursor<Vec<u8>>, n: f32) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_float".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_float: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Writes a `double` to a `Cursor<Vec<u8>>`.
@@ -798,16 +1283,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_float(ptr: *mut Cursor<Vec<u
 /// * `ptr` - A raw pointer to a `Cursor<Vec<u8>>`.
 /// * `n` - The `f64` to write.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_double(ptr: *mut Cursor<Vec<u8>>, n: f64) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite).unwrap();
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_double(ptr: *mut Cursor<Vec<u8>>, n: f64) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_double".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_double: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Writes a `bool` to a `Cursor<Vec<u8>>`.
@@ -824,16 +1315,22 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_double(ptr: *mut Cursor<Vec<
 /// * `ptr` - A raw pointer to a `Cursor<Vec<u8>>`.
 /// * `n` - The `bool` to write.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_bool(ptr: *mut Cursor<Vec<u8>>, n: bool) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite).unwrap();
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_bool(ptr: *mut Cursor<Vec<u8>>, n: bool) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_bool".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_bool: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
 }
 
 /// Writes a string to a `Cursor<Vec<u8>>`.
@@ -851,29 +1348,38 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_bool(ptr: *mut Cursor<Vec<u8
 /// * `s` - A raw pointer to a C string.
 /// * `size` - The length of the string (excluding null terminator).
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null,
+/// `InteriorNul` if `s` contains a NUL byte before `size` (so it cannot round-trip through
+/// `CString`), or `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_string(
     ptr: *mut Cursor<Vec<u8>>,
     s: *const c_char,
     size: usize,
-) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    if size == 0 || s.is_null() {
-        // Write empty string
-        let empty = CString::new("").unwrap();
-        cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &empty, cdr::Infinite)
-            .unwrap();
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_string".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let cstring = if size == 0 || s.is_null() {
+        CString::new("").unwrap()
     } else {
         let cs = unsafe { CStr::from_ptr(s) };
-        let cstring = CString::new(cs.to_bytes()).unwrap();
-        cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &cstring, cdr::Infinite)
-            .unwrap();
+        match CString::new(cs.to_bytes()) {
+            Ok(cstring) => cstring,
+            Err(err) => {
+                set_last_error(format!("rs_libp2p_cdr_buffer_write_string: {err}"));
+                return CdrBufferStatus::InteriorNul as i32;
+            }
+        }
+    };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &cstring, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_string: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
     }
 }
 
@@ -892,412 +1398,6449 @@ pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_string(
 /// * `s` - A raw pointer to a u16 array.
 /// * `size` - The number of u16 elements in the string.
 ///
-/// # Panics
-///
-/// This function will panic if the provided pointer is null.
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_u16string(
     ptr: *mut Cursor<Vec<u8>>,
     s: *const u16,
     size: usize,
-) {
-    let libp2p_cdr_buffer = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
-    };
-    if size == 0 || s.is_null() {
-        // Write empty u16 string
-        let empty: Vec<u16> = Vec::new();
-        cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &empty, cdr::Infinite)
-            .unwrap();
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_u16string".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let vec: Vec<u16> = if size == 0 || s.is_null() {
+        Vec::new()
     } else {
         let slice = unsafe { slice::from_raw_parts(s, size) };
-        let vec: Vec<u16> = slice.to_vec();
-        cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &vec, cdr::Infinite).unwrap();
+        slice.to_vec()
+    };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &vec, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_u16string: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::ffi::CStr;
-
-    // Helper function to get buffer data for reading
-    fn get_buffer_data(ptr: *mut Cursor<Vec<u8>>) -> Vec<u8> {
-        unsafe {
-            let cursor = &*ptr;
-            cursor.get_ref().clone()
-        }
-    }
+/// A CDR read buffer that rejects any declared string/sequence length exceeding a configured
+/// allocation cap, so a hostile libp2p peer cannot trigger an unbounded allocation with a
+/// multi-gigabyte length prefix before any data is validated. The plain `Cursor<Vec<u8>>` buffer
+/// above always deserializes with `cdr::Infinite`, which has no such cap.
+pub struct BoundedCdrBuffer {
+    cursor: Cursor<Vec<u8>>,
+    max_alloc: u64,
+}
 
-    #[test]
-    fn test_buffer_lifecycle() {
-        // Test buffer creation and cleanup
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        assert!(!write_buf.is_null());
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+/// Creates a new [`BoundedCdrBuffer`] that rejects any string/sequence whose declared length
+/// exceeds `max_alloc` bytes, or the bytes actually remaining in the buffer, whichever is
+/// smaller. Use this instead of [`rs_libp2p_cdr_buffer_read_new`] whenever the bytes originate
+/// from an untrusted peer.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_new_bounded(
+    data: *const u8,
+    length: usize,
+    max_alloc: usize,
+) -> *mut BoundedCdrBuffer {
+    let bytes = unsafe { slice::from_raw_parts(data, length).to_vec() };
+    let buffer = BoundedCdrBuffer {
+        cursor: Cursor::new(bytes),
+        max_alloc: max_alloc as u64,
+    };
+    Box::into_raw(Box::new(buffer))
+}
 
-        // Test read buffer creation
-        let data = [0u8, 1, 2, 3];
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
-        assert!(!read_buf.is_null());
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+/// Frees a [`BoundedCdrBuffer`] from memory.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_bounded(ptr: *mut BoundedCdrBuffer) {
+    if ptr.is_null() {
+        return;
     }
+    unsafe { drop(Box::from_raw(ptr)) };
+}
 
-    #[test]
-    fn test_null_pointer_handling() {
-        // free should handle null gracefully
-        unsafe { rs_libp2p_cdr_buffer_free(std::ptr::null_mut()) };
+impl BoundedCdrBuffer {
+    /// The effective bound for the next read: the smaller of the configured cap and the bytes
+    /// actually remaining, so a declared length can never allocate past either limit.
+    fn effective_limit(&self) -> u64 {
+        let remaining = self.cursor.get_ref().len() as u64 - self.cursor.position();
+        self.max_alloc.min(remaining)
+    }
+}
 
-        // free_string should handle null gracefully
-        unsafe { rs_libp2p_cdr_buffer_free_string(std::ptr::null_mut()) };
+/// Reads a `CString` from a [`BoundedCdrBuffer`], rejecting a declared length that exceeds the
+/// buffer's configured `max_alloc` cap or the bytes remaining. Returns a [`CdrBufferStatus`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_string_bounded(
+    ptr: *mut BoundedCdrBuffer,
+    s: *mut *const c_char,
+    size: *mut usize,
+) -> i32 {
+    if ptr.is_null() || s.is_null() || size.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_string_bounded".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let limit = buffer.effective_limit();
+    let cs = match cdr::deserialize_from::<_, CString, _>(&mut buffer.cursor, cdr::Bounded(limit))
+    {
+        Ok(cs) => cs,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_string_bounded: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let len = cs.as_bytes().len();
+    unsafe {
+        *size = len;
+        if len != 0 {
+            *s = cs.into_raw();
+        }
     }
+    CdrBufferStatus::Ok as i32
+}
 
-    // === Unsigned Integer Roundtrip Tests ===
+/// Reads a `u16` string from a [`BoundedCdrBuffer`], rejecting a declared length that exceeds the
+/// buffer's configured `max_alloc` cap or the bytes remaining. Returns a [`CdrBufferStatus`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_u16string_bounded(
+    ptr: *mut BoundedCdrBuffer,
+    s: *mut *const u16,
+    size: *mut usize,
+) -> i32 {
+    if ptr.is_null() || s.is_null() || size.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_u16string_bounded".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let limit = buffer.effective_limit();
+    let cs =
+        match cdr::deserialize_from::<_, Vec<u16>, _>(&mut buffer.cursor, cdr::Bounded(limit)) {
+            Ok(cs) => cs,
+            Err(err) => {
+                set_last_error(format!("rs_libp2p_cdr_buffer_read_u16string_bounded: {err}"));
+                return CdrBufferStatus::Underrun as i32;
+            }
+        };
+    let len = cs.len();
+    unsafe {
+        *size = len;
+        if len != 0 {
+            *s = Box::into_raw(cs.into_boxed_slice()) as *const u16;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
 
-    #[test]
-    fn test_uint64_roundtrip() {
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val: u64 = 0x0123456789ABCDEF;
+/// Reads a bulk `u8` array from a [`BoundedCdrBuffer`] (see [`rs_libp2p_cdr_buffer_read_uint8_array`]),
+/// rejecting a declared element count that would exceed the buffer's configured `max_alloc` cap or
+/// the bytes remaining. Returns a [`CdrBufferStatus`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint8_array_bounded(
+    ptr: *mut BoundedCdrBuffer,
+    out: *mut *mut u8,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_uint8_array_bounded".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let limit = buffer.effective_limit();
+    let values =
+        match cdr::deserialize_from::<_, Vec<u8>, _>(&mut buffer.cursor, cdr::Bounded(limit)) {
+            Ok(values) => values,
+            Err(err) => {
+                set_last_error(format!("rs_libp2p_cdr_buffer_read_uint8_array_bounded: {err}"));
+                return CdrBufferStatus::Underrun as i32;
+            }
+        };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut u8;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
 
-        unsafe { rs_libp2p_cdr_buffer_write_uint64(write_buf, test_val) };
+/// A CDR read cursor over memory owned by the caller rather than a clone of it.
+/// [`rs_libp2p_cdr_buffer_read_new`] copies the entire incoming payload into a `Vec<u8>` even
+/// though reads never mutate it; this type instead stores the raw pointer/length pair the caller
+/// already holds and advances a position into it, so decoding a received libp2p frame takes no
+/// allocation. The caller must keep the underlying memory alive for at least as long as this
+/// buffer is used and must not mutate it while outstanding reads may still observe it.
+pub struct BorrowedCdrBuffer {
+    data: *const u8,
+    len: usize,
+    pos: u64,
+}
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+impl BorrowedCdrBuffer {
+    /// Builds a short-lived `Cursor<&[u8]>` over the borrowed memory, positioned where the last
+    /// read left off, for a single `cdr::deserialize_from` call.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure `self.data`/`self.len` still describe valid, live memory.
+    unsafe fn cursor(&self) -> Cursor<&[u8]> {
+        let slice = unsafe { slice::from_raw_parts(self.data, self.len) };
+        let mut cursor = Cursor::new(slice);
+        cursor.set_position(self.pos);
+        cursor
+    }
+}
 
-        let mut result: u64 = 0;
-        unsafe { rs_libp2p_cdr_buffer_read_uint64(read_buf, &mut result as *mut u64) };
+/// Creates a new [`BorrowedCdrBuffer`] over caller-owned memory with no allocation or copy.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers. The caller must keep `data` valid and
+/// unchanged for the lifetime of the returned buffer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_borrowed(
+    data: *const u8,
+    length: usize,
+) -> *mut BorrowedCdrBuffer {
+    Box::into_raw(Box::new(BorrowedCdrBuffer {
+        data,
+        len: length,
+        pos: 0,
+    }))
+}
 
-        assert_eq!(result, test_val);
+/// Frees a [`BorrowedCdrBuffer`] wrapper. This never frees the underlying caller-owned memory,
+/// only the small struct tracking the read position into it.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_borrowed(ptr: *mut BorrowedCdrBuffer) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe { drop(Box::from_raw(ptr)) };
+}
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+/// Reads a `u32` from a [`BorrowedCdrBuffer`] without copying the backing memory.
+/// Returns a [`CdrBufferStatus`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_borrowed_uint32(
+    ptr: *mut BorrowedCdrBuffer,
+    n: *mut u32,
+) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_borrowed_uint32".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let mut cursor = unsafe { buffer.cursor() };
+    match cdr::deserialize_from::<_, u32, _>(&mut cursor, cdr::Infinite) {
+        Ok(value) => {
+            buffer.pos = cursor.position();
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_borrowed_uint32: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
     }
+}
 
-    #[test]
+/// Reads an `f64` from a [`BorrowedCdrBuffer`] without copying the backing memory.
+/// Returns a [`CdrBufferStatus`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_borrowed_double(
+    ptr: *mut BorrowedCdrBuffer,
+    n: *mut f64,
+) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_borrowed_double".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let mut cursor = unsafe { buffer.cursor() };
+    match cdr::deserialize_from::<_, f64, _>(&mut cursor, cdr::Infinite) {
+        Ok(value) => {
+            buffer.pos = cursor.position();
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_borrowed_double: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a `CString` from a [`BorrowedCdrBuffer`] without copying the backing memory ahead of
+/// time (the returned string is still a fresh allocation, since a CDR string is not necessarily
+/// NUL-terminated in place). Returns a [`CdrBufferStatus`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_borrowed_string(
+    ptr: *mut BorrowedCdrBuffer,
+    s: *mut *const c_char,
+    size: *mut usize,
+) -> i32 {
+    if ptr.is_null() || s.is_null() || size.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_borrowed_string".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let mut cursor = unsafe { buffer.cursor() };
+    let cs = match cdr::deserialize_from::<_, CString, _>(&mut cursor, cdr::Infinite) {
+        Ok(cs) => cs,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_borrowed_string: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    buffer.pos = cursor.position();
+    let len = cs.as_bytes().len();
+    unsafe {
+        *size = len;
+        if len != 0 {
+            *s = cs.into_raw();
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Locates a CDR sequence of `elem_size`-byte elements at byte offset `pos` within `data` and
+/// returns `(element_count, byte_offset_of_first_element)` without copying or allocating, mirroring
+/// the length-prefix handling `cdr::deserialize_from` performs internally but stopping short of
+/// materializing a `Vec`. Returns `None` if the length prefix or the declared elements don't fit.
+fn locate_cdr_sequence(data: &[u8], pos: u64, elem_size: usize) -> Option<(u32, usize)> {
+    let aligned = usize::try_from((pos + 3) & !3).ok()?;
+    if aligned.checked_add(4)? > data.len() {
+        return None;
+    }
+    let count = u32::from_be_bytes(data[aligned..aligned + 4].try_into().unwrap());
+    let elems_start = aligned + 4;
+    let elems_start = if elem_size > 1 {
+        (elems_start + (elem_size - 1)) & !(elem_size - 1)
+    } else {
+        elems_start
+    };
+    let needed = (count as usize).checked_mul(elem_size)?;
+    if elems_start.checked_add(needed)? > data.len() {
+        return None;
+    }
+    Some((count, elems_start))
+}
+
+/// Maps a CDR `u16string` (a `u16` sequence) at a [`BorrowedCdrBuffer`]'s current position directly
+/// onto its backing memory, the way GStreamer's `gst_buffer_map`/`ReadBufferMap` exposes a `&[T]`
+/// tied to a buffer's lifetime rather than copying out of it — unlike
+/// [`rs_libp2p_cdr_buffer_read_borrowed_string`], which still allocates a fresh `CString` on every
+/// call. The element count and a pointer into the buffer's own storage are written through
+/// `out_ptr`/`out_len`; the pointer is valid only as long as the [`BorrowedCdrBuffer`] (and the
+/// caller-owned memory it wraps) remains alive, and must be released with
+/// [`rs_libp2p_cdr_buffer_unmap`].
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out_ptr`/`out_len` are left
+/// untouched.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_map_u16string(
+    ptr: *mut BorrowedCdrBuffer,
+    out_ptr: *mut *const u16,
+    out_len: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out_ptr.is_null() || out_len.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_map_u16string".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let slice = unsafe { slice::from_raw_parts(buffer.data, buffer.len) };
+    let Some((count, elems_start)) = locate_cdr_sequence(slice, buffer.pos, 2) else {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_map_u16string: declared length exceeds remaining buffer"
+                .to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    };
+    unsafe {
+        *out_ptr = slice.as_ptr().add(elems_start) as *const u16;
+        *out_len = count as usize;
+    }
+    buffer.pos = (elems_start + count as usize * 2) as u64;
+    CdrBufferStatus::Ok as i32
+}
+
+/// Maps a CDR `uint32` sequence at a [`BorrowedCdrBuffer`]'s current position directly onto its
+/// backing memory, the bulk-array analogue of [`rs_libp2p_cdr_buffer_map_u16string`] — see that
+/// function's documentation for the map/unmap lifetime contract. Useful for large numeric sequence
+/// fields (e.g. `sensor_msgs/PointCloud2` point indices) where copying out of the receive buffer
+/// would double memory traffic.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_map_sequence_uint32(
+    ptr: *mut BorrowedCdrBuffer,
+    out_ptr: *mut *const u32,
+    out_len: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out_ptr.is_null() || out_len.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_map_sequence_uint32".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let slice = unsafe { slice::from_raw_parts(buffer.data, buffer.len) };
+    let Some((count, elems_start)) = locate_cdr_sequence(slice, buffer.pos, 4) else {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_map_sequence_uint32: declared length exceeds remaining buffer"
+                .to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    };
+    unsafe {
+        *out_ptr = slice.as_ptr().add(elems_start) as *const u32;
+        *out_len = count as usize;
+    }
+    buffer.pos = (elems_start + count as usize * 4) as u64;
+    CdrBufferStatus::Ok as i32
+}
+
+/// Releases a mapping created by [`rs_libp2p_cdr_buffer_map_u16string`] or
+/// [`rs_libp2p_cdr_buffer_map_sequence_uint32`]. Both map directly onto the
+/// [`BorrowedCdrBuffer`]'s own backing memory rather than an allocation of their own, so there is
+/// nothing to free here; this exists purely to mirror GStreamer's map/unmap pairing so callers have
+/// a single, symmetric release point regardless of which mapped type they used.
+#[unsafe(no_mangle)]
+pub extern "C" fn rs_libp2p_cdr_buffer_unmap(_ptr: *const std::ffi::c_void, _len: usize) {}
+
+/// A single caller-owned memory region within a [`SegmentedCdrReader`]'s ordered frame list.
+#[derive(Clone, Copy)]
+struct CdrSegment {
+    data: *const u8,
+    len: usize,
+}
+
+/// A zero-copy `std::io::Read` + `std::io::Seek` cursor over an ordered list of caller-owned
+/// memory segments, e.g. the individual frames libp2p delivers for one message, so CDR decoding
+/// never requires concatenating received frames into one contiguous `Vec<u8>` first.
+pub struct SegmentedCdrReader {
+    segments: Vec<CdrSegment>,
+    total_len: u64,
+    segment_index: usize,
+    segment_offset: usize,
+    pos: u64,
+}
+
+impl SegmentedCdrReader {
+    fn new(segments: Vec<CdrSegment>) -> Self {
+        let total_len = segments.iter().map(|segment| segment.len as u64).sum();
+        SegmentedCdrReader {
+            segments,
+            total_len,
+            segment_index: 0,
+            segment_offset: 0,
+            pos: 0,
+        }
+    }
+
+    /// Recomputes `segment_index`/`segment_offset` for an absolute byte position, clamping to
+    /// `[0, total_len]` rather than allowing a seek past the end.
+    fn seek_to(&mut self, pos: u64) {
+        let pos = pos.min(self.total_len);
+        let mut remaining = pos;
+        for (index, segment) in self.segments.iter().enumerate() {
+            if remaining <= segment.len as u64 {
+                self.segment_index = index;
+                self.segment_offset = remaining as usize;
+                self.pos = pos;
+                return;
+            }
+            remaining -= segment.len as u64;
+        }
+        self.segment_index = self.segments.len();
+        self.segment_offset = 0;
+        self.pos = pos;
+    }
+}
+
+impl std::io::Read for SegmentedCdrReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut written = 0;
+        while written < buf.len() && self.segment_index < self.segments.len() {
+            let segment = self.segments[self.segment_index];
+            let available = segment.len - self.segment_offset;
+            if available == 0 {
+                self.segment_index += 1;
+                self.segment_offset = 0;
+                continue;
+            }
+            let to_copy = available.min(buf.len() - written);
+            let src =
+                unsafe { slice::from_raw_parts(segment.data.add(self.segment_offset), to_copy) };
+            buf[written..written + to_copy].copy_from_slice(src);
+            written += to_copy;
+            self.segment_offset += to_copy;
+            self.pos += to_copy as u64;
+            if self.segment_offset == segment.len {
+                self.segment_index += 1;
+                self.segment_offset = 0;
+            }
+        }
+        Ok(written)
+    }
+}
+
+impl std::io::Seek for SegmentedCdrReader {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        let target = match pos {
+            std::io::SeekFrom::Start(offset) => offset as i64,
+            std::io::SeekFrom::End(offset) => self.total_len as i64 + offset,
+            std::io::SeekFrom::Current(offset) => self.pos as i64 + offset,
+        };
+        if target < 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "cannot seek before the start of a SegmentedCdrReader",
+            ));
+        }
+        self.seek_to(target as u64);
+        Ok(self.pos)
+    }
+}
+
+/// A single `(pointer, length)` frame descriptor passed across the FFI boundary to
+/// [`rs_libp2p_cdr_buffer_read_new_segmented`].
+#[repr(C)]
+pub struct CdrSegmentView {
+    pub data: *const u8,
+    pub len: usize,
+}
+
+/// Creates a new [`SegmentedCdrReader`] over `count` caller-owned frames, read in order with no
+/// copy or concatenation. Decode off it with the `read_segmented_*` functions below, which call
+/// `cdr::deserialize_from` directly against the reader the same way the other buffer wrappers
+/// do internally.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers. The caller must keep every segment's
+/// memory valid and unchanged for the lifetime of the returned reader.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_new_segmented(
+    segments: *const CdrSegmentView,
+    count: usize,
+) -> *mut SegmentedCdrReader {
+    let views: &[CdrSegmentView] = if count == 0 || segments.is_null() {
+        &[]
+    } else {
+        unsafe { slice::from_raw_parts(segments, count) }
+    };
+    let segments = views
+        .iter()
+        .map(|view| CdrSegment {
+            data: view.data,
+            len: view.len,
+        })
+        .collect();
+    Box::into_raw(Box::new(SegmentedCdrReader::new(segments)))
+}
+
+/// Frees a [`SegmentedCdrReader`]. This never frees the underlying caller-owned frame memory,
+/// only the small struct tracking segment/position bookkeeping.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_segmented(ptr: *mut SegmentedCdrReader) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe { drop(Box::from_raw(ptr)) };
+}
+
+/// Seeks within a [`SegmentedCdrReader`], recomputing the current segment/offset pair. `whence`
+/// is `0` for `SeekFrom::Start`, `1` for `SeekFrom::Current`, `2` for `SeekFrom::End`, matching
+/// `SEEK_SET`/`SEEK_CUR`/`SEEK_END`. The resulting absolute position, clamped to
+/// `[0, total length]`, is written through `out_pos`.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_seek_segmented(
+    ptr: *mut SegmentedCdrReader,
+    whence: i32,
+    offset: i64,
+    out_pos: *mut u64,
+) -> i32 {
+    if ptr.is_null() || out_pos.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_seek_segmented".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let reader = unsafe { &mut *ptr };
+    let seek_from = match whence {
+        0 => std::io::SeekFrom::Start(offset as u64),
+        1 => std::io::SeekFrom::Current(offset),
+        2 => std::io::SeekFrom::End(offset),
+        _ => {
+            set_last_error(format!(
+                "rs_libp2p_cdr_buffer_seek_segmented: invalid whence {whence}"
+            ));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    match std::io::Seek::seek(reader, seek_from) {
+        Ok(pos) => {
+            unsafe { *out_pos = pos };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_seek_segmented: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a `u32` from a [`SegmentedCdrReader`], decoding directly off the underlying frames.
+/// Returns a [`CdrBufferStatus`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_segmented_uint32(
+    ptr: *mut SegmentedCdrReader,
+    n: *mut u32,
+) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_segmented_uint32".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let reader = unsafe { &mut *ptr };
+    match cdr::deserialize_from::<_, u32, _>(reader, cdr::Infinite) {
+        Ok(value) => {
+            unsafe { *n = value };
+            CdrBufferStatus::Ok as i32
+        }
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_segmented_uint32: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a `CString` from a [`SegmentedCdrReader`], decoding directly off the underlying frames.
+/// Returns a [`CdrBufferStatus`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_segmented_string(
+    ptr: *mut SegmentedCdrReader,
+    s: *mut *const c_char,
+    size: *mut usize,
+) -> i32 {
+    if ptr.is_null() || s.is_null() || size.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_segmented_string".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let reader = unsafe { &mut *ptr };
+    let cs = match cdr::deserialize_from::<_, CString, _>(reader, cdr::Infinite) {
+        Ok(cs) => cs,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_segmented_string: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let len = cs.as_bytes().len();
+    unsafe {
+        *size = len;
+        if len != 0 {
+            *s = cs.into_raw();
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// The fixed size of each chunk in a [`ChunkedCdrWriteBuffer`]'s chain.
+const CDR_CHUNK_SIZE: usize = 4096;
+
+/// A single reference-counted chunk in a [`ChunkedCdrWriteBuffer`]'s chain. The `Rc` lets a
+/// chunk be handed to a transport for vectored I/O without copying its bytes.
+struct CdrChunk {
+    bytes: Rc<RefCell<Vec<u8>>>,
+}
+
+/// A write buffer that grows by appending fixed-size, reference-counted chunks instead of
+/// reallocating and copying one contiguous `Vec<u8>`, so serializing a large point cloud or
+/// image never re-copies bytes it already wrote. [`Self::as_contiguous`] flattens the chain into
+/// a single buffer only when a transport demands it; otherwise the chunk chain itself can be
+/// sent via vectored I/O through [`Self::scatter_views`].
+pub struct ChunkedCdrWriteBuffer {
+    chunks: Vec<CdrChunk>,
+    total_len: usize,
+}
+
+impl ChunkedCdrWriteBuffer {
+    fn new() -> Self {
+        ChunkedCdrWriteBuffer {
+            chunks: vec![CdrChunk {
+                bytes: Rc::new(RefCell::new(Vec::with_capacity(CDR_CHUNK_SIZE))),
+            }],
+            total_len: 0,
+        }
+    }
+
+    /// Copies every chunk's bytes into one contiguous buffer. The chunk chain itself is left
+    /// untouched, so callers that only need vectored I/O never pay this copy.
+    fn as_contiguous(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.total_len);
+        for chunk in &self.chunks {
+            out.extend_from_slice(&chunk.bytes.borrow());
+        }
+        out
+    }
+
+    /// Returns the chunk chain as `(pointer, length)` views suitable for vectored I/O, without
+    /// copying any bytes.
+    fn scatter_views(&self) -> Vec<(*const u8, usize)> {
+        self.chunks
+            .iter()
+            .map(|chunk| {
+                let bytes = chunk.bytes.borrow();
+                (bytes.as_ptr(), bytes.len())
+            })
+            .collect()
+    }
+}
+
+impl std::io::Write for ChunkedCdrWriteBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut written = 0;
+        while written < buf.len() {
+            let current = self
+                .chunks
+                .last()
+                .expect("ChunkedCdrWriteBuffer always holds at least one chunk");
+            let space = CDR_CHUNK_SIZE - current.bytes.borrow().len();
+            if space == 0 {
+                self.chunks.push(CdrChunk {
+                    bytes: Rc::new(RefCell::new(Vec::with_capacity(CDR_CHUNK_SIZE))),
+                });
+                continue;
+            }
+            let to_copy = space.min(buf.len() - written);
+            current
+                .bytes
+                .borrow_mut()
+                .extend_from_slice(&buf[written..written + to_copy]);
+            written += to_copy;
+        }
+        self.total_len += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Creates a new [`ChunkedCdrWriteBuffer`].
+#[unsafe(no_mangle)]
+pub extern "C" fn rs_libp2p_cdr_buffer_write_new_chunked() -> *mut ChunkedCdrWriteBuffer {
+    Box::into_raw(Box::new(ChunkedCdrWriteBuffer::new()))
+}
+
+/// Frees a [`ChunkedCdrWriteBuffer`] and every chunk in its chain.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_chunked(ptr: *mut ChunkedCdrWriteBuffer) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe { drop(Box::from_raw(ptr)) };
+}
+
+/// Copies every chunk in `ptr`'s chain into one freshly allocated contiguous buffer, returning
+/// its pointer/length through `out`/`out_len`. Use this only when the transport cannot consume
+/// the scatter list directly; prefer `rs_libp2p_cdr_buffer_chunked_scatter_count` and
+/// `rs_libp2p_cdr_buffer_chunked_scatter_view` for vectored I/O. The returned buffer must be
+/// released with [`rs_libp2p_cdr_buffer_free_flattened_chunked`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_flatten_chunked(
+    ptr: *mut ChunkedCdrWriteBuffer,
+    out: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_len.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_flatten_chunked".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &*ptr };
+    let flat = buffer.as_contiguous().into_boxed_slice();
+    let len = flat.len();
+    let raw = Box::into_raw(flat) as *mut u8;
+    unsafe {
+        *out_len = len;
+        *out = raw;
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Frees a buffer previously returned by [`rs_libp2p_cdr_buffer_flatten_chunked`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_flattened_chunked(ptr: *mut u8, len: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe { drop(Box::from_raw(slice::from_raw_parts_mut(ptr, len))) };
+}
+
+/// Returns the number of scatter-list segments currently in `ptr`'s chain, for iterating with
+/// [`rs_libp2p_cdr_buffer_chunked_scatter_view`]. Returns `0` for a null pointer.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_chunked_scatter_count(
+    ptr: *const ChunkedCdrWriteBuffer,
+) -> usize {
+    if ptr.is_null() {
+        return 0;
+    }
+    unsafe { &*ptr }.chunks.len()
+}
+
+/// Writes the `index`th scatter-list segment's pointer/length through `out_data`/`out_len`
+/// without copying, for vectored I/O. Returns a [`CdrBufferStatus`]; `Underrun` if `index` is
+/// out of range.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_chunked_scatter_view(
+    ptr: *mut ChunkedCdrWriteBuffer,
+    index: usize,
+    out_data: *mut *const u8,
+    out_len: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out_data.is_null() || out_len.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_chunked_scatter_view".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &*ptr };
+    match buffer.scatter_views().get(index) {
+        Some(&(data, len)) => {
+            unsafe {
+                *out_data = data;
+                *out_len = len;
+            }
+            CdrBufferStatus::Ok as i32
+        }
+        None => {
+            set_last_error(format!(
+                "rs_libp2p_cdr_buffer_chunked_scatter_view: index {index} out of range"
+            ));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Writes a `u32` to a [`ChunkedCdrWriteBuffer`], appending into its current chunk and
+/// allocating a new chunk on overflow instead of reallocating a single growing buffer.
+/// Returns a [`CdrBufferStatus`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_chunked_uint32(
+    ptr: *mut ChunkedCdrWriteBuffer,
+    n: u32,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_chunked_uint32".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(buffer, &n, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_chunked_uint32: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Writes a string to a [`ChunkedCdrWriteBuffer`]. Returns a [`CdrBufferStatus`]: `NullPointer`
+/// if `ptr` is null, `InteriorNul` if `s` contains a NUL byte before `size`, or `Underrun` if
+/// the underlying serialization fails.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_chunked_string(
+    ptr: *mut ChunkedCdrWriteBuffer,
+    s: *const c_char,
+    size: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_chunked_string".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let cstring = if size == 0 || s.is_null() {
+        CString::new("").unwrap()
+    } else {
+        let cs = unsafe { CStr::from_ptr(s) };
+        match CString::new(cs.to_bytes()) {
+            Ok(cstring) => cstring,
+            Err(err) => {
+                set_last_error(format!("rs_libp2p_cdr_buffer_write_chunked_string: {err}"));
+                return CdrBufferStatus::InteriorNul as i32;
+            }
+        }
+    };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(buffer, &cstring, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_chunked_string: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// The codec tag written as the first byte of a buffer produced by
+/// [`rs_libp2p_cdr_buffer_compress`], read back by [`rs_libp2p_cdr_buffer_decompress`] to know
+/// how to restore the original bytes.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CdrCompressionCodec {
+    /// The payload is stored uncompressed, still framed with the tag/length header.
+    None = 0,
+    /// The payload was compressed with zlib/deflate.
+    Zlib = 1,
+    /// The payload was compressed with Snappy.
+    Snappy = 2,
+}
+
+/// Compresses the bytes currently in `ptr` with `codec` (`0` = none, `1` = zlib/deflate, `2` =
+/// Snappy), replacing `ptr`'s contents with a framed buffer: a 1-byte codec tag, the original
+/// uncompressed length as a little-endian `u64`, then the (possibly compressed) payload.
+///
+/// Falls back to `CdrCompressionCodec::None` whenever the compressed form is not smaller than
+/// the original, so small messages are never penalized with a second, larger copy.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_compress(ptr: *mut Cursor<Vec<u8>>, codec: i32) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_compress".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let requested = match codec {
+        0 => CdrCompressionCodec::None,
+        1 => CdrCompressionCodec::Zlib,
+        2 => CdrCompressionCodec::Snappy,
+        _ => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_compress: unknown codec {codec}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let buffer = unsafe { &mut *ptr };
+    let original = buffer.get_ref().clone();
+
+    let compressed = match requested {
+        CdrCompressionCodec::None => None,
+        CdrCompressionCodec::Zlib => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            match std::io::Write::write_all(&mut encoder, &original).and_then(|_| encoder.finish()) {
+                Ok(bytes) => Some(bytes),
+                Err(err) => {
+                    set_last_error(format!("rs_libp2p_cdr_buffer_compress: {err}"));
+                    return CdrBufferStatus::Underrun as i32;
+                }
+            }
+        }
+        CdrCompressionCodec::Snappy => match SnapEncoder::new().compress_vec(&original) {
+            Ok(bytes) => Some(bytes),
+            Err(err) => {
+                set_last_error(format!("rs_libp2p_cdr_buffer_compress: {err}"));
+                return CdrBufferStatus::Underrun as i32;
+            }
+        },
+    };
+
+    let (codec, payload) = match compressed {
+        Some(bytes) if bytes.len() < original.len() => (requested, bytes),
+        _ => (CdrCompressionCodec::None, original.clone()),
+    };
+
+    let mut framed = Vec::with_capacity(1 + 8 + payload.len());
+    framed.push(codec as u8);
+    framed.extend_from_slice(&(original.len() as u64).to_le_bytes());
+    framed.extend_from_slice(&payload);
+
+    *buffer = Cursor::new(framed);
+    CdrBufferStatus::Ok as i32
+}
+
+/// Reverses [`rs_libp2p_cdr_buffer_compress`]: reads the codec tag and original length from the
+/// front of `ptr`'s bytes, decompresses (or copies, for `None`) exactly that many bytes, and
+/// replaces `ptr`'s contents with the restored buffer, positioned at the start so the usual
+/// `read_*` functions can run against it unchanged.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_decompress(ptr: *mut Cursor<Vec<u8>>) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_decompress".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let framed = buffer.get_ref();
+    if framed.len() < 9 {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_decompress: frame too short for codec header".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+    let codec_tag = framed[0];
+    let original_len = u64::from_le_bytes(framed[1..9].try_into().unwrap()) as usize;
+    let payload = &framed[9..];
+
+    let restored = match codec_tag {
+        0 => payload.to_vec(),
+        1 => {
+            let mut decoder = ZlibDecoder::new(payload);
+            let mut out = Vec::with_capacity(original_len);
+            if let Err(err) = std::io::Read::read_to_end(&mut decoder, &mut out) {
+                set_last_error(format!("rs_libp2p_cdr_buffer_decompress: {err}"));
+                return CdrBufferStatus::Underrun as i32;
+            }
+            out
+        }
+        2 => match SnapDecoder::new().decompress_vec(payload) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                set_last_error(format!("rs_libp2p_cdr_buffer_decompress: {err}"));
+                return CdrBufferStatus::Underrun as i32;
+            }
+        },
+        other => {
+            set_last_error(format!(
+                "rs_libp2p_cdr_buffer_decompress: unknown codec tag {other}"
+            ));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+
+    if restored.len() != original_len {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_decompress: decompressed length mismatch".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+
+    *buffer = Cursor::new(restored);
+    CdrBufferStatus::Ok as i32
+}
+
+/// The maximum number of bytes a 64-bit LEB128 varint can occupy (10 groups of 7 bits covers
+/// the full 64-bit range); used as an overflow guard by the `read_varint_*` functions below.
+const VARINT_MAX_BYTES: u32 = 10;
+
+/// Writes `value` as an unsigned LEB128 varint: each byte holds 7 value bits in its low bits,
+/// with the high bit set whenever more bytes follow. This is not a DDS/CDR-interop encoding —
+/// it's for the crate's own peer-discovery and QoS-metadata frames, which carry many small
+/// integers and benefit from a compact header.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_varint_u64(
+    ptr: *mut Cursor<Vec<u8>>,
+    value: u64,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_varint_u64".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let mut value = value;
+    let mut bytes = Vec::new();
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        bytes.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+    match std::io::Write::write_all(buffer, &bytes) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_varint_u64: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads an unsigned LEB128 varint written by [`rs_libp2p_cdr_buffer_write_varint_u64`].
+/// Returns a [`CdrBufferStatus`]; `Underrun` if the buffer ends mid-varint or the varint
+/// exceeds [`VARINT_MAX_BYTES`] bytes without terminating (an overflow guard against a hostile
+/// frame with the continuation bit set forever).
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_varint_u64(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut u64,
+) -> i32 {
+    if ptr.is_null() || out.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_varint_u64".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let mut result: u64 = 0;
+    let mut shift: u32 = 0;
+    for _ in 0..VARINT_MAX_BYTES {
+        let mut byte = [0u8; 1];
+        if let Err(err) = std::io::Read::read_exact(buffer, &mut byte) {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_varint_u64: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+        let byte = byte[0];
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            unsafe { *out = result };
+            return CdrBufferStatus::Ok as i32;
+        }
+        shift += 7;
+    }
+    set_last_error(format!(
+        "rs_libp2p_cdr_buffer_read_varint_u64: varint exceeds {VARINT_MAX_BYTES} bytes"
+    ));
+    CdrBufferStatus::Underrun as i32
+}
+
+/// Writes `value` as a sign-extending LEB128 varint: after writing each 7-bit group, encoding
+/// continues only until the remaining bits are all zero with the group's sign bit (`0x40`)
+/// clear, or all one with the sign bit set — i.e. until the written groups alone are enough to
+/// sign-extend back to `value`.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_varint_i64(
+    ptr: *mut Cursor<Vec<u8>>,
+    value: i64,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_varint_i64".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let mut value = value;
+    let mut bytes = Vec::new();
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        let sign_bit_set = byte & 0x40 != 0;
+        if (value == 0 && !sign_bit_set) || (value == -1 && sign_bit_set) {
+            bytes.push(byte);
+            break;
+        }
+        bytes.push(byte | 0x80);
+    }
+    match std::io::Write::write_all(buffer, &bytes) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_varint_i64: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a sign-extending LEB128 varint written by [`rs_libp2p_cdr_buffer_write_varint_i64`]:
+/// if the final group's `0x40` bit is set and fewer than the full 64 bits were read, the high
+/// bits are sign-extended. Returns a [`CdrBufferStatus`]; `Underrun` if the buffer ends mid-varint
+/// or the varint exceeds [`VARINT_MAX_BYTES`] bytes without terminating.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_varint_i64(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut i64,
+) -> i32 {
+    if ptr.is_null() || out.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_varint_i64".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let mut result: i64 = 0;
+    let mut shift: u32 = 0;
+    for _ in 0..VARINT_MAX_BYTES {
+        let mut byte = [0u8; 1];
+        if let Err(err) = std::io::Read::read_exact(buffer, &mut byte) {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_varint_i64: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+        let byte = byte[0];
+        result |= ((byte & 0x7f) as i64) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            if shift < 64 && (byte & 0x40) != 0 {
+                result |= -(1i64 << shift);
+            }
+            unsafe { *out = result };
+            return CdrBufferStatus::Ok as i32;
+        }
+    }
+    set_last_error(format!(
+        "rs_libp2p_cdr_buffer_read_varint_i64: varint exceeds {VARINT_MAX_BYTES} bytes"
+    ));
+    CdrBufferStatus::Underrun as i32
+}
+
+/// Writes a contiguous `u8` sequence to a `Cursor<Vec<u8>>` in one call: a `u32` element count
+/// followed by the packed, CDR-aligned elements, matching the layout `cdr` uses for `Vec<u8>`.
+/// This turns what would otherwise be `count` individual FFI crossings into one.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint8_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const u8,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_uint8_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let vec: Vec<u8> = if count == 0 || data.is_null() {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(data, count) }.to_vec()
+    };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &vec, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_uint8_array: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a contiguous `u8` sequence written by [`rs_libp2p_cdr_buffer_write_uint8_array`] in one call: the `u32` element
+/// count is read first, then the packed elements are bulk-copied into a freshly allocated buffer
+/// whose pointer and length are written through `out`/`out_count`.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out`/`out_count` are left
+/// untouched. The returned buffer must be released with [`rs_libp2p_cdr_buffer_free_uint8_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint8_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut *mut u8,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_uint8_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let values = match cdr::deserialize_from::<_, Vec<u8>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(values) => values,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_uint8_array: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut u8;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Frees a `u8` array allocated by [`rs_libp2p_cdr_buffer_read_uint8_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Does nothing if the provided pointer is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_uint8_array(ptr: *mut u8, count: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, count));
+    }
+}
+
+
+/// Writes a contiguous `u16` sequence to a `Cursor<Vec<u8>>` in one call: a `u32` element count
+/// followed by the packed, CDR-aligned elements, matching the layout `cdr` uses for `Vec<u16>`.
+/// This turns what would otherwise be `count` individual FFI crossings into one.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint16_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const u16,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_uint16_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let vec: Vec<u16> = if count == 0 || data.is_null() {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(data, count) }.to_vec()
+    };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &vec, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_uint16_array: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a contiguous `u16` sequence written by [`rs_libp2p_cdr_buffer_write_uint16_array`] in one call: the `u32` element
+/// count is read first, then the packed elements are bulk-copied into a freshly allocated buffer
+/// whose pointer and length are written through `out`/`out_count`.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out`/`out_count` are left
+/// untouched. The returned buffer must be released with [`rs_libp2p_cdr_buffer_free_uint16_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint16_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut *mut u16,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_uint16_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let values = match cdr::deserialize_from::<_, Vec<u16>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(values) => values,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_uint16_array: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut u16;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Frees a `u16` array allocated by [`rs_libp2p_cdr_buffer_read_uint16_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Does nothing if the provided pointer is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_uint16_array(ptr: *mut u16, count: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, count));
+    }
+}
+
+
+/// Writes a contiguous `u32` sequence to a `Cursor<Vec<u8>>` in one call: a `u32` element count
+/// followed by the packed, CDR-aligned elements, matching the layout `cdr` uses for `Vec<u32>`.
+/// This turns what would otherwise be `count` individual FFI crossings into one.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint32_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const u32,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_uint32_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let vec: Vec<u32> = if count == 0 || data.is_null() {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(data, count) }.to_vec()
+    };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &vec, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_uint32_array: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a contiguous `u32` sequence written by [`rs_libp2p_cdr_buffer_write_uint32_array`] in one call: the `u32` element
+/// count is read first, then the packed elements are bulk-copied into a freshly allocated buffer
+/// whose pointer and length are written through `out`/`out_count`.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out`/`out_count` are left
+/// untouched. The returned buffer must be released with [`rs_libp2p_cdr_buffer_free_uint32_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint32_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut *mut u32,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_uint32_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let values = match cdr::deserialize_from::<_, Vec<u32>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(values) => values,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_uint32_array: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut u32;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Frees a `u32` array allocated by [`rs_libp2p_cdr_buffer_read_uint32_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Does nothing if the provided pointer is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_uint32_array(ptr: *mut u32, count: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, count));
+    }
+}
+
+
+/// Writes a contiguous `u64` sequence to a `Cursor<Vec<u8>>` in one call: a `u32` element count
+/// followed by the packed, CDR-aligned elements, matching the layout `cdr` uses for `Vec<u64>`.
+/// This turns what would otherwise be `count` individual FFI crossings into one.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint64_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const u64,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_uint64_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let vec: Vec<u64> = if count == 0 || data.is_null() {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(data, count) }.to_vec()
+    };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &vec, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_uint64_array: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a contiguous `u64` sequence written by [`rs_libp2p_cdr_buffer_write_uint64_array`] in one call: the `u32` element
+/// count is read first, then the packed elements are bulk-copied into a freshly allocated buffer
+/// whose pointer and length are written through `out`/`out_count`.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out`/`out_count` are left
+/// untouched. The returned buffer must be released with [`rs_libp2p_cdr_buffer_free_uint64_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint64_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut *mut u64,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_uint64_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let values = match cdr::deserialize_from::<_, Vec<u64>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(values) => values,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_uint64_array: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut u64;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Frees a `u64` array allocated by [`rs_libp2p_cdr_buffer_read_uint64_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Does nothing if the provided pointer is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_uint64_array(ptr: *mut u64, count: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, count));
+    }
+}
+
+
+/// Writes a contiguous `i8` sequence to a `Cursor<Vec<u8>>` in one call: a `u32` element count
+/// followed by the packed, CDR-aligned elements, matching the layout `cdr` uses for `Vec<i8>`.
+/// This turns what would otherwise be `count` individual FFI crossings into one.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int8_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const i8,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_int8_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let vec: Vec<i8> = if count == 0 || data.is_null() {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(data, count) }.to_vec()
+    };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &vec, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_int8_array: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a contiguous `i8` sequence written by [`rs_libp2p_cdr_buffer_write_int8_array`] in one call: the `u32` element
+/// count is read first, then the packed elements are bulk-copied into a freshly allocated buffer
+/// whose pointer and length are written through `out`/`out_count`.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out`/`out_count` are left
+/// untouched. The returned buffer must be released with [`rs_libp2p_cdr_buffer_free_int8_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int8_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut *mut i8,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_int8_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let values = match cdr::deserialize_from::<_, Vec<i8>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(values) => values,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_int8_array: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut i8;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Frees a `i8` array allocated by [`rs_libp2p_cdr_buffer_read_int8_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Does nothing if the provided pointer is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_int8_array(ptr: *mut i8, count: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, count));
+    }
+}
+
+
+/// Writes a contiguous `i16` sequence to a `Cursor<Vec<u8>>` in one call: a `u32` element count
+/// followed by the packed, CDR-aligned elements, matching the layout `cdr` uses for `Vec<i16>`.
+/// This turns what would otherwise be `count` individual FFI crossings into one.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int16_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const i16,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_int16_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let vec: Vec<i16> = if count == 0 || data.is_null() {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(data, count) }.to_vec()
+    };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &vec, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_int16_array: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a contiguous `i16` sequence written by [`rs_libp2p_cdr_buffer_write_int16_array`] in one call: the `u32` element
+/// count is read first, then the packed elements are bulk-copied into a freshly allocated buffer
+/// whose pointer and length are written through `out`/`out_count`.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out`/`out_count` are left
+/// untouched. The returned buffer must be released with [`rs_libp2p_cdr_buffer_free_int16_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int16_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut *mut i16,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_int16_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let values = match cdr::deserialize_from::<_, Vec<i16>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(values) => values,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_int16_array: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut i16;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Frees a `i16` array allocated by [`rs_libp2p_cdr_buffer_read_int16_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Does nothing if the provided pointer is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_int16_array(ptr: *mut i16, count: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, count));
+    }
+}
+
+
+/// Writes a contiguous `i32` sequence to a `Cursor<Vec<u8>>` in one call: a `u32` element count
+/// followed by the packed, CDR-aligned elements, matching the layout `cdr` uses for `Vec<i32>`.
+/// This turns what would otherwise be `count` individual FFI crossings into one.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int32_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const i32,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_int32_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let vec: Vec<i32> = if count == 0 || data.is_null() {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(data, count) }.to_vec()
+    };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &vec, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_int32_array: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a contiguous `i32` sequence written by [`rs_libp2p_cdr_buffer_write_int32_array`] in one call: the `u32` element
+/// count is read first, then the packed elements are bulk-copied into a freshly allocated buffer
+/// whose pointer and length are written through `out`/`out_count`.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out`/`out_count` are left
+/// untouched. The returned buffer must be released with [`rs_libp2p_cdr_buffer_free_int32_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int32_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut *mut i32,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_int32_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let values = match cdr::deserialize_from::<_, Vec<i32>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(values) => values,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_int32_array: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut i32;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Frees a `i32` array allocated by [`rs_libp2p_cdr_buffer_read_int32_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Does nothing if the provided pointer is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_int32_array(ptr: *mut i32, count: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, count));
+    }
+}
+
+
+/// Writes a contiguous `i64` sequence to a `Cursor<Vec<u8>>` in one call: a `u32` element count
+/// followed by the packed, CDR-aligned elements, matching the layout `cdr` uses for `Vec<i64>`.
+/// This turns what would otherwise be `count` individual FFI crossings into one.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int64_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const i64,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_int64_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let vec: Vec<i64> = if count == 0 || data.is_null() {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(data, count) }.to_vec()
+    };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &vec, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_int64_array: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a contiguous `i64` sequence written by [`rs_libp2p_cdr_buffer_write_int64_array`] in one call: the `u32` element
+/// count is read first, then the packed elements are bulk-copied into a freshly allocated buffer
+/// whose pointer and length are written through `out`/`out_count`.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out`/`out_count` are left
+/// untouched. The returned buffer must be released with [`rs_libp2p_cdr_buffer_free_int64_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int64_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut *mut i64,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_int64_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let values = match cdr::deserialize_from::<_, Vec<i64>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(values) => values,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_int64_array: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut i64;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Frees a `i64` array allocated by [`rs_libp2p_cdr_buffer_read_int64_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Does nothing if the provided pointer is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_int64_array(ptr: *mut i64, count: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, count));
+    }
+}
+
+
+/// Writes a contiguous `f32` sequence to a `Cursor<Vec<u8>>` in one call: a `u32` element count
+/// followed by the packed, CDR-aligned elements, matching the layout `cdr` uses for `Vec<f32>`.
+/// This turns what would otherwise be `count` individual FFI crossings into one.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_float_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const f32,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_float_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let vec: Vec<f32> = if count == 0 || data.is_null() {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(data, count) }.to_vec()
+    };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &vec, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_float_array: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a contiguous `f32` sequence written by [`rs_libp2p_cdr_buffer_write_float_array`] in one call: the `u32` element
+/// count is read first, then the packed elements are bulk-copied into a freshly allocated buffer
+/// whose pointer and length are written through `out`/`out_count`.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out`/`out_count` are left
+/// untouched. The returned buffer must be released with [`rs_libp2p_cdr_buffer_free_float_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_float_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut *mut f32,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_float_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let values = match cdr::deserialize_from::<_, Vec<f32>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(values) => values,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_float_array: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut f32;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Frees a `f32` array allocated by [`rs_libp2p_cdr_buffer_read_float_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Does nothing if the provided pointer is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_float_array(ptr: *mut f32, count: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, count));
+    }
+}
+
+
+/// Writes a contiguous `f64` sequence to a `Cursor<Vec<u8>>` in one call: a `u32` element count
+/// followed by the packed, CDR-aligned elements, matching the layout `cdr` uses for `Vec<f64>`.
+/// This turns what would otherwise be `count` individual FFI crossings into one.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null, or
+/// `Underrun` if the underlying serialization fails.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_double_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const f64,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_write_double_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let vec: Vec<f64> = if count == 0 || data.is_null() {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(data, count) }.to_vec()
+    };
+    match cdr::serialize_into::<_, _, _, cdr::CdrBe>(libp2p_cdr_buffer, &vec, cdr::Infinite) {
+        Ok(_) => CdrBufferStatus::Ok as i32,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_write_double_array: {err}"));
+            CdrBufferStatus::Underrun as i32
+        }
+    }
+}
+
+/// Reads a contiguous `f64` sequence written by [`rs_libp2p_cdr_buffer_write_double_array`] in one call: the `u32` element
+/// count is read first, then the packed elements are bulk-copied into a freshly allocated buffer
+/// whose pointer and length are written through `out`/`out_count`.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out`/`out_count` are left
+/// untouched. The returned buffer must be released with [`rs_libp2p_cdr_buffer_free_double_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_double_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut *mut f64,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_double_array".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    let values = match cdr::deserialize_from::<_, Vec<f64>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(values) => values,
+        Err(err) => {
+            set_last_error(format!("rs_libp2p_cdr_buffer_read_double_array: {err}"));
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut f64;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Frees a `f64` array allocated by [`rs_libp2p_cdr_buffer_read_double_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Does nothing if the provided pointer is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_double_array(ptr: *mut f64, count: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, count));
+    }
+}
+
+
+/// Aligns `cursor`'s write position up to a multiple of `width` bytes, padding with zero bytes.
+/// The fixed-size array writers below align once before their first element; since every element
+/// of such an array shares the same width, position stays aligned for every subsequent element
+/// with no further padding needed between them.
+fn align_cursor_write(cursor: &mut Cursor<Vec<u8>>, width: usize) {
+    let pos = cursor.position() as usize;
+    let padding = (width - (pos % width)) % width;
+    for _ in 0..padding {
+        cursor.get_mut().push(0);
+    }
+    cursor.set_position((pos + padding) as u64);
+}
+
+/// Advances `cursor`'s read position up to a multiple of `width` bytes, mirroring
+/// [`align_cursor_write`] on the read side.
+fn align_cursor_read(cursor: &mut Cursor<Vec<u8>>, width: usize) {
+    let pos = cursor.position() as usize;
+    let padding = (width - (pos % width)) % width;
+    cursor.set_position((pos + padding) as u64);
+}
+
+
+/// Writes a fixed-size `i8` array of `count` elements to a `Cursor<Vec<u8>>`, with no
+/// element-count prefix: the buffer's write position is aligned to `i8`'s 1-byte width
+/// once before the first element, then every element is written packed, big-endian, with no
+/// further per-element padding (matching [`rs_libp2p_cdr_buffer_write_int8_array`]'s element
+/// layout, minus its `u32` length prefix). Unlike that sequence writer, the caller is responsible
+/// for communicating `count` out of band — e.g. the IDL's fixed array bound — since the wire
+/// format carries none.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int8_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const i8,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_write_int8_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_write(cursor, 1);
+    if count != 0 && !data.is_null() {
+        let values = unsafe { slice::from_raw_parts(data, count) };
+        for value in values {
+            cursor.get_mut().extend_from_slice(&value.to_be_bytes());
+        }
+        cursor.set_position(cursor.position() + (count * 1) as u64);
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Reads a fixed-size `i8` array of `count` elements (no length prefix) written by
+/// [`rs_libp2p_cdr_buffer_write_int8_fixed_array`], bulk-copying into a freshly allocated buffer
+/// whose pointer is written through `out`. The caller supplies `count`, since the wire format
+/// carries none.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out` is left untouched.
+/// `Underrun` if fewer than `count` elements remain in the buffer. The returned buffer must be
+/// released with [`rs_libp2p_cdr_buffer_free_int8_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int8_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    count: usize,
+    out: *mut *mut i8,
+) -> i32 {
+    if ptr.is_null() || out.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_int8_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_read(cursor, 1);
+    let needed = count * 1;
+    let pos = cursor.position() as usize;
+    if cursor.get_ref().len() < pos + needed {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_int8_fixed_array: buffer underrun".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+    let mut values: Vec<i8> = Vec::with_capacity(count);
+    {
+        let buf = cursor.get_ref();
+        for i in 0..count {
+            let start = pos + i * 1;
+            values.push(i8::from_be_bytes(buf[start..start + 1].try_into().unwrap()));
+        }
+    }
+    cursor.set_position((pos + needed) as u64);
+    unsafe {
+        if count != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut i8;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a fixed-size `i16` array of `count` elements to a `Cursor<Vec<u8>>`, with no
+/// element-count prefix: the buffer's write position is aligned to `i16`'s 2-byte width
+/// once before the first element, then every element is written packed, big-endian, with no
+/// further per-element padding (matching [`rs_libp2p_cdr_buffer_write_int16_array`]'s element
+/// layout, minus its `u32` length prefix). Unlike that sequence writer, the caller is responsible
+/// for communicating `count` out of band — e.g. the IDL's fixed array bound — since the wire
+/// format carries none.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int16_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const i16,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_write_int16_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_write(cursor, 2);
+    if count != 0 && !data.is_null() {
+        let values = unsafe { slice::from_raw_parts(data, count) };
+        for value in values {
+            cursor.get_mut().extend_from_slice(&value.to_be_bytes());
+        }
+        cursor.set_position(cursor.position() + (count * 2) as u64);
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Reads a fixed-size `i16` array of `count` elements (no length prefix) written by
+/// [`rs_libp2p_cdr_buffer_write_int16_fixed_array`], bulk-copying into a freshly allocated buffer
+/// whose pointer is written through `out`. The caller supplies `count`, since the wire format
+/// carries none.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out` is left untouched.
+/// `Underrun` if fewer than `count` elements remain in the buffer. The returned buffer must be
+/// released with [`rs_libp2p_cdr_buffer_free_int16_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int16_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    count: usize,
+    out: *mut *mut i16,
+) -> i32 {
+    if ptr.is_null() || out.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_int16_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_read(cursor, 2);
+    let needed = count * 2;
+    let pos = cursor.position() as usize;
+    if cursor.get_ref().len() < pos + needed {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_int16_fixed_array: buffer underrun".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+    let mut values: Vec<i16> = Vec::with_capacity(count);
+    {
+        let buf = cursor.get_ref();
+        for i in 0..count {
+            let start = pos + i * 2;
+            values.push(i16::from_be_bytes(buf[start..start + 2].try_into().unwrap()));
+        }
+    }
+    cursor.set_position((pos + needed) as u64);
+    unsafe {
+        if count != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut i16;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a fixed-size `i32` array of `count` elements to a `Cursor<Vec<u8>>`, with no
+/// element-count prefix: the buffer's write position is aligned to `i32`'s 4-byte width
+/// once before the first element, then every element is written packed, big-endian, with no
+/// further per-element padding (matching [`rs_libp2p_cdr_buffer_write_int32_array`]'s element
+/// layout, minus its `u32` length prefix). Unlike that sequence writer, the caller is responsible
+/// for communicating `count` out of band — e.g. the IDL's fixed array bound — since the wire
+/// format carries none.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int32_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const i32,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_write_int32_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_write(cursor, 4);
+    if count != 0 && !data.is_null() {
+        let values = unsafe { slice::from_raw_parts(data, count) };
+        for value in values {
+            cursor.get_mut().extend_from_slice(&value.to_be_bytes());
+        }
+        cursor.set_position(cursor.position() + (count * 4) as u64);
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Reads a fixed-size `i32` array of `count` elements (no length prefix) written by
+/// [`rs_libp2p_cdr_buffer_write_int32_fixed_array`], bulk-copying into a freshly allocated buffer
+/// whose pointer is written through `out`. The caller supplies `count`, since the wire format
+/// carries none.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out` is left untouched.
+/// `Underrun` if fewer than `count` elements remain in the buffer. The returned buffer must be
+/// released with [`rs_libp2p_cdr_buffer_free_int32_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int32_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    count: usize,
+    out: *mut *mut i32,
+) -> i32 {
+    if ptr.is_null() || out.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_int32_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_read(cursor, 4);
+    let needed = count * 4;
+    let pos = cursor.position() as usize;
+    if cursor.get_ref().len() < pos + needed {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_int32_fixed_array: buffer underrun".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+    let mut values: Vec<i32> = Vec::with_capacity(count);
+    {
+        let buf = cursor.get_ref();
+        for i in 0..count {
+            let start = pos + i * 4;
+            values.push(i32::from_be_bytes(buf[start..start + 4].try_into().unwrap()));
+        }
+    }
+    cursor.set_position((pos + needed) as u64);
+    unsafe {
+        if count != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut i32;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a fixed-size `i64` array of `count` elements to a `Cursor<Vec<u8>>`, with no
+/// element-count prefix: the buffer's write position is aligned to `i64`'s 8-byte width
+/// once before the first element, then every element is written packed, big-endian, with no
+/// further per-element padding (matching [`rs_libp2p_cdr_buffer_write_int64_array`]'s element
+/// layout, minus its `u32` length prefix). Unlike that sequence writer, the caller is responsible
+/// for communicating `count` out of band — e.g. the IDL's fixed array bound — since the wire
+/// format carries none.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_int64_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const i64,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_write_int64_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_write(cursor, 8);
+    if count != 0 && !data.is_null() {
+        let values = unsafe { slice::from_raw_parts(data, count) };
+        for value in values {
+            cursor.get_mut().extend_from_slice(&value.to_be_bytes());
+        }
+        cursor.set_position(cursor.position() + (count * 8) as u64);
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Reads a fixed-size `i64` array of `count` elements (no length prefix) written by
+/// [`rs_libp2p_cdr_buffer_write_int64_fixed_array`], bulk-copying into a freshly allocated buffer
+/// whose pointer is written through `out`. The caller supplies `count`, since the wire format
+/// carries none.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out` is left untouched.
+/// `Underrun` if fewer than `count` elements remain in the buffer. The returned buffer must be
+/// released with [`rs_libp2p_cdr_buffer_free_int64_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_int64_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    count: usize,
+    out: *mut *mut i64,
+) -> i32 {
+    if ptr.is_null() || out.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_int64_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_read(cursor, 8);
+    let needed = count * 8;
+    let pos = cursor.position() as usize;
+    if cursor.get_ref().len() < pos + needed {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_int64_fixed_array: buffer underrun".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+    let mut values: Vec<i64> = Vec::with_capacity(count);
+    {
+        let buf = cursor.get_ref();
+        for i in 0..count {
+            let start = pos + i * 8;
+            values.push(i64::from_be_bytes(buf[start..start + 8].try_into().unwrap()));
+        }
+    }
+    cursor.set_position((pos + needed) as u64);
+    unsafe {
+        if count != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut i64;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a fixed-size `u8` array of `count` elements to a `Cursor<Vec<u8>>`, with no
+/// element-count prefix: the buffer's write position is aligned to `u8`'s 1-byte width
+/// once before the first element, then every element is written packed, big-endian, with no
+/// further per-element padding (matching [`rs_libp2p_cdr_buffer_write_uint8_array`]'s element
+/// layout, minus its `u32` length prefix). Unlike that sequence writer, the caller is responsible
+/// for communicating `count` out of band — e.g. the IDL's fixed array bound — since the wire
+/// format carries none.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint8_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const u8,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_write_uint8_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_write(cursor, 1);
+    if count != 0 && !data.is_null() {
+        let values = unsafe { slice::from_raw_parts(data, count) };
+        for value in values {
+            cursor.get_mut().extend_from_slice(&value.to_be_bytes());
+        }
+        cursor.set_position(cursor.position() + (count * 1) as u64);
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Reads a fixed-size `u8` array of `count` elements (no length prefix) written by
+/// [`rs_libp2p_cdr_buffer_write_uint8_fixed_array`], bulk-copying into a freshly allocated buffer
+/// whose pointer is written through `out`. The caller supplies `count`, since the wire format
+/// carries none.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out` is left untouched.
+/// `Underrun` if fewer than `count` elements remain in the buffer. The returned buffer must be
+/// released with [`rs_libp2p_cdr_buffer_free_uint8_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint8_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    count: usize,
+    out: *mut *mut u8,
+) -> i32 {
+    if ptr.is_null() || out.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_uint8_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_read(cursor, 1);
+    let needed = count * 1;
+    let pos = cursor.position() as usize;
+    if cursor.get_ref().len() < pos + needed {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_uint8_fixed_array: buffer underrun".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+    let mut values: Vec<u8> = Vec::with_capacity(count);
+    {
+        let buf = cursor.get_ref();
+        for i in 0..count {
+            let start = pos + i * 1;
+            values.push(u8::from_be_bytes(buf[start..start + 1].try_into().unwrap()));
+        }
+    }
+    cursor.set_position((pos + needed) as u64);
+    unsafe {
+        if count != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut u8;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a fixed-size `u16` array of `count` elements to a `Cursor<Vec<u8>>`, with no
+/// element-count prefix: the buffer's write position is aligned to `u16`'s 2-byte width
+/// once before the first element, then every element is written packed, big-endian, with no
+/// further per-element padding (matching [`rs_libp2p_cdr_buffer_write_uint16_array`]'s element
+/// layout, minus its `u32` length prefix). Unlike that sequence writer, the caller is responsible
+/// for communicating `count` out of band — e.g. the IDL's fixed array bound — since the wire
+/// format carries none.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint16_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const u16,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_write_uint16_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_write(cursor, 2);
+    if count != 0 && !data.is_null() {
+        let values = unsafe { slice::from_raw_parts(data, count) };
+        for value in values {
+            cursor.get_mut().extend_from_slice(&value.to_be_bytes());
+        }
+        cursor.set_position(cursor.position() + (count * 2) as u64);
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Reads a fixed-size `u16` array of `count` elements (no length prefix) written by
+/// [`rs_libp2p_cdr_buffer_write_uint16_fixed_array`], bulk-copying into a freshly allocated buffer
+/// whose pointer is written through `out`. The caller supplies `count`, since the wire format
+/// carries none.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out` is left untouched.
+/// `Underrun` if fewer than `count` elements remain in the buffer. The returned buffer must be
+/// released with [`rs_libp2p_cdr_buffer_free_uint16_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint16_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    count: usize,
+    out: *mut *mut u16,
+) -> i32 {
+    if ptr.is_null() || out.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_uint16_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_read(cursor, 2);
+    let needed = count * 2;
+    let pos = cursor.position() as usize;
+    if cursor.get_ref().len() < pos + needed {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_uint16_fixed_array: buffer underrun".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+    let mut values: Vec<u16> = Vec::with_capacity(count);
+    {
+        let buf = cursor.get_ref();
+        for i in 0..count {
+            let start = pos + i * 2;
+            values.push(u16::from_be_bytes(buf[start..start + 2].try_into().unwrap()));
+        }
+    }
+    cursor.set_position((pos + needed) as u64);
+    unsafe {
+        if count != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut u16;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a fixed-size `u32` array of `count` elements to a `Cursor<Vec<u8>>`, with no
+/// element-count prefix: the buffer's write position is aligned to `u32`'s 4-byte width
+/// once before the first element, then every element is written packed, big-endian, with no
+/// further per-element padding (matching [`rs_libp2p_cdr_buffer_write_uint32_array`]'s element
+/// layout, minus its `u32` length prefix). Unlike that sequence writer, the caller is responsible
+/// for communicating `count` out of band — e.g. the IDL's fixed array bound — since the wire
+/// format carries none.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint32_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const u32,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_write_uint32_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_write(cursor, 4);
+    if count != 0 && !data.is_null() {
+        let values = unsafe { slice::from_raw_parts(data, count) };
+        for value in values {
+            cursor.get_mut().extend_from_slice(&value.to_be_bytes());
+        }
+        cursor.set_position(cursor.position() + (count * 4) as u64);
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Reads a fixed-size `u32` array of `count` elements (no length prefix) written by
+/// [`rs_libp2p_cdr_buffer_write_uint32_fixed_array`], bulk-copying into a freshly allocated buffer
+/// whose pointer is written through `out`. The caller supplies `count`, since the wire format
+/// carries none.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out` is left untouched.
+/// `Underrun` if fewer than `count` elements remain in the buffer. The returned buffer must be
+/// released with [`rs_libp2p_cdr_buffer_free_uint32_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint32_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    count: usize,
+    out: *mut *mut u32,
+) -> i32 {
+    if ptr.is_null() || out.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_uint32_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_read(cursor, 4);
+    let needed = count * 4;
+    let pos = cursor.position() as usize;
+    if cursor.get_ref().len() < pos + needed {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_uint32_fixed_array: buffer underrun".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+    let mut values: Vec<u32> = Vec::with_capacity(count);
+    {
+        let buf = cursor.get_ref();
+        for i in 0..count {
+            let start = pos + i * 4;
+            values.push(u32::from_be_bytes(buf[start..start + 4].try_into().unwrap()));
+        }
+    }
+    cursor.set_position((pos + needed) as u64);
+    unsafe {
+        if count != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut u32;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a fixed-size `u64` array of `count` elements to a `Cursor<Vec<u8>>`, with no
+/// element-count prefix: the buffer's write position is aligned to `u64`'s 8-byte width
+/// once before the first element, then every element is written packed, big-endian, with no
+/// further per-element padding (matching [`rs_libp2p_cdr_buffer_write_uint64_array`]'s element
+/// layout, minus its `u32` length prefix). Unlike that sequence writer, the caller is responsible
+/// for communicating `count` out of band — e.g. the IDL's fixed array bound — since the wire
+/// format carries none.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_uint64_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const u64,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_write_uint64_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_write(cursor, 8);
+    if count != 0 && !data.is_null() {
+        let values = unsafe { slice::from_raw_parts(data, count) };
+        for value in values {
+            cursor.get_mut().extend_from_slice(&value.to_be_bytes());
+        }
+        cursor.set_position(cursor.position() + (count * 8) as u64);
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Reads a fixed-size `u64` array of `count` elements (no length prefix) written by
+/// [`rs_libp2p_cdr_buffer_write_uint64_fixed_array`], bulk-copying into a freshly allocated buffer
+/// whose pointer is written through `out`. The caller supplies `count`, since the wire format
+/// carries none.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out` is left untouched.
+/// `Underrun` if fewer than `count` elements remain in the buffer. The returned buffer must be
+/// released with [`rs_libp2p_cdr_buffer_free_uint64_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint64_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    count: usize,
+    out: *mut *mut u64,
+) -> i32 {
+    if ptr.is_null() || out.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_uint64_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_read(cursor, 8);
+    let needed = count * 8;
+    let pos = cursor.position() as usize;
+    if cursor.get_ref().len() < pos + needed {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_uint64_fixed_array: buffer underrun".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+    let mut values: Vec<u64> = Vec::with_capacity(count);
+    {
+        let buf = cursor.get_ref();
+        for i in 0..count {
+            let start = pos + i * 8;
+            values.push(u64::from_be_bytes(buf[start..start + 8].try_into().unwrap()));
+        }
+    }
+    cursor.set_position((pos + needed) as u64);
+    unsafe {
+        if count != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut u64;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a fixed-size `f32` array of `count` elements to a `Cursor<Vec<u8>>`, with no
+/// element-count prefix: the buffer's write position is aligned to `f32`'s 4-byte width
+/// once before the first element, then every element is written packed, big-endian, with no
+/// further per-element padding (matching [`rs_libp2p_cdr_buffer_write_float_array`]'s element
+/// layout, minus its `u32` length prefix). Unlike that sequence writer, the caller is responsible
+/// for communicating `count` out of band — e.g. the IDL's fixed array bound — since the wire
+/// format carries none.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_float_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const f32,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_write_float_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_write(cursor, 4);
+    if count != 0 && !data.is_null() {
+        let values = unsafe { slice::from_raw_parts(data, count) };
+        for value in values {
+            cursor.get_mut().extend_from_slice(&value.to_be_bytes());
+        }
+        cursor.set_position(cursor.position() + (count * 4) as u64);
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Reads a fixed-size `f32` array of `count` elements (no length prefix) written by
+/// [`rs_libp2p_cdr_buffer_write_float_fixed_array`], bulk-copying into a freshly allocated buffer
+/// whose pointer is written through `out`. The caller supplies `count`, since the wire format
+/// carries none.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out` is left untouched.
+/// `Underrun` if fewer than `count` elements remain in the buffer. The returned buffer must be
+/// released with [`rs_libp2p_cdr_buffer_free_float_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_float_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    count: usize,
+    out: *mut *mut f32,
+) -> i32 {
+    if ptr.is_null() || out.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_float_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_read(cursor, 4);
+    let needed = count * 4;
+    let pos = cursor.position() as usize;
+    if cursor.get_ref().len() < pos + needed {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_float_fixed_array: buffer underrun".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+    let mut values: Vec<f32> = Vec::with_capacity(count);
+    {
+        let buf = cursor.get_ref();
+        for i in 0..count {
+            let start = pos + i * 4;
+            values.push(f32::from_be_bytes(buf[start..start + 4].try_into().unwrap()));
+        }
+    }
+    cursor.set_position((pos + needed) as u64);
+    unsafe {
+        if count != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut f32;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a fixed-size `f64` array of `count` elements to a `Cursor<Vec<u8>>`, with no
+/// element-count prefix: the buffer's write position is aligned to `f64`'s 8-byte width
+/// once before the first element, then every element is written packed, big-endian, with no
+/// further per-element padding (matching [`rs_libp2p_cdr_buffer_write_double_array`]'s element
+/// layout, minus its `u32` length prefix). Unlike that sequence writer, the caller is responsible
+/// for communicating `count` out of band — e.g. the IDL's fixed array bound — since the wire
+/// format carries none.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// Returns a [`CdrBufferStatus`] instead of panicking: `NullPointer` if `ptr` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_double_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    data: *const f64,
+    count: usize,
+) -> i32 {
+    if ptr.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_write_double_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_write(cursor, 8);
+    if count != 0 && !data.is_null() {
+        let values = unsafe { slice::from_raw_parts(data, count) };
+        for value in values {
+            cursor.get_mut().extend_from_slice(&value.to_be_bytes());
+        }
+        cursor.set_position(cursor.position() + (count * 8) as u64);
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Reads a fixed-size `f64` array of `count` elements (no length prefix) written by
+/// [`rs_libp2p_cdr_buffer_write_double_fixed_array`], bulk-copying into a freshly allocated buffer
+/// whose pointer is written through `out`. The caller supplies `count`, since the wire format
+/// carries none.
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out` is left untouched.
+/// `Underrun` if fewer than `count` elements remain in the buffer. The returned buffer must be
+/// released with [`rs_libp2p_cdr_buffer_free_double_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_double_fixed_array(
+    ptr: *mut Cursor<Vec<u8>>,
+    count: usize,
+    out: *mut *mut f64,
+) -> i32 {
+    if ptr.is_null() || out.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_double_fixed_array".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let cursor = unsafe { &mut *ptr };
+    align_cursor_read(cursor, 8);
+    let needed = count * 8;
+    let pos = cursor.position() as usize;
+    if cursor.get_ref().len() < pos + needed {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_double_fixed_array: buffer underrun".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+    let mut values: Vec<f64> = Vec::with_capacity(count);
+    {
+        let buf = cursor.get_ref();
+        for i in 0..count {
+            let start = pos + i * 8;
+            values.push(f64::from_be_bytes(buf[start..start + 8].try_into().unwrap()));
+        }
+    }
+    cursor.set_position((pos + needed) as u64);
+    unsafe {
+        if count != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut f64;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Peeks the `u32` element count that prefixes a CDR sequence at `cursor`'s current position,
+/// without consuming it, so a caller can validate a declared length against the bytes actually
+/// remaining before committing to an allocation. CDR aligns a sequence's length prefix to 4 bytes,
+/// so this accounts for any padding between the current position and the prefix.
+///
+/// Returns `None` if fewer than the padding plus 4 bytes remain in the buffer.
+fn peek_sequence_len(cursor: &Cursor<Vec<u8>>) -> Option<u32> {
+    let buf = cursor.get_ref();
+    let pos = cursor.position();
+    let aligned = (pos + 3) & !3;
+    let start = usize::try_from(aligned).ok()?;
+    if start.checked_add(4)? > buf.len() {
+        return None;
+    }
+    Some(u32::from_be_bytes(buf[start..start + 4].try_into().unwrap()))
+}
+
+/// Validates that a sequence of `elem_size`-byte elements, as declared by the length prefix at
+/// `cursor`'s current position, actually fits within the bytes remaining in the buffer, without
+/// allocating anything. Used by the `*_array_checked` readers to reject a bogus, attacker-supplied
+/// length before committing to an allocation sized from it.
+fn validate_sequence_fits(cursor: &Cursor<Vec<u8>>, elem_size: usize) -> bool {
+    let pos = cursor.position();
+    let aligned = (pos + 3) & !3;
+    let Some(declared_len) = peek_sequence_len(cursor) else {
+        return false;
+    };
+    let Some(payload_len) = (declared_len as u64).checked_mul(elem_size as u64) else {
+        return false;
+    };
+    let Some(needed) = payload_len.checked_add(aligned - pos + 4) else {
+        return false;
+    };
+    let remaining = (cursor.get_ref().len() as u64).saturating_sub(pos);
+    needed <= remaining
+}
+
+/// Finer-grained status code for the `*_array_checked` readers, which can fail in more ways than
+/// the coarse [`CdrBufferStatus`] used elsewhere in this file distinguishes: a buffer that simply
+/// ran out of bytes mid-read versus a length prefix that was rejected *before* any read was
+/// attempted. Untrusted libp2p peers can send either, and callers doing key-based deduplication or
+/// logging benefit from telling the two apart.
+///
+/// Numbered starting at 100, well clear of [`CdrBufferStatus`]'s `0..=5` range: both enums cross
+/// the FFI boundary as a bare `i32`, and a C caller that mixed up which status type a given
+/// function returns must not have its mistake silently "work" just because `Valid == 0` happens to
+/// line up with `CdrBufferStatus::Ok`.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CdrStatus {
+    Valid = 100,
+    NullBuffer = 101,
+    /// The length prefix passed pre-validation but the subsequent `cdr` deserialization still
+    /// failed. Unreachable for the element types below given an accurate [`validate_sequence_fits`]
+    /// check, but kept distinct from `InvalidLength` for forward-compatibility with readers whose
+    /// validation is necessarily looser (e.g. variable-width elements).
+    Truncated = 102,
+    InvalidLength = 103,
+    AllocationFailed = 104,
+}
+
+/// Reads a contiguous `u32` sequence exactly like [`rs_libp2p_cdr_buffer_read_uint32_array`], but
+/// first validates the declared element count against the bytes actually remaining in the buffer
+/// and fails with `CdrStatus::InvalidLength` *before* allocating, rather than the generic
+/// `Underrun` the unchecked reader would eventually surface. A peer on an untrusted libp2p
+/// connection can advertise an arbitrary 32-bit length; without this check, decoding a bulk
+/// sequence from such a payload can force a multi-gigabyte allocation attempt before the
+/// truncated read ever fails. Prefer this entry point over [`rs_libp2p_cdr_buffer_read_uint32_array`]
+/// for payloads such as `sensor_msgs/PointCloud2` that arrive from a remote peer rather than a
+/// trusted local stack.
+///
+/// Returns a [`CdrStatus`]; on any status other than `Valid`, `out`/`out_count` are left untouched.
+/// The returned buffer must be released with [`rs_libp2p_cdr_buffer_free_uint32_array`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_uint32_array_checked(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut *mut u32,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_uint32_array_checked".to_string(),
+        );
+        return CdrStatus::NullBuffer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    if !validate_sequence_fits(libp2p_cdr_buffer, std::mem::size_of::<u32>()) {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_uint32_array_checked: declared length exceeds remaining buffer"
+                .to_string(),
+        );
+        return CdrStatus::InvalidLength as i32;
+    }
+    let values = match cdr::deserialize_from::<_, Vec<u32>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(values) => values,
+        Err(err) => {
+            set_last_error(format!(
+                "rs_libp2p_cdr_buffer_read_uint32_array_checked: {err}"
+            ));
+            return CdrStatus::Truncated as i32;
+        }
+    };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut u32;
+        }
+    }
+    CdrStatus::Valid as i32
+}
+
+/// Reads a contiguous `f32` sequence exactly like [`rs_libp2p_cdr_buffer_read_float_array`], but
+/// first validates the declared element count the same way as
+/// [`rs_libp2p_cdr_buffer_read_uint32_array_checked`] — see that function's documentation.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_float_array_checked(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut *mut f32,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_float_array_checked".to_string(),
+        );
+        return CdrStatus::NullBuffer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    if !validate_sequence_fits(libp2p_cdr_buffer, std::mem::size_of::<f32>()) {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_float_array_checked: declared length exceeds remaining buffer"
+                .to_string(),
+        );
+        return CdrStatus::InvalidLength as i32;
+    }
+    let values = match cdr::deserialize_from::<_, Vec<f32>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(values) => values,
+        Err(err) => {
+            set_last_error(format!(
+                "rs_libp2p_cdr_buffer_read_float_array_checked: {err}"
+            ));
+            return CdrStatus::Truncated as i32;
+        }
+    };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut f32;
+        }
+    }
+    CdrStatus::Valid as i32
+}
+
+/// Reads a contiguous `f64` sequence exactly like [`rs_libp2p_cdr_buffer_read_double_array`], but
+/// first validates the declared element count the same way as
+/// [`rs_libp2p_cdr_buffer_read_uint32_array_checked`] — see that function's documentation.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_double_array_checked(
+    ptr: *mut Cursor<Vec<u8>>,
+    out: *mut *mut f64,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_double_array_checked".to_string(),
+        );
+        return CdrStatus::NullBuffer as i32;
+    }
+    let libp2p_cdr_buffer = unsafe { &mut *ptr };
+    if !validate_sequence_fits(libp2p_cdr_buffer, std::mem::size_of::<f64>()) {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_double_array_checked: declared length exceeds remaining buffer"
+                .to_string(),
+        );
+        return CdrStatus::InvalidLength as i32;
+    }
+    let values = match cdr::deserialize_from::<_, Vec<f64>, _>(libp2p_cdr_buffer, cdr::Infinite) {
+        Ok(values) => values,
+        Err(err) => {
+            set_last_error(format!(
+                "rs_libp2p_cdr_buffer_read_double_array_checked: {err}"
+            ));
+            return CdrStatus::Truncated as i32;
+        }
+    };
+    let len = values.len();
+    unsafe {
+        *out_count = len;
+        if len != 0 {
+            *out = Box::into_raw(values.into_boxed_slice()) as *mut f64;
+        }
+    }
+    CdrStatus::Valid as i32
+}
+
+/// Byte order selected for an [`EncapsulatedCdrBuffer`], mirroring the representation identifier
+/// carried in the 4-byte RTPS/DDS CDR encapsulation header that real `rmw_fastrtps`/CycloneDDS
+/// peers prepend to every payload. The functions above always serialize with `cdr::CdrBe` and
+/// never emit that header, so buffers they produce cannot interoperate with a stock DDS peer;
+/// `EncapsulatedCdrBuffer` and its `rs_libp2p_cdr_buffer_*_encapsulation`/`*_encapsulated_*`
+/// entry points below are the endianness-aware, header-carrying alternative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CdrEndianness {
+    Big,
+    Little,
+}
+
+impl CdrEndianness {
+    /// The representation identifier (bytes 0-1 of the encapsulation header) for a plain
+    /// (non parameter-list) CDR payload in this byte order.
+    fn representation_id(self) -> u16 {
+        match self {
+            CdrEndianness::Big => 0x0000,
+            CdrEndianness::Little => 0x0001,
+        }
+    }
+
+    /// Recovers the byte order from a representation identifier. `0x0000`/`0x0002` are the
+    /// big-endian PLAIN_CDR/PL_CDR variants and `0x0001`/`0x0003` the little-endian ones, so the
+    /// byte order is simply the low bit of the identifier.
+    fn from_representation_id(id: u16) -> Self {
+        if id & 0x0001 == 0 {
+            CdrEndianness::Big
+        } else {
+            CdrEndianness::Little
+        }
+    }
+}
+
+/// Length in bytes of the CDR encapsulation header (representation id + options).
+const ENCAPSULATION_HEADER_LEN: u64 = 4;
+
+/// Representation identifier for big-endian plain CDR, per the RTPS/DDS encapsulation header.
+pub const CDR_REPRESENTATION_CDR_BE: u16 = 0x0000;
+/// Representation identifier for little-endian plain CDR.
+pub const CDR_REPRESENTATION_CDR_LE: u16 = 0x0001;
+/// Representation identifier for big-endian PL_CDR (parameter-list payload), used by the
+/// built-in DDS discovery topics and any type with mutable/appendable members.
+pub const CDR_REPRESENTATION_PL_CDR_BE: u16 = 0x0002;
+/// Representation identifier for little-endian PL_CDR.
+pub const CDR_REPRESENTATION_PL_CDR_LE: u16 = 0x0003;
+
+/// Returns whether `representation_id` (as read from an encapsulation header by
+/// [`rs_libp2p_cdr_buffer_read_encapsulation`]) indicates a PL_CDR parameter-list payload rather
+/// than plain CDR, i.e. whether its value is `CDR_REPRESENTATION_PL_CDR_BE`/`_LE`.
+#[unsafe(no_mangle)]
+pub extern "C" fn rs_libp2p_cdr_buffer_encapsulation_is_parameter_list(
+    representation_id: u16,
+) -> bool {
+    representation_id & 0x0002 != 0
+}
+
+/// Shared cursor/alignment/bounds-check plumbing behind every CDR buffer variant (currently
+/// [`EncapsulatedCdrBuffer`] and [`VersionedCdrBuffer`]): a running `Cursor<Vec<u8>>` that fields
+/// are written into/read out of sequentially, so alignment padding accumulates across the whole
+/// stream the way a real CDR encoder requires, rather than resetting per field the way repeated
+/// calls to `cdr::serialize_into` on a fresh cursor would. Reads are bounds-checked against the
+/// buffer's actual length and return `None` instead of panicking on a truncated or malformed
+/// buffer from a hostile peer. Implementors only need to expose the cursor and, if they differ
+/// from the defaults, where alignment is measured from and how a field's declared width maps to
+/// its real alignment — a fix to the padding or bounds-check logic then only has to be made once.
+trait CdrCursor {
+    fn cursor(&self) -> &Cursor<Vec<u8>>;
+    fn cursor_mut(&mut self) -> &mut Cursor<Vec<u8>>;
+
+    /// The cursor position alignment is measured from, e.g. after a header that doesn't count
+    /// towards body alignment. Defaults to the start of the buffer.
+    fn align_origin(&self) -> u64 {
+        0
+    }
+
+    /// The real alignment to apply for a field declared as `width` bytes wide. Defaults to
+    /// `width` itself.
+    fn alignment_for(&self, width: u64) -> u64 {
+        width
+    }
+
+    fn pad_write(&mut self, width: u64) {
+        let align = self.alignment_for(width);
+        let offset = self.cursor().position() - self.align_origin();
+        let padding = (align - (offset % align)) % align;
+        for _ in 0..padding {
+            self.cursor_mut().get_mut().push(0);
+        }
+        let position = self.cursor().position();
+        self.cursor_mut().set_position(position + padding);
+    }
+
+    fn pad_read(&mut self, width: u64) {
+        let align = self.alignment_for(width);
+        let offset = self.cursor().position() - self.align_origin();
+        let padding = (align - (offset % align)) % align;
+        let position = self.cursor().position();
+        self.cursor_mut().set_position(position + padding);
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.cursor_mut().get_mut().extend_from_slice(bytes);
+        let position = self.cursor().position();
+        self.cursor_mut().set_position(position + bytes.len() as u64);
+    }
+
+    /// Reads `len` bytes from the current position, or `None` if fewer than `len` bytes remain
+    /// (e.g. a truncated or malformed buffer from a hostile peer), leaving the cursor unmoved.
+    fn read_bytes(&mut self, len: usize) -> Option<Vec<u8>> {
+        let start = self.cursor().position() as usize;
+        let end = start.checked_add(len)?;
+        if end > self.cursor().get_ref().len() {
+            return None;
+        }
+        let bytes = self.cursor().get_ref()[start..end].to_vec();
+        self.cursor_mut().set_position(end as u64);
+        Some(bytes)
+    }
+}
+
+/// A CDR byte stream prefixed with the 4-byte DDS/RTPS encapsulation header, together with the
+/// byte order that header selected.
+///
+/// Every `write_*`/`read_*` function below consults `endianness` instead of hardcoding
+/// `cdr::CdrBe`. Alignment is measured from the start of the body (i.e. after the 4-byte header),
+/// per the CDR spec; see [`CdrCursor`] for the shared cursor/padding/bounds-check plumbing.
+pub struct EncapsulatedCdrBuffer {
+    cursor: Cursor<Vec<u8>>,
+    endianness: CdrEndianness,
+}
+
+impl CdrCursor for EncapsulatedCdrBuffer {
+    fn cursor(&self) -> &Cursor<Vec<u8>> {
+        &self.cursor
+    }
+
+    fn cursor_mut(&mut self) -> &mut Cursor<Vec<u8>> {
+        &mut self.cursor
+    }
+
+    fn align_origin(&self) -> u64 {
+        ENCAPSULATION_HEADER_LEN
+    }
+}
+
+/// Creates a new [`EncapsulatedCdrBuffer`] to write to, emitting the 4-byte encapsulation header
+/// (representation id for `endianness`, options `0x0000`) immediately so it is written exactly
+/// once, before any field.
+///
+/// # Arguments
+///
+/// * `endianness` - `0` selects big-endian (`CDR_BE`), any other value selects little-endian (`CDR_LE`).
+///
+/// # Returns
+///
+/// A raw pointer to an [`EncapsulatedCdrBuffer`].
+#[unsafe(no_mangle)]
+pub extern "C" fn rs_libp2p_cdr_buffer_write_new_encapsulated(
+    endianness: u8,
+) -> *mut EncapsulatedCdrBuffer {
+    let endianness = if endianness == 0 {
+        CdrEndianness::Big
+    } else {
+        CdrEndianness::Little
+    };
+    let mut buffer = EncapsulatedCdrBuffer {
+        cursor: Cursor::new(Vec::new()),
+        endianness,
+    };
+    unsafe { rs_libp2p_cdr_buffer_write_encapsulation(&mut buffer, endianness.representation_id(), 0x0000) };
+    Box::into_raw(Box::new(buffer))
+}
+
+/// Creates a new [`EncapsulatedCdrBuffer`] to write to, like
+/// [`rs_libp2p_cdr_buffer_write_new_encapsulated`], but taking the full representation
+/// identifier directly so callers can select PL_CDR (`CDR_REPRESENTATION_PL_CDR_BE`/`_LE`) for
+/// the parameter-list payloads used by DDS discovery topics and mutable/appendable types,
+/// instead of only plain CDR.
+///
+/// # Arguments
+///
+/// * `representation_id` - One of the `CDR_REPRESENTATION_*` constants.
+#[unsafe(no_mangle)]
+pub extern "C" fn rs_libp2p_cdr_buffer_write_new_encapsulated_with_representation(
+    representation_id: u16,
+) -> *mut EncapsulatedCdrBuffer {
+    let endianness = CdrEndianness::from_representation_id(representation_id);
+    let mut buffer = EncapsulatedCdrBuffer {
+        cursor: Cursor::new(Vec::new()),
+        endianness,
+    };
+    unsafe { rs_libp2p_cdr_buffer_write_encapsulation(&mut buffer, representation_id, 0x0000) };
+    Box::into_raw(Box::new(buffer))
+}
+
+/// Creates a new [`EncapsulatedCdrBuffer`] to read from, parsing the leading 4-byte encapsulation
+/// header to decide whether subsequent reads dispatch to `CDR_LE` or `CDR_BE`.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Panics
+///
+/// This function will panic if `length` is less than the 4-byte header.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_new_encapsulated(
+    data: *const u8,
+    length: usize,
+) -> *mut EncapsulatedCdrBuffer {
+    assert!(length >= ENCAPSULATION_HEADER_LEN as usize);
+    let bytes = unsafe { slice::from_raw_parts(data, length).to_vec() };
+    let mut buffer = EncapsulatedCdrBuffer {
+        cursor: Cursor::new(bytes),
+        // Overwritten by read_encapsulation below; Big is an arbitrary placeholder.
+        endianness: CdrEndianness::Big,
+    };
+    let mut representation_id = 0u16;
+    let mut options = 0u16;
+    rs_libp2p_cdr_buffer_read_encapsulation(&mut buffer, &mut representation_id, &mut options);
+    Box::into_raw(Box::new(buffer))
+}
+
+/// Frees an [`EncapsulatedCdrBuffer`] from memory.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_encapsulated(ptr: *mut EncapsulatedCdrBuffer) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe { drop(Box::from_raw(ptr)) };
+}
+
+/// Writes the 4-byte CDR encapsulation header (representation identifier + options) at the
+/// buffer's current position and records the resulting byte order, so every later `write_*` on
+/// this buffer uses the matching serializer. Call this at most once, immediately after creating
+/// the buffer; [`rs_libp2p_cdr_buffer_write_new_encapsulated`] already does so.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_encapsulation(
+    ptr: *mut EncapsulatedCdrBuffer,
+    representation_id: u16,
+    options: u16,
+) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.endianness = CdrEndianness::from_representation_id(representation_id);
+    buffer.cursor.get_mut().extend_from_slice(&representation_id.to_be_bytes());
+    buffer.cursor.get_mut().extend_from_slice(&options.to_be_bytes());
+    buffer
+        .cursor
+        .set_position(buffer.cursor.position() + ENCAPSULATION_HEADER_LEN);
+}
+
+/// Reads the 4-byte CDR encapsulation header at the buffer's current position, setting the
+/// decode endianness for every later `read_*` on this buffer to match.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_encapsulation(
+    ptr: *mut EncapsulatedCdrBuffer,
+    out_representation_id: *mut u16,
+    out_options: *mut u16,
+) -> i32 {
+    if ptr.is_null() || out_representation_id.is_null() || out_options.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_encapsulation".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    let header = match buffer.read_bytes(ENCAPSULATION_HEADER_LEN as usize) {
+        Some(header) => header,
+        None => {
+            set_last_error("rs_libp2p_cdr_buffer_read_encapsulation: buffer too short for the 4-byte encapsulation header".to_string());
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let representation_id = u16::from_be_bytes([header[0], header[1]]);
+    let options = u16::from_be_bytes([header[2], header[3]]);
+    buffer.endianness = CdrEndianness::from_representation_id(representation_id);
+    unsafe {
+        *out_representation_id = representation_id;
+        *out_options = options;
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Writes a `u32` to an [`EncapsulatedCdrBuffer`] using its stored byte order, 4-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_encapsulated_uint32(
+    ptr: *mut EncapsulatedCdrBuffer,
+    n: u32,
+) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(4);
+    let bytes = match buffer.endianness {
+        CdrEndianness::Big => n.to_be_bytes(),
+        CdrEndianness::Little => n.to_le_bytes(),
+    };
+    buffer.write_bytes(&bytes);
+}
+
+/// Reads a `u32` from an [`EncapsulatedCdrBuffer`] using its stored byte order, 4-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_encapsulated_uint32(
+    ptr: *mut EncapsulatedCdrBuffer,
+    n: *mut u32,
+) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_encapsulated_uint32".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(4);
+    let bytes = match buffer.read_bytes(4) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error("rs_libp2p_cdr_buffer_read_encapsulated_uint32: buffer ended before the value could be fully decoded".to_string());
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let value = match buffer.endianness {
+        CdrEndianness::Big => u32::from_be_bytes(bytes.try_into().unwrap()),
+        CdrEndianness::Little => u32::from_le_bytes(bytes.try_into().unwrap()),
+    };
+    unsafe { *n = value };
+    CdrBufferStatus::Ok as i32
+}
+
+/// Writes an `f64` to an [`EncapsulatedCdrBuffer`] using its stored byte order, 8-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_encapsulated_double(
+    ptr: *mut EncapsulatedCdrBuffer,
+    n: f64,
+) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(8);
+    let bytes = match buffer.endianness {
+        CdrEndianness::Big => n.to_be_bytes(),
+        CdrEndianness::Little => n.to_le_bytes(),
+    };
+    buffer.write_bytes(&bytes);
+}
+
+/// Reads an `f64` from an [`EncapsulatedCdrBuffer`] using its stored byte order, 8-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_encapsulated_double(
+    ptr: *mut EncapsulatedCdrBuffer,
+    n: *mut f64,
+) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_encapsulated_double".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(8);
+    let bytes = match buffer.read_bytes(8) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error("rs_libp2p_cdr_buffer_read_encapsulated_double: buffer ended before the value could be fully decoded".to_string());
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let value = match buffer.endianness {
+        CdrEndianness::Big => f64::from_be_bytes(bytes.try_into().unwrap()),
+        CdrEndianness::Little => f64::from_le_bytes(bytes.try_into().unwrap()),
+    };
+    unsafe { *n = value };
+    CdrBufferStatus::Ok as i32
+}
+
+/// Convenience alias for [`rs_libp2p_cdr_buffer_write_new_encapsulated`] under the name this
+/// crate's DDS-interop peers (Fast-CDR/CycloneDDS) would expect: an explicit endianness selector
+/// on the write-side constructor, mirroring `rs_libp2p_cdr_buffer_read_new_encapsulated`'s
+/// automatic detection on the read side.
+///
+/// # Arguments
+///
+/// * `endianness` - `0` selects big-endian (`CDR_BE`), any other value selects little-endian (`CDR_LE`).
+#[unsafe(no_mangle)]
+pub extern "C" fn rs_libp2p_cdr_buffer_write_new_with_endianness(
+    endianness: u8,
+) -> *mut EncapsulatedCdrBuffer {
+    rs_libp2p_cdr_buffer_write_new_encapsulated(endianness)
+}
+
+/// Writes a `i32` to an [`EncapsulatedCdrBuffer`] using its stored byte order, 4-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_encapsulated_int32(ptr: *mut EncapsulatedCdrBuffer, n: i32) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(4);
+    let bytes = match buffer.endianness {
+        CdrEndianness::Big => n.to_be_bytes(),
+        CdrEndianness::Little => n.to_le_bytes(),
+    };
+    buffer.write_bytes(&bytes);
+}
+
+/// Reads a `i32` from an [`EncapsulatedCdrBuffer`] using its stored byte order, 4-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_encapsulated_int32(
+    ptr: *mut EncapsulatedCdrBuffer,
+    n: *mut i32,
+) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_encapsulated_int32".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(4);
+    let bytes = match buffer.read_bytes(4) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error("rs_libp2p_cdr_buffer_read_encapsulated_int32: buffer ended before the value could be fully decoded".to_string());
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let value = match buffer.endianness {
+        CdrEndianness::Big => i32::from_be_bytes(bytes.try_into().unwrap()),
+        CdrEndianness::Little => i32::from_le_bytes(bytes.try_into().unwrap()),
+    };
+    unsafe { *n = value };
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a `u16` to an [`EncapsulatedCdrBuffer`] using its stored byte order, 2-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_encapsulated_uint16(ptr: *mut EncapsulatedCdrBuffer, n: u16) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(2);
+    let bytes = match buffer.endianness {
+        CdrEndianness::Big => n.to_be_bytes(),
+        CdrEndianness::Little => n.to_le_bytes(),
+    };
+    buffer.write_bytes(&bytes);
+}
+
+/// Reads a `u16` from an [`EncapsulatedCdrBuffer`] using its stored byte order, 2-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_encapsulated_uint16(
+    ptr: *mut EncapsulatedCdrBuffer,
+    n: *mut u16,
+) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_encapsulated_uint16".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(2);
+    let bytes = match buffer.read_bytes(2) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error("rs_libp2p_cdr_buffer_read_encapsulated_uint16: buffer ended before the value could be fully decoded".to_string());
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let value = match buffer.endianness {
+        CdrEndianness::Big => u16::from_be_bytes(bytes.try_into().unwrap()),
+        CdrEndianness::Little => u16::from_le_bytes(bytes.try_into().unwrap()),
+    };
+    unsafe { *n = value };
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a `i16` to an [`EncapsulatedCdrBuffer`] using its stored byte order, 2-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_encapsulated_int16(ptr: *mut EncapsulatedCdrBuffer, n: i16) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(2);
+    let bytes = match buffer.endianness {
+        CdrEndianness::Big => n.to_be_bytes(),
+        CdrEndianness::Little => n.to_le_bytes(),
+    };
+    buffer.write_bytes(&bytes);
+}
+
+/// Reads a `i16` from an [`EncapsulatedCdrBuffer`] using its stored byte order, 2-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_encapsulated_int16(
+    ptr: *mut EncapsulatedCdrBuffer,
+    n: *mut i16,
+) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_encapsulated_int16".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(2);
+    let bytes = match buffer.read_bytes(2) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error("rs_libp2p_cdr_buffer_read_encapsulated_int16: buffer ended before the value could be fully decoded".to_string());
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let value = match buffer.endianness {
+        CdrEndianness::Big => i16::from_be_bytes(bytes.try_into().unwrap()),
+        CdrEndianness::Little => i16::from_le_bytes(bytes.try_into().unwrap()),
+    };
+    unsafe { *n = value };
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a `u64` to an [`EncapsulatedCdrBuffer`] using its stored byte order, 8-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_encapsulated_uint64(ptr: *mut EncapsulatedCdrBuffer, n: u64) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(8);
+    let bytes = match buffer.endianness {
+        CdrEndianness::Big => n.to_be_bytes(),
+        CdrEndianness::Little => n.to_le_bytes(),
+    };
+    buffer.write_bytes(&bytes);
+}
+
+/// Reads a `u64` from an [`EncapsulatedCdrBuffer`] using its stored byte order, 8-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_encapsulated_uint64(
+    ptr: *mut EncapsulatedCdrBuffer,
+    n: *mut u64,
+) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_encapsulated_uint64".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(8);
+    let bytes = match buffer.read_bytes(8) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error("rs_libp2p_cdr_buffer_read_encapsulated_uint64: buffer ended before the value could be fully decoded".to_string());
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let value = match buffer.endianness {
+        CdrEndianness::Big => u64::from_be_bytes(bytes.try_into().unwrap()),
+        CdrEndianness::Little => u64::from_le_bytes(bytes.try_into().unwrap()),
+    };
+    unsafe { *n = value };
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a `i64` to an [`EncapsulatedCdrBuffer`] using its stored byte order, 8-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_encapsulated_int64(ptr: *mut EncapsulatedCdrBuffer, n: i64) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(8);
+    let bytes = match buffer.endianness {
+        CdrEndianness::Big => n.to_be_bytes(),
+        CdrEndianness::Little => n.to_le_bytes(),
+    };
+    buffer.write_bytes(&bytes);
+}
+
+/// Reads a `i64` from an [`EncapsulatedCdrBuffer`] using its stored byte order, 8-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_encapsulated_int64(
+    ptr: *mut EncapsulatedCdrBuffer,
+    n: *mut i64,
+) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_encapsulated_int64".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(8);
+    let bytes = match buffer.read_bytes(8) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error("rs_libp2p_cdr_buffer_read_encapsulated_int64: buffer ended before the value could be fully decoded".to_string());
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let value = match buffer.endianness {
+        CdrEndianness::Big => i64::from_be_bytes(bytes.try_into().unwrap()),
+        CdrEndianness::Little => i64::from_le_bytes(bytes.try_into().unwrap()),
+    };
+    unsafe { *n = value };
+    CdrBufferStatus::Ok as i32
+}
+
+
+/// Writes a `f32` to an [`EncapsulatedCdrBuffer`] using its stored byte order, 4-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_encapsulated_float(ptr: *mut EncapsulatedCdrBuffer, n: f32) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(4);
+    let bytes = match buffer.endianness {
+        CdrEndianness::Big => n.to_be_bytes(),
+        CdrEndianness::Little => n.to_le_bytes(),
+    };
+    buffer.write_bytes(&bytes);
+}
+
+/// Reads a `f32` from an [`EncapsulatedCdrBuffer`] using its stored byte order, 4-byte aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_encapsulated_float(
+    ptr: *mut EncapsulatedCdrBuffer,
+    n: *mut f32,
+) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_encapsulated_float".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(4);
+    let bytes = match buffer.read_bytes(4) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error("rs_libp2p_cdr_buffer_read_encapsulated_float: buffer ended before the value could be fully decoded".to_string());
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let value = match buffer.endianness {
+        CdrEndianness::Big => f32::from_be_bytes(bytes.try_into().unwrap()),
+        CdrEndianness::Little => f32::from_le_bytes(bytes.try_into().unwrap()),
+    };
+    unsafe { *n = value };
+    CdrBufferStatus::Ok as i32
+}
+
+/// Writes a `char16` (`u16`) to an [`EncapsulatedCdrBuffer`] using its stored byte order, 2-byte
+/// aligned. Completes the encapsulation-aware scalar family alongside
+/// [`rs_libp2p_cdr_buffer_write_encapsulated_uint16`]/`_int16`/etc., which only covered the numeric
+/// types.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_encapsulated_char16(ptr: *mut EncapsulatedCdrBuffer, n: u16) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(2);
+    let bytes = match buffer.endianness {
+        CdrEndianness::Big => n.to_be_bytes(),
+        CdrEndianness::Little => n.to_le_bytes(),
+    };
+    buffer.write_bytes(&bytes);
+}
+
+/// Reads a `char16` (`u16`) from an [`EncapsulatedCdrBuffer`] using its stored byte order, 2-byte
+/// aligned.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_encapsulated_char16(
+    ptr: *mut EncapsulatedCdrBuffer,
+    n: *mut u16,
+) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_encapsulated_char16".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(2);
+    let bytes = match buffer.read_bytes(2) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error("rs_libp2p_cdr_buffer_read_encapsulated_char16: buffer ended before the value could be fully decoded".to_string());
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let value = match buffer.endianness {
+        CdrEndianness::Big => u16::from_be_bytes(bytes.try_into().unwrap()),
+        CdrEndianness::Little => u16::from_le_bytes(bytes.try_into().unwrap()),
+    };
+    unsafe { *n = value };
+    CdrBufferStatus::Ok as i32
+}
+
+/// Writes a `u16string` (CDR `u16` sequence: a `u32` element count followed by the packed
+/// elements) to an [`EncapsulatedCdrBuffer`] using its stored byte order for both the count and
+/// every element, 4-byte aligned for the count and then 2-byte aligned for the elements — unlike
+/// [`rs_libp2p_cdr_buffer_write_u16string`], which always serializes big-endian with no header.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_encapsulated_u16string(
+    ptr: *mut EncapsulatedCdrBuffer,
+    s: *const u16,
+    size: usize,
+) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(4);
+    let count_bytes = match buffer.endianness {
+        CdrEndianness::Big => (size as u32).to_be_bytes(),
+        CdrEndianness::Little => (size as u32).to_le_bytes(),
+    };
+    buffer.write_bytes(&count_bytes);
+    if size == 0 || s.is_null() {
+        return;
+    }
+    let elements = unsafe { slice::from_raw_parts(s, size) };
+    for &element in elements {
+        buffer.pad_write(2);
+        let bytes = match buffer.endianness {
+            CdrEndianness::Big => element.to_be_bytes(),
+            CdrEndianness::Little => element.to_le_bytes(),
+        };
+        buffer.write_bytes(&bytes);
+    }
+}
+
+/// Reads a `u16string` written by [`rs_libp2p_cdr_buffer_write_encapsulated_u16string`] from an
+/// [`EncapsulatedCdrBuffer`], byte-swapping the count and every element according to the buffer's
+/// stored byte order. The returned buffer must be released with
+/// [`rs_libp2p_cdr_buffer_free_u16string`].
+///
+/// Returns a [`CdrBufferStatus`]; on any status other than `Ok`, `out`/`out_count` are left
+/// untouched. `Underrun` covers both a truncated count field and a declared count this buffer
+/// cannot possibly still hold, checked before allocating so a hostile peer can't force an
+/// oversized allocation with a single 4-byte count field.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_encapsulated_u16string(
+    ptr: *mut EncapsulatedCdrBuffer,
+    out: *mut *mut u16,
+    out_count: *mut usize,
+) -> i32 {
+    if ptr.is_null() || out.is_null() || out_count.is_null() {
+        set_last_error(
+            "null pointer passed to rs_libp2p_cdr_buffer_read_encapsulated_u16string".to_string(),
+        );
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(4);
+    let count_bytes = match buffer.read_bytes(4) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error(
+                "rs_libp2p_cdr_buffer_read_encapsulated_u16string: buffer ended before the element count could be read".to_string(),
+            );
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let count = match buffer.endianness {
+        CdrEndianness::Big => u32::from_be_bytes(count_bytes.try_into().unwrap()),
+        CdrEndianness::Little => u32::from_le_bytes(count_bytes.try_into().unwrap()),
+    };
+    let remaining = buffer.cursor.get_ref().len() - buffer.cursor.position() as usize;
+    if (count as usize) > remaining / 2 {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_encapsulated_u16string: declared element count exceeds the remaining buffer".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+    let mut elements: Vec<u16> = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        buffer.pad_read(2);
+        let bytes = match buffer.read_bytes(2) {
+            Some(bytes) => bytes,
+            None => {
+                set_last_error(
+                    "rs_libp2p_cdr_buffer_read_encapsulated_u16string: buffer ended before all elements could be read".to_string(),
+                );
+                return CdrBufferStatus::Underrun as i32;
+            }
+        };
+        let element = match buffer.endianness {
+            CdrEndianness::Big => u16::from_be_bytes(bytes.try_into().unwrap()),
+            CdrEndianness::Little => u16::from_le_bytes(bytes.try_into().unwrap()),
+        };
+        elements.push(element);
+    }
+    unsafe {
+        *out_count = elements.len();
+        if !elements.is_empty() {
+            *out = Box::into_raw(elements.into_boxed_slice()) as *mut u16;
+        }
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// CDR encoding version, which changes the alignment width used for 8-byte scalars: classic CDR
+/// (XCDR1, used by `EncapsulatedCdrBuffer` above) aligns `u64`/`i64`/`f64` to 8 bytes, while XCDR2
+/// — the default for Humble+ ROS 2 DDS peers — narrows that to 4. Alignment for narrower scalars
+/// is unaffected and always equals their own width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CdrVersion {
+    Xcdr1,
+    Xcdr2,
+}
+
+impl CdrVersion {
+    /// Alignment in bytes for an 8-byte scalar under this encoding version.
+    fn eight_byte_alignment(self) -> u64 {
+        match self {
+            CdrVersion::Xcdr1 => 8,
+            CdrVersion::Xcdr2 => 4,
+        }
+    }
+}
+
+/// A CDR byte stream whose scalar alignment is a property of an explicit encoding version
+/// (XCDR1 vs XCDR2), together with support for the DHEADER that XCDR2 requires around
+/// appendable/mutable aggregates: a leading `u32` giving the serialized byte length of the member
+/// block, so a reader built against an older IDL revision can skip trailing members it doesn't
+/// recognize. `dheader_starts` tracks, for nested member blocks, either the byte offset of the
+/// placeholder length slot to backpatch (while writing) or the end offset to skip to (while
+/// reading). See [`CdrCursor`] for the shared cursor/padding/bounds-check plumbing.
+pub struct VersionedCdrBuffer {
+    cursor: Cursor<Vec<u8>>,
+    endianness: CdrEndianness,
+    version: CdrVersion,
+    dheader_starts: Vec<u64>,
+}
+
+impl CdrCursor for VersionedCdrBuffer {
+    fn cursor(&self) -> &Cursor<Vec<u8>> {
+        &self.cursor
+    }
+
+    fn cursor_mut(&mut self) -> &mut Cursor<Vec<u8>> {
+        &mut self.cursor
+    }
+
+    fn alignment_for(&self, width: u64) -> u64 {
+        if width == 8 {
+            self.version.eight_byte_alignment()
+        } else {
+            width
+        }
+    }
+}
+
+/// Creates a new [`VersionedCdrBuffer`] to write to.
+///
+/// # Arguments
+///
+/// * `endianness` - `0` selects big-endian, any other value selects little-endian.
+/// * `version` - `0` selects XCDR1 (8-byte alignment for 8-byte scalars), any other value
+///   selects XCDR2 (4-byte alignment).
+#[unsafe(no_mangle)]
+pub extern "C" fn rs_libp2p_cdr_buffer_write_new_v2(endianness: u8, version: u8) -> *mut VersionedCdrBuffer {
+    let endianness = if endianness == 0 { CdrEndianness::Big } else { CdrEndianness::Little };
+    let version = if version == 0 { CdrVersion::Xcdr1 } else { CdrVersion::Xcdr2 };
+    Box::into_raw(Box::new(VersionedCdrBuffer {
+        cursor: Cursor::new(Vec::new()),
+        endianness,
+        version,
+        dheader_starts: Vec::new(),
+    }))
+}
+
+/// Creates a new [`VersionedCdrBuffer`] to read from, over a copy of `data`. Unlike
+/// [`rs_libp2p_cdr_buffer_read_new_encapsulated`], this does not expect or parse a leading
+/// encapsulation header — `endianness`/`version` are supplied directly by the caller, since XCDR2's
+/// DHEADER framing is an orthogonal concern from the RTPS encapsulation header.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_new_v2(
+    data: *const u8,
+    length: usize,
+    endianness: u8,
+    version: u8,
+) -> *mut VersionedCdrBuffer {
+    let endianness = if endianness == 0 { CdrEndianness::Big } else { CdrEndianness::Little };
+    let version = if version == 0 { CdrVersion::Xcdr1 } else { CdrVersion::Xcdr2 };
+    let bytes = unsafe { slice::from_raw_parts(data, length).to_vec() };
+    Box::into_raw(Box::new(VersionedCdrBuffer {
+        cursor: Cursor::new(bytes),
+        endianness,
+        version,
+        dheader_starts: Vec::new(),
+    }))
+}
+
+/// Frees a [`VersionedCdrBuffer`] from memory.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_free_v2(ptr: *mut VersionedCdrBuffer) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe { drop(Box::from_raw(ptr)) };
+}
+
+/// Writes a `u32` to a [`VersionedCdrBuffer`], 4-byte aligned under either encoding version.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_v2_uint32(ptr: *mut VersionedCdrBuffer, n: u32) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(4);
+    let bytes = match buffer.endianness {
+        CdrEndianness::Big => n.to_be_bytes(),
+        CdrEndianness::Little => n.to_le_bytes(),
+    };
+    buffer.write_bytes(&bytes);
+}
+
+/// Reads a `u32` from a [`VersionedCdrBuffer`], 4-byte aligned under either encoding version.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_v2_uint32(ptr: *mut VersionedCdrBuffer, n: *mut u32) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_v2_uint32".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(4);
+    let bytes = match buffer.read_bytes(4) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error("rs_libp2p_cdr_buffer_read_v2_uint32: buffer ended before the value could be fully decoded".to_string());
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let value = match buffer.endianness {
+        CdrEndianness::Big => u32::from_be_bytes(bytes.try_into().unwrap()),
+        CdrEndianness::Little => u32::from_le_bytes(bytes.try_into().unwrap()),
+    };
+    unsafe { *n = value };
+    CdrBufferStatus::Ok as i32
+}
+
+/// Writes a `u64` to a [`VersionedCdrBuffer`], aligned per [`CdrVersion::eight_byte_alignment`] —
+/// 8 bytes under XCDR1, 4 under XCDR2. This is the scalar most directly affected by the encoding
+/// version, since narrower scalars align to their own width under both.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_v2_uint64(ptr: *mut VersionedCdrBuffer, n: u64) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(8);
+    let bytes = match buffer.endianness {
+        CdrEndianness::Big => n.to_be_bytes(),
+        CdrEndianness::Little => n.to_le_bytes(),
+    };
+    buffer.write_bytes(&bytes);
+}
+
+/// Reads a `u64` from a [`VersionedCdrBuffer`], aligned per [`CdrVersion::eight_byte_alignment`] —
+/// see [`rs_libp2p_cdr_buffer_write_v2_uint64`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_v2_uint64(ptr: *mut VersionedCdrBuffer, n: *mut u64) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_v2_uint64".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(8);
+    let bytes = match buffer.read_bytes(8) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error("rs_libp2p_cdr_buffer_read_v2_uint64: buffer ended before the value could be fully decoded".to_string());
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let value = match buffer.endianness {
+        CdrEndianness::Big => u64::from_be_bytes(bytes.try_into().unwrap()),
+        CdrEndianness::Little => u64::from_le_bytes(bytes.try_into().unwrap()),
+    };
+    unsafe { *n = value };
+    CdrBufferStatus::Ok as i32
+}
+
+/// Writes a `f64` to a [`VersionedCdrBuffer`], aligned per [`CdrVersion::eight_byte_alignment`] —
+/// see [`rs_libp2p_cdr_buffer_write_v2_uint64`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_write_v2_double(ptr: *mut VersionedCdrBuffer, n: f64) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(8);
+    let bytes = match buffer.endianness {
+        CdrEndianness::Big => n.to_be_bytes(),
+        CdrEndianness::Little => n.to_le_bytes(),
+    };
+    buffer.write_bytes(&bytes);
+}
+
+/// Reads a `f64` from a [`VersionedCdrBuffer`], aligned per [`CdrVersion::eight_byte_alignment`] —
+/// see [`rs_libp2p_cdr_buffer_write_v2_uint64`].
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_v2_double(ptr: *mut VersionedCdrBuffer, n: *mut f64) -> i32 {
+    if ptr.is_null() || n.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_v2_double".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(8);
+    let bytes = match buffer.read_bytes(8) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error("rs_libp2p_cdr_buffer_read_v2_double: buffer ended before the value could be fully decoded".to_string());
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let value = match buffer.endianness {
+        CdrEndianness::Big => f64::from_be_bytes(bytes.try_into().unwrap()),
+        CdrEndianness::Little => f64::from_le_bytes(bytes.try_into().unwrap()),
+    };
+    unsafe { *n = value };
+    CdrBufferStatus::Ok as i32
+}
+
+/// Reserves a 4-byte DHEADER placeholder at the buffer's current (4-byte aligned) position and
+/// remembers its offset, so the matching [`rs_libp2p_cdr_buffer_end_dheader`] can backpatch it
+/// with the serialized length of the member block that follows. DHEADERs may nest; each `begin`
+/// pushes onto `dheader_starts` and the matching `end` pops the innermost one.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_begin_dheader(ptr: *mut VersionedCdrBuffer) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    buffer.pad_write(4);
+    let placeholder_pos = buffer.cursor.position();
+    buffer.write_bytes(&[0u8; 4]);
+    buffer.dheader_starts.push(placeholder_pos);
+}
+
+/// Closes the innermost DHEADER opened by [`rs_libp2p_cdr_buffer_begin_dheader`], backpatching its
+/// placeholder slot with the number of bytes written since the member block began.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Panics
+///
+/// This function will panic if called without a matching, still-open `begin_dheader`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_end_dheader(ptr: *mut VersionedCdrBuffer) {
+    let buffer = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    let placeholder_pos = buffer
+        .dheader_starts
+        .pop()
+        .expect("rs_libp2p_cdr_buffer_end_dheader called without a matching begin_dheader");
+    let member_block_len = buffer.cursor.position() - (placeholder_pos + 4);
+    let bytes = match buffer.endianness {
+        CdrEndianness::Big => (member_block_len as u32).to_be_bytes(),
+        CdrEndianness::Little => (member_block_len as u32).to_le_bytes(),
+    };
+    let start = placeholder_pos as usize;
+    buffer.cursor.get_mut()[start..start + 4].copy_from_slice(&bytes);
+}
+
+/// Consumes a DHEADER at the buffer's current (4-byte aligned) read position and remembers the
+/// resulting member block's end offset (pushed onto `dheader_starts`, mirroring the write side),
+/// so [`rs_libp2p_cdr_buffer_skip_to_dheader_end`] can fast-forward past any trailing members a
+/// reader built against an older IDL revision doesn't recognize.
+///
+/// Returns a [`CdrBufferStatus`]; `Underrun` covers both a truncated length field and a declared
+/// member length that would put the end offset past the buffer's actual length (a peer-controlled
+/// `u32` that [`rs_libp2p_cdr_buffer_skip_to_dheader_end`] would otherwise seek past the end with).
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_read_dheader(ptr: *mut VersionedCdrBuffer) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_read_dheader".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    buffer.pad_read(4);
+    let bytes = match buffer.read_bytes(4) {
+        Some(bytes) => bytes,
+        None => {
+            set_last_error(
+                "rs_libp2p_cdr_buffer_read_dheader: buffer ended before the DHEADER length could be read".to_string(),
+            );
+            return CdrBufferStatus::Underrun as i32;
+        }
+    };
+    let member_len = match buffer.endianness {
+        CdrEndianness::Big => u32::from_be_bytes(bytes.try_into().unwrap()),
+        CdrEndianness::Little => u32::from_le_bytes(bytes.try_into().unwrap()),
+    };
+    let end_offset = buffer.cursor.position() + member_len as u64;
+    if end_offset > buffer.cursor.get_ref().len() as u64 {
+        set_last_error(
+            "rs_libp2p_cdr_buffer_read_dheader: declared member length extends past the end of the buffer".to_string(),
+        );
+        return CdrBufferStatus::Underrun as i32;
+    }
+    buffer.dheader_starts.push(end_offset);
+    CdrBufferStatus::Ok as i32
+}
+
+/// Fast-forwards the read position to the end of the innermost DHEADER-delimited member block
+/// opened by [`rs_libp2p_cdr_buffer_read_dheader`], skipping any trailing members the caller didn't
+/// consume. A no-op if no DHEADER is currently open.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rs_libp2p_cdr_buffer_skip_to_dheader_end(ptr: *mut VersionedCdrBuffer) -> i32 {
+    if ptr.is_null() {
+        set_last_error("null pointer passed to rs_libp2p_cdr_buffer_skip_to_dheader_end".to_string());
+        return CdrBufferStatus::NullPointer as i32;
+    }
+    let buffer = unsafe { &mut *ptr };
+    if let Some(end_offset) = buffer.dheader_starts.pop() {
+        buffer.cursor.set_position(end_offset);
+    }
+    CdrBufferStatus::Ok as i32
+}
+
+/// Maps an `f32`'s bit pattern onto a `u32` key whose unsigned ordering matches the IEEE 754 §5.10
+/// `totalOrder` predicate: negative values (including `-0.0` and negative-signed NaNs) compare as
+/// less than non-negative ones (a positive-signed NaN orders above `+inf`), `-0.0` sorts strictly before `+0.0`, and magnitude ordering is preserved
+/// within each sign. Flips every bit for negative values (descending magnitude becomes ascending
+/// key order) and sets the top bit for non-negative ones (so they sort above every negative key).
+fn total_order_key_f32(value: f32) -> u32 {
+    let bits = value.to_bits();
+    if bits & (1 << 31) != 0 {
+        !bits
+    } else {
+        bits | (1 << 31)
+    }
+}
+
+/// The `f64` analogue of [`total_order_key_f32`].
+fn total_order_key_f64(value: f64) -> u64 {
+    let bits = value.to_bits();
+    if bits & (1 << 63) != 0 {
+        !bits
+    } else {
+        bits | (1 << 63)
+    }
+}
+
+/// Compares two `f32` values per the IEEE 754 §5.10 `totalOrder` predicate rather than IEEE 754
+/// comparison operators, so DDS keyed-topic deduplication gets a deterministic ordering even across
+/// NaN payloads and signed zeros (which compare equal, or incomparable, under `<`/`==`).
+///
+/// Returns `-1` if `a` orders before `b`, `0` if they order equal, `1` if `a` orders after `b`.
+#[unsafe(no_mangle)]
+pub extern "C" fn rs_libp2p_cdr_compare_float_total(a: f32, b: f32) -> i32 {
+    total_order_key_f32(a).cmp(&total_order_key_f32(b)) as i32
+}
+
+/// The `f64` analogue of [`rs_libp2p_cdr_compare_float_total`].
+#[unsafe(no_mangle)]
+pub extern "C" fn rs_libp2p_cdr_compare_double_total(a: f64, b: f64) -> i32 {
+    total_order_key_f64(a).cmp(&total_order_key_f64(b)) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    // Helper function to get buffer data for reading
+    fn get_buffer_data(ptr: *mut Cursor<Vec<u8>>) -> Vec<u8> {
+        unsafe {
+            let cursor = &*ptr;
+            cursor.get_ref().clone()
+        }
+    }
+
+    #[test]
+    fn test_buffer_lifecycle() {
+        // Test buffer creation and cleanup
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        assert!(!write_buf.is_null());
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+
+        // Test read buffer creation
+        let data = [0u8, 1, 2, 3];
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+        assert!(!read_buf.is_null());
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_null_pointer_handling() {
+        // free should handle null gracefully
+        unsafe { rs_libp2p_cdr_buffer_free(std::ptr::null_mut()) };
+
+        // free_string should handle null gracefully
+        unsafe { rs_libp2p_cdr_buffer_free_string(std::ptr::null_mut()) };
+    }
+
+    // === Seek/Tell/Remaining Tests ===
+
+    #[test]
+    fn test_seek_start_clamps_to_size() {
+        let data = [0u8, 1, 2, 3, 4];
+        let buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut pos: u64 = 0;
+        let status = unsafe { rs_libp2p_cdr_buffer_seek(buf, 0, 3, &mut pos) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(pos, 3);
+
+        // Seeking past the end clamps to size rather than erroring.
+        let status = unsafe { rs_libp2p_cdr_buffer_seek(buf, 0, 100, &mut pos) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(pos, 5);
+
+        unsafe { rs_libp2p_cdr_buffer_free(buf) };
+    }
+
+    #[test]
+    fn test_seek_end_rejects_underflow_past_start() {
+        let data = [0u8, 1, 2, 3, 4];
+        let buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut pos: u64 = 0;
+        let status = unsafe { rs_libp2p_cdr_buffer_seek(buf, 2, -2, &mut pos) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(pos, 3);
+
+        let status = unsafe { rs_libp2p_cdr_buffer_seek(buf, 2, -100, &mut pos) };
+        assert_eq!(status, CdrBufferStatus::Underrun as i32);
+
+        unsafe { rs_libp2p_cdr_buffer_free(buf) };
+    }
+
+    #[test]
+    fn test_seek_current_errors_on_underflow_and_clamps_on_overflow() {
+        let data = [0u8, 1, 2, 3, 4];
+        let buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut pos: u64 = 0;
+        unsafe { rs_libp2p_cdr_buffer_seek(buf, 0, 2, &mut pos) };
+
+        let status = unsafe { rs_libp2p_cdr_buffer_seek(buf, 1, -10, &mut pos) };
+        assert_eq!(status, CdrBufferStatus::Underrun as i32);
+
+        let status = unsafe { rs_libp2p_cdr_buffer_seek(buf, 1, 10, &mut pos) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(pos, 5);
+
+        unsafe { rs_libp2p_cdr_buffer_free(buf) };
+    }
+
+    #[test]
+    fn test_tell_and_remaining_track_position() {
+        let data = [0u8, 1, 2, 3, 4];
+        let buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut pos: u64 = 0;
+        unsafe { rs_libp2p_cdr_buffer_seek(buf, 0, 2, &mut pos) };
+
+        let mut tell_pos: u64 = 0;
+        let status = unsafe { rs_libp2p_cdr_buffer_tell(buf, &mut tell_pos) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(tell_pos, 2);
+
+        let mut remaining: u64 = 0;
+        let status = unsafe { rs_libp2p_cdr_buffer_remaining(buf, &mut remaining) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(remaining, 3);
+
+        unsafe { rs_libp2p_cdr_buffer_free(buf) };
+    }
+
+    #[test]
+    fn test_seek_rejects_null_pointer() {
+        let mut pos: u64 = 0;
+        let status = unsafe { rs_libp2p_cdr_buffer_seek(std::ptr::null_mut(), 0, 0, &mut pos) };
+        assert_eq!(status, CdrBufferStatus::NullPointer as i32);
+    }
+
+    // === Unsigned Integer Roundtrip Tests ===
+
+    #[test]
+    fn test_uint64_roundtrip() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val: u64 = 0x0123456789ABCDEF;
+
+        unsafe { rs_libp2p_cdr_buffer_write_uint64(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: u64 = 0;
+        unsafe { rs_libp2p_cdr_buffer_read_uint64(read_buf, &mut result as *mut u64) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
     fn test_uint32_roundtrip() {
         let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val: u32 = 0x01234567;
+        let test_val: u32 = 0x01234567;
+
+        unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: u32 = 0;
+        unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut result as *mut u32) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_uint16_roundtrip() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val: u16 = 0x0123;
+
+        unsafe { rs_libp2p_cdr_buffer_write_uint16(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: u16 = 0;
+        unsafe { rs_libp2p_cdr_buffer_read_uint16(read_buf, &mut result as *mut u16) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_uint8_roundtrip() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val: u8 = 0x42;
+
+        unsafe { rs_libp2p_cdr_buffer_write_uint8(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: u8 = 0;
+        unsafe { rs_libp2p_cdr_buffer_read_uint8(read_buf, &mut result as *mut u8) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === Signed Integer Roundtrip Tests ===
+
+    #[test]
+    fn test_int64_roundtrip() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val: i64 = -0x0123456789ABCDEF;
+
+        unsafe { rs_libp2p_cdr_buffer_write_int64(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: i64 = 0;
+        unsafe { rs_libp2p_cdr_buffer_read_int64(read_buf, &mut result as *mut i64) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_int32_roundtrip() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val: i32 = -0x01234567;
+
+        unsafe { rs_libp2p_cdr_buffer_write_int32(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: i32 = 0;
+        unsafe { rs_libp2p_cdr_buffer_read_int32(read_buf, &mut result as *mut i32) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_int16_roundtrip() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val: i16 = -0x0123;
+
+        unsafe { rs_libp2p_cdr_buffer_write_int16(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: i16 = 0;
+        unsafe { rs_libp2p_cdr_buffer_read_int16(read_buf, &mut result as *mut i16) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_int8_roundtrip() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val: i8 = -42;
+
+        unsafe { rs_libp2p_cdr_buffer_write_int8(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: i8 = 0;
+        unsafe { rs_libp2p_cdr_buffer_read_int8(read_buf, &mut result as *mut i8) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === Character Roundtrip Tests ===
+
+    #[test]
+    fn test_char_roundtrip() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val: c_char = b'A' as c_char;
+
+        unsafe { rs_libp2p_cdr_buffer_write_char(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: c_char = 0;
+        unsafe { rs_libp2p_cdr_buffer_read_char(read_buf, &mut result as *mut c_char) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_char16_roundtrip() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val: u16 = 0x3042; // Japanese Hiragana '„ÅÇ'
+
+        unsafe { rs_libp2p_cdr_buffer_write_char16(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: u16 = 0;
+        unsafe { rs_libp2p_cdr_buffer_read_char16(read_buf, &mut result as *mut u16) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === Floating Point Roundtrip Tests ===
+
+    #[test]
+    fn test_float_roundtrip() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val: f32 = std::f32::consts::PI;
+
+        unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: f32 = 0.0;
+        unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut result as *mut f32) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_double_roundtrip() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val: f64 = std::f64::consts::E;
+
+        unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: f64 = 0.0;
+        unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut result as *mut f64) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === Boolean Roundtrip Tests ===
+
+    #[test]
+    fn test_bool_roundtrip_true() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val = true;
+
+        unsafe { rs_libp2p_cdr_buffer_write_bool(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result = false;
+        unsafe { rs_libp2p_cdr_buffer_read_bool(read_buf, &mut result as *mut bool) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_bool_roundtrip_false() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val = false;
+
+        unsafe { rs_libp2p_cdr_buffer_write_bool(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result = true;
+        unsafe { rs_libp2p_cdr_buffer_read_bool(read_buf, &mut result as *mut bool) };
+
+        assert_eq!(result, test_val);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === String Roundtrip Tests ===
+
+    #[test]
+    fn test_string_roundtrip() {
+        // Manually serialize a string using CDR
+        let test_string = CString::new("Hello, World!").unwrap();
+        let mut buffer = Cursor::new(Vec::<u8>::new());
+        cdr::serialize_into::<_, _, _, cdr::CdrBe>(&mut buffer, &test_string, cdr::Infinite)
+            .unwrap();
+
+        let data = buffer.get_ref().clone();
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut s_ptr: *const c_char = std::ptr::null();
+        let mut size: usize = 0;
+
+        unsafe {
+            rs_libp2p_cdr_buffer_read_string(
+                read_buf,
+                &mut s_ptr as *mut *const c_char,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert!(!s_ptr.is_null());
+        assert_eq!(size, 13);
+
+        let result_str = unsafe { CStr::from_ptr(s_ptr) };
+        assert_eq!(result_str.to_str().unwrap(), "Hello, World!");
+
+        unsafe { rs_libp2p_cdr_buffer_free_string(s_ptr as *mut c_char) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_empty_string() {
+        let test_string = CString::new("").unwrap();
+        let mut buffer = Cursor::new(Vec::<u8>::new());
+        cdr::serialize_into::<_, _, _, cdr::CdrBe>(&mut buffer, &test_string, cdr::Infinite)
+            .unwrap();
+
+        let data = buffer.get_ref().clone();
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut s_ptr: *const c_char = std::ptr::null();
+        let mut size: usize = 0;
+
+        unsafe {
+            rs_libp2p_cdr_buffer_read_string(
+                read_buf,
+                &mut s_ptr as *mut *const c_char,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert_eq!(size, 0);
+        // Empty strings should not set the pointer
+
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === Write/Read String Roundtrip Tests ===
+
+    #[test]
+    fn test_string_write_read_roundtrip() {
+        // Test basic ASCII string roundtrip through write/read functions
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_string = CString::new("Hello, World!").unwrap();
+
+        unsafe {
+            rs_libp2p_cdr_buffer_write_string(
+                write_buf,
+                test_string.as_ptr(),
+                test_string.to_bytes().len(),
+            )
+        };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut s_ptr: *const c_char = std::ptr::null();
+        let mut size: usize = 0;
+
+        unsafe {
+            rs_libp2p_cdr_buffer_read_string(
+                read_buf,
+                &mut s_ptr as *mut *const c_char,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert!(!s_ptr.is_null());
+        assert_eq!(size, 13);
+
+        let result_str = unsafe { CStr::from_ptr(s_ptr) };
+        assert_eq!(result_str.to_str().unwrap(), "Hello, World!");
+
+        unsafe { rs_libp2p_cdr_buffer_free_string(s_ptr as *mut c_char) };
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_empty_string_write_read_roundtrip() {
+        // Test empty string edge case through write/read functions
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_string = CString::new("").unwrap();
+
+        unsafe {
+            rs_libp2p_cdr_buffer_write_string(
+                write_buf,
+                test_string.as_ptr(),
+                test_string.to_bytes().len(),
+            )
+        };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut s_ptr: *const c_char = std::ptr::null();
+        let mut size: usize = 0;
+
+        unsafe {
+            rs_libp2p_cdr_buffer_read_string(
+                read_buf,
+                &mut s_ptr as *mut *const c_char,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert_eq!(size, 0);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_long_string_write_read_roundtrip() {
+        // Test 10KB string stress test through write/read functions
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_data = "A".repeat(10240); // 10KB string
+        let test_string = CString::new(test_data.as_str()).unwrap();
+
+        unsafe {
+            rs_libp2p_cdr_buffer_write_string(
+                write_buf,
+                test_string.as_ptr(),
+                test_string.to_bytes().len(),
+            )
+        };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut s_ptr: *const c_char = std::ptr::null();
+        let mut size: usize = 0;
+
+        unsafe {
+            rs_libp2p_cdr_buffer_read_string(
+                read_buf,
+                &mut s_ptr as *mut *const c_char,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert!(!s_ptr.is_null());
+        assert_eq!(size, 10240);
+
+        let result_str = unsafe { CStr::from_ptr(s_ptr) };
+        assert_eq!(result_str.to_str().unwrap(), test_data);
+
+        unsafe { rs_libp2p_cdr_buffer_free_string(s_ptr as *mut c_char) };
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_unicode_string_write_read_roundtrip() {
+        // Test UTF-8 emoji/multibyte characters through write/read functions
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_data = "Hello üëã ‰∏ñÁïå üåç";
+        let test_string = CString::new(test_data).unwrap();
+
+        unsafe {
+            rs_libp2p_cdr_buffer_write_string(
+                write_buf,
+                test_string.as_ptr(),
+                test_string.to_bytes().len(),
+            )
+        };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut s_ptr: *const c_char = std::ptr::null();
+        let mut size: usize = 0;
+
+        unsafe {
+            rs_libp2p_cdr_buffer_read_string(
+                read_buf,
+                &mut s_ptr as *mut *const c_char,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert!(!s_ptr.is_null());
+
+        let result_str = unsafe { CStr::from_ptr(s_ptr) };
+        assert_eq!(result_str.to_str().unwrap(), test_data);
+
+        unsafe { rs_libp2p_cdr_buffer_free_string(s_ptr as *mut c_char) };
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === Write/Read U16String Roundtrip Tests ===
+
+    #[test]
+    fn test_u16string_write_read_roundtrip() {
+        // Test basic u16 array roundtrip through write/read functions
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_data: Vec<u16> = vec![0x0041, 0x0042, 0x0043]; // ABC
+
+        unsafe {
+            rs_libp2p_cdr_buffer_write_u16string(write_buf, test_data.as_ptr(), test_data.len())
+        };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut s_ptr: *const u16 = std::ptr::null();
+        let mut size: usize = 0;
+
+        unsafe {
+            rs_libp2p_cdr_buffer_read_u16string(
+                read_buf,
+                &mut s_ptr as *mut *const u16,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert!(!s_ptr.is_null());
+        assert_eq!(size, 3);
+
+        let result_slice = unsafe { slice::from_raw_parts(s_ptr, size) };
+        assert_eq!(result_slice, test_data.as_slice());
+
+        unsafe { rs_libp2p_cdr_buffer_free_u16string(s_ptr as *mut u16, size) };
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_empty_u16string_write_read_roundtrip() {
+        // Test empty Vec<u16> edge case through write/read functions
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_data: Vec<u16> = Vec::new();
+
+        unsafe {
+            rs_libp2p_cdr_buffer_write_u16string(write_buf, test_data.as_ptr(), test_data.len())
+        };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut s_ptr: *const u16 = std::ptr::null();
+        let mut size: usize = 0;
+
+        unsafe {
+            rs_libp2p_cdr_buffer_read_u16string(
+                read_buf,
+                &mut s_ptr as *mut *const u16,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert_eq!(size, 0);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_long_u16string_write_read_roundtrip() {
+        // Test 10000 element u16 array stress test through write/read functions
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_data: Vec<u16> = (0..10000).map(|i| (i % 65536) as u16).collect();
+
+        unsafe {
+            rs_libp2p_cdr_buffer_write_u16string(write_buf, test_data.as_ptr(), test_data.len())
+        };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut s_ptr: *const u16 = std::ptr::null();
+        let mut size: usize = 0;
+
+        unsafe {
+            rs_libp2p_cdr_buffer_read_u16string(
+                read_buf,
+                &mut s_ptr as *mut *const u16,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert!(!s_ptr.is_null());
+        assert_eq!(size, 10000);
+
+        let result_slice = unsafe { slice::from_raw_parts(s_ptr, size) };
+        assert_eq!(result_slice, test_data.as_slice());
+
+        unsafe { rs_libp2p_cdr_buffer_free_u16string(s_ptr as *mut u16, size) };
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_unicode_u16string_write_read_roundtrip() {
+        // Test Japanese hiragana/kanji through write/read functions
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_data: Vec<u16> = vec![0x3042, 0x3044, 0x3046, 0x4E00, 0x4E8C]; // „ÅÇ„ÅÑ„ÅÜ‰∏Ä‰∫å
+
+        unsafe {
+            rs_libp2p_cdr_buffer_write_u16string(write_buf, test_data.as_ptr(), test_data.len())
+        };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut s_ptr: *const u16 = std::ptr::null();
+        let mut size: usize = 0;
+
+        unsafe {
+            rs_libp2p_cdr_buffer_read_u16string(
+                read_buf,
+                &mut s_ptr as *mut *const u16,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert!(!s_ptr.is_null());
+        assert_eq!(size, 5);
+
+        let result_slice = unsafe { slice::from_raw_parts(s_ptr, size) };
+        assert_eq!(result_slice, test_data.as_slice());
+
+        unsafe { rs_libp2p_cdr_buffer_free_u16string(s_ptr as *mut u16, size) };
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_u16string_roundtrip() {
+        // Manually serialize a u16 string using CDR
+        let test_string: Vec<u16> = vec![0x3042, 0x3044, 0x3046]; // Japanese hiragana
+        let mut buffer = Cursor::new(Vec::<u8>::new());
+        cdr::serialize_into::<_, _, _, cdr::CdrBe>(&mut buffer, &test_string, cdr::Infinite)
+            .unwrap();
+
+        let data = buffer.get_ref().clone();
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut s_ptr: *const u16 = std::ptr::null();
+        let mut size: usize = 0;
+
+        unsafe {
+            rs_libp2p_cdr_buffer_read_u16string(
+                read_buf,
+                &mut s_ptr as *mut *const u16,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert!(!s_ptr.is_null());
+        assert_eq!(size, 3);
+
+        let result_slice = unsafe { std::slice::from_raw_parts(s_ptr, size) };
+        assert_eq!(result_slice, &test_string[..]);
+
+        unsafe { rs_libp2p_cdr_buffer_free_u16string(s_ptr as *mut u16, size) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === Boundary Value Tests ===
+
+    #[test]
+    fn test_uint64_boundary_values() {
+        let test_values = vec![0u64, u64::MAX, u64::MIN, u64::MAX / 2];
+
+        for test_val in test_values {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            unsafe { rs_libp2p_cdr_buffer_write_uint64(write_buf, test_val) };
+
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+            let mut result: u64 = 0;
+            unsafe { rs_libp2p_cdr_buffer_read_uint64(read_buf, &mut result as *mut u64) };
+
+            assert_eq!(result, test_val, "Failed for value: {}", test_val);
+
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
+    }
+
+    #[test]
+    fn test_int64_boundary_values() {
+        let test_values = vec![0i64, i64::MAX, i64::MIN, -1, 1];
+
+        for test_val in test_values {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            unsafe { rs_libp2p_cdr_buffer_write_int64(write_buf, test_val) };
+
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+            let mut result: i64 = 0;
+            unsafe { rs_libp2p_cdr_buffer_read_int64(read_buf, &mut result as *mut i64) };
+
+            assert_eq!(result, test_val, "Failed for value: {}", test_val);
+
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
+    }
+
+    #[test]
+    fn test_float_special_values() {
+        let test_values = vec![
+            0.0f32,
+            -0.0f32,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::MIN,
+            f32::MAX,
+        ];
+
+        for test_val in test_values {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, test_val) };
+
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+            let mut result: f32 = 0.0;
+            unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut result as *mut f32) };
+
+            assert_eq!(result, test_val, "Failed for value: {}", test_val);
+
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
+    }
+
+    #[test]
+    fn test_float_nan() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val = f32::NAN;
+
+        unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: f32 = 0.0;
+        unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut result as *mut f32) };
+
+        assert!(result.is_nan(), "Expected NaN");
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_double_special_values() {
+        let test_values = vec![
+            0.0f64,
+            -0.0f64,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::MIN,
+            f64::MAX,
+        ];
+
+        for test_val in test_values {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, test_val) };
+
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+            let mut result: f64 = 0.0;
+            unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut result as *mut f64) };
+
+            assert_eq!(result, test_val, "Failed for value: {}", test_val);
+
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
+    }
+
+    #[test]
+    fn test_double_nan() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_val = f64::NAN;
+
+        unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, test_val) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: f64 = 0.0;
+        unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut result as *mut f64) };
+
+        assert!(result.is_nan(), "Expected NaN");
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === Multiple Values Test ===
+
+    #[test]
+    fn test_multiple_values_sequence() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+
+        // Write multiple values
+        unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, 42) };
+        unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, std::f32::consts::PI) };
+        unsafe { rs_libp2p_cdr_buffer_write_bool(write_buf, true) };
+        unsafe { rs_libp2p_cdr_buffer_write_int16(write_buf, -100) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        // Read them back in order
+        let mut val1: u32 = 0;
+        let mut val2: f32 = 0.0;
+        let mut val3: bool = false;
+        let mut val4: i16 = 0;
+
+        unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut val1 as *mut u32) };
+        unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut val2 as *mut f32) };
+        unsafe { rs_libp2p_cdr_buffer_read_bool(read_buf, &mut val3 as *mut bool) };
+        unsafe { rs_libp2p_cdr_buffer_read_int16(read_buf, &mut val4 as *mut i16) };
+
+        assert_eq!(val1, 42);
+        assert_eq!(val2, std::f32::consts::PI);
+        assert!(val3);
+        assert_eq!(val4, -100);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === Large Data Stress Test ===
+
+    #[test]
+    fn test_large_data_sequence() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+
+        // Write 10000 values to stress buffer growth
+        for i in 0..10000u32 {
+            unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, i) };
+        }
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        // Read them back and verify
+        for i in 0..10000u32 {
+            let mut val: u32 = 0;
+            unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut val as *mut u32) };
+            assert_eq!(val, i, "Mismatch at index {}", i);
+        }
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_long_string() {
+        // Test with a 10KB string
+        let long_str = "A".repeat(10000);
+        let test_string = CString::new(long_str.clone()).unwrap();
+        let mut buffer = Cursor::new(Vec::<u8>::new());
+        cdr::serialize_into::<_, _, _, cdr::CdrBe>(&mut buffer, &test_string, cdr::Infinite)
+            .unwrap();
+
+        let data = buffer.get_ref().clone();
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut s_ptr: *const c_char = std::ptr::null();
+        let mut size: usize = 0;
+
+        unsafe {
+            rs_libp2p_cdr_buffer_read_string(
+                read_buf,
+                &mut s_ptr as *mut *const c_char,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert!(!s_ptr.is_null());
+        assert_eq!(size, 10000);
+
+        let result_str = unsafe { CStr::from_ptr(s_ptr) };
+        assert_eq!(result_str.to_str().unwrap(), long_str);
+
+        unsafe { rs_libp2p_cdr_buffer_free_string(s_ptr as *mut c_char) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === Null Pointer String Write Tests ===
+
+    #[test]
+    fn test_write_null_string() {
+        // Test writing null string pointer with zero size
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        unsafe { rs_libp2p_cdr_buffer_write_string(write_buf, std::ptr::null(), 0) };
 
-        unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, test_val) };
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut s_ptr: *const c_char = std::ptr::null();
+        let mut size: usize = 0;
+
+        unsafe {
+            rs_libp2p_cdr_buffer_read_string(
+                read_buf,
+                &mut s_ptr as *mut *const c_char,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert_eq!(size, 0);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_write_null_u16string() {
+        // Test writing null u16 string pointer with zero size
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        unsafe { rs_libp2p_cdr_buffer_write_u16string(write_buf, std::ptr::null(), 0) };
 
         let data = get_buffer_data(write_buf);
         let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        let mut result: u32 = 0;
-        unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut result as *mut u32) };
+        let mut s_ptr: *const u16 = std::ptr::null();
+        let mut size: usize = 0;
 
-        assert_eq!(result, test_val);
+        unsafe {
+            rs_libp2p_cdr_buffer_read_u16string(
+                read_buf,
+                &mut s_ptr as *mut *const u16,
+                &mut size as *mut usize,
+            )
+        };
+
+        assert_eq!(size, 0);
 
         unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
         unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
     }
 
+    // === Mixed Type Sequence Tests ===
+
     #[test]
-    fn test_uint16_roundtrip() {
+    fn test_complex_mixed_sequence() {
+        // Test complex real-world scenario with mixed types
         let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val: u16 = 0x0123;
 
-        unsafe { rs_libp2p_cdr_buffer_write_uint16(write_buf, test_val) };
+        // Simulate a ROS 2 message with header and data
+        unsafe { rs_libp2p_cdr_buffer_write_uint64(write_buf, 1234567890) }; // timestamp
+        unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, 42) }; // sequence number
+        let frame_id = CString::new("base_link").unwrap();
+        unsafe {
+            rs_libp2p_cdr_buffer_write_string(
+                write_buf,
+                frame_id.as_ptr(),
+                frame_id.to_bytes().len(),
+            )
+        };
+        unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, 1.23456789) }; // position x
+        unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, 9.87654321) }; // position y
+        unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, 0.0) }; // position z
+        unsafe { rs_libp2p_cdr_buffer_write_bool(write_buf, true) }; // active flag
 
         let data = get_buffer_data(write_buf);
         let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        let mut result: u16 = 0;
-        unsafe { rs_libp2p_cdr_buffer_read_uint16(read_buf, &mut result as *mut u16) };
+        // Read back in order
+        let mut timestamp: u64 = 0;
+        let mut seq: u32 = 0;
+        let mut frame_ptr: *const c_char = std::ptr::null();
+        let mut frame_len: usize = 0;
+        let mut pos_x: f64 = 0.0;
+        let mut pos_y: f64 = 0.0;
+        let mut pos_z: f64 = 0.0;
+        let mut active: bool = false;
+
+        unsafe { rs_libp2p_cdr_buffer_read_uint64(read_buf, &mut timestamp as *mut u64) };
+        unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut seq as *mut u32) };
+        unsafe {
+            rs_libp2p_cdr_buffer_read_string(
+                read_buf,
+                &mut frame_ptr as *mut *const c_char,
+                &mut frame_len as *mut usize,
+            )
+        };
+        unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut pos_x as *mut f64) };
+        unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut pos_y as *mut f64) };
+        unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut pos_z as *mut f64) };
+        unsafe { rs_libp2p_cdr_buffer_read_bool(read_buf, &mut active as *mut bool) };
+
+        assert_eq!(timestamp, 1234567890);
+        assert_eq!(seq, 42);
+        assert!(!frame_ptr.is_null());
+        let frame_str = unsafe { CStr::from_ptr(frame_ptr) };
+        assert_eq!(frame_str.to_str().unwrap(), "base_link");
+        assert_eq!(pos_x, 1.23456789);
+        assert_eq!(pos_y, 9.87654321);
+        assert_eq!(pos_z, 0.0);
+        assert!(active);
+
+        unsafe { rs_libp2p_cdr_buffer_free_string(frame_ptr as *mut c_char) };
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === Zero/Boundary Value Edge Cases ===
+
+    #[test]
+    fn test_zero_values() {
+        // Test that zero values serialize correctly
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+
+        unsafe { rs_libp2p_cdr_buffer_write_uint64(write_buf, 0) };
+        unsafe { rs_libp2p_cdr_buffer_write_int64(write_buf, 0) };
+        unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, 0.0) };
+        unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, 0.0) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut u: u64 = 999;
+        let mut i: i64 = 999;
+        let mut f: f32 = 999.0;
+        let mut d: f64 = 999.0;
+
+        unsafe { rs_libp2p_cdr_buffer_read_uint64(read_buf, &mut u as *mut u64) };
+        unsafe { rs_libp2p_cdr_buffer_read_int64(read_buf, &mut i as *mut i64) };
+        unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut f as *mut f32) };
+        unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut d as *mut f64) };
+
+        assert_eq!(u, 0);
+        assert_eq!(i, 0);
+        assert_eq!(f, 0.0);
+        assert_eq!(d, 0.0);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_negative_zero_float() {
+        // Test that -0.0 is preserved correctly
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, -0.0f32) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut result: f32 = 0.0;
+        unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut result as *mut f32) };
+
+        // -0.0 should equal 0.0 but have different bit pattern
+        assert_eq!(result, -0.0f32);
+        assert_eq!(result.to_bits(), (-0.0f32).to_bits());
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === All Integer Types Comprehensive Test ===
+
+    #[test]
+    fn test_all_integer_types_together() {
+        // Test all integer types in a single buffer
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+
+        unsafe { rs_libp2p_cdr_buffer_write_uint64(write_buf, u64::MAX) };
+        unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, u32::MAX) };
+        unsafe { rs_libp2p_cdr_buffer_write_uint16(write_buf, u16::MAX) };
+        unsafe { rs_libp2p_cdr_buffer_write_uint8(write_buf, u8::MAX) };
+        unsafe { rs_libp2p_cdr_buffer_write_int64(write_buf, i64::MIN) };
+        unsafe { rs_libp2p_cdr_buffer_write_int32(write_buf, i32::MIN) };
+        unsafe { rs_libp2p_cdr_buffer_write_int16(write_buf, i16::MIN) };
+        unsafe { rs_libp2p_cdr_buffer_write_int8(write_buf, i8::MIN) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+        let mut u64_val: u64 = 0;
+        let mut u32_val: u32 = 0;
+        let mut u16_val: u16 = 0;
+        let mut u8_val: u8 = 0;
+        let mut i64_val: i64 = 0;
+        let mut i32_val: i32 = 0;
+        let mut i16_val: i16 = 0;
+        let mut i8_val: i8 = 0;
+
+        unsafe { rs_libp2p_cdr_buffer_read_uint64(read_buf, &mut u64_val as *mut u64) };
+        unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut u32_val as *mut u32) };
+        unsafe { rs_libp2p_cdr_buffer_read_uint16(read_buf, &mut u16_val as *mut u16) };
+        unsafe { rs_libp2p_cdr_buffer_read_uint8(read_buf, &mut u8_val as *mut u8) };
+        unsafe { rs_libp2p_cdr_buffer_read_int64(read_buf, &mut i64_val as *mut i64) };
+        unsafe { rs_libp2p_cdr_buffer_read_int32(read_buf, &mut i32_val as *mut i32) };
+        unsafe { rs_libp2p_cdr_buffer_read_int16(read_buf, &mut i16_val as *mut i16) };
+        unsafe { rs_libp2p_cdr_buffer_read_int8(read_buf, &mut i8_val as *mut i8) };
+
+        assert_eq!(u64_val, u64::MAX);
+        assert_eq!(u32_val, u32::MAX);
+        assert_eq!(u16_val, u16::MAX);
+        assert_eq!(u8_val, u8::MAX);
+        assert_eq!(i64_val, i64::MIN);
+        assert_eq!(i32_val, i32::MIN);
+        assert_eq!(i16_val, i16::MIN);
+        assert_eq!(i8_val, i8::MIN);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    // === Encapsulation Header Roundtrip Tests ===
+
+    #[test]
+    fn test_encapsulated_buffer_roundtrip_big_endian() {
+        let write_buf = rs_libp2p_cdr_buffer_write_new_encapsulated(0);
+
+        unsafe {
+            rs_libp2p_cdr_buffer_write_encapsulated_uint32(write_buf, 0x01234567);
+            rs_libp2p_cdr_buffer_write_encapsulated_double(write_buf, std::f64::consts::PI);
+        }
+
+        let data = unsafe { (*write_buf).cursor.get_ref().clone() };
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_encapsulated(data.as_ptr(), data.len()) };
+
+        let mut u32_val: u32 = 0;
+        let mut f64_val: f64 = 0.0;
+        unsafe {
+            rs_libp2p_cdr_buffer_read_encapsulated_uint32(read_buf, &mut u32_val as *mut u32);
+            rs_libp2p_cdr_buffer_read_encapsulated_double(read_buf, &mut f64_val as *mut f64);
+        }
 
-        assert_eq!(result, test_val);
+        assert_eq!(u32_val, 0x01234567);
+        assert_eq!(f64_val, std::f64::consts::PI);
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_encapsulated(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_encapsulated(read_buf) };
     }
 
     #[test]
-    fn test_uint8_roundtrip() {
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val: u8 = 0x42;
+    fn test_encapsulated_buffer_roundtrip_little_endian() {
+        let write_buf = rs_libp2p_cdr_buffer_write_new_encapsulated(1);
 
-        unsafe { rs_libp2p_cdr_buffer_write_uint8(write_buf, test_val) };
+        unsafe { rs_libp2p_cdr_buffer_write_encapsulated_uint32(write_buf, 0x01234567) };
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+        let data = unsafe { (*write_buf).cursor.get_ref().clone() };
+        // Representation id at byte 0-1 should be CDR_LE (0x0001).
+        assert_eq!(&data[0..2], &[0x00, 0x01]);
 
-        let mut result: u8 = 0;
-        unsafe { rs_libp2p_cdr_buffer_read_uint8(read_buf, &mut result as *mut u8) };
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_encapsulated(data.as_ptr(), data.len()) };
 
-        assert_eq!(result, test_val);
+        let mut u32_val: u32 = 0;
+        unsafe { rs_libp2p_cdr_buffer_read_encapsulated_uint32(read_buf, &mut u32_val as *mut u32) };
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
-    }
+        assert_eq!(u32_val, 0x01234567);
 
-    // === Signed Integer Roundtrip Tests ===
+        unsafe { rs_libp2p_cdr_buffer_free_encapsulated(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_encapsulated(read_buf) };
+    }
 
     #[test]
-    fn test_int64_roundtrip() {
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val: i64 = -0x0123456789ABCDEF;
+    fn test_encapsulated_char16_and_u16string_roundtrip_little_endian() {
+        let write_buf = rs_libp2p_cdr_buffer_write_new_encapsulated(1);
+        let test_data: Vec<u16> = vec![0x41, 0x42, 0x43];
 
-        unsafe { rs_libp2p_cdr_buffer_write_int64(write_buf, test_val) };
+        unsafe {
+            rs_libp2p_cdr_buffer_write_encapsulated_char16(write_buf, 0x3042);
+            rs_libp2p_cdr_buffer_write_encapsulated_u16string(
+                write_buf,
+                test_data.as_ptr(),
+                test_data.len(),
+            );
+        }
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+        let data = unsafe { (*write_buf).cursor.get_ref().clone() };
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_encapsulated(data.as_ptr(), data.len()) };
 
-        let mut result: i64 = 0;
-        unsafe { rs_libp2p_cdr_buffer_read_int64(read_buf, &mut result as *mut i64) };
+        let mut char_val: u16 = 0;
+        let mut out: *mut u16 = std::ptr::null_mut();
+        let mut out_count: usize = 0;
+        unsafe {
+            rs_libp2p_cdr_buffer_read_encapsulated_char16(read_buf, &mut char_val);
+            rs_libp2p_cdr_buffer_read_encapsulated_u16string(
+                read_buf,
+                &mut out,
+                &mut out_count as *mut usize,
+            );
+        }
 
-        assert_eq!(result, test_val);
+        assert_eq!(char_val, 0x3042);
+        let result = unsafe { slice::from_raw_parts(out, out_count) };
+        assert_eq!(result, test_data.as_slice());
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_u16string(out, out_count) };
+        unsafe { rs_libp2p_cdr_buffer_free_encapsulated(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_encapsulated(read_buf) };
     }
 
     #[test]
-    fn test_int32_roundtrip() {
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val: i32 = -0x01234567;
-
-        unsafe { rs_libp2p_cdr_buffer_write_int32(write_buf, test_val) };
-
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
-
-        let mut result: i32 = 0;
-        unsafe { rs_libp2p_cdr_buffer_read_int32(read_buf, &mut result as *mut i32) };
+    fn test_encapsulated_read_returns_underrun_instead_of_panicking_on_a_truncated_buffer() {
+        // Only the 4-byte encapsulation header, no payload: any scalar read must report
+        // `Underrun` rather than indexing past the end of the buffer.
+        let header = [0x00u8, 0x00, 0x00, 0x00];
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_encapsulated(header.as_ptr(), header.len()) };
 
-        assert_eq!(result, test_val);
+        let mut u32_val: u32 = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_encapsulated_uint32(read_buf, &mut u32_val as *mut u32)
+        };
+        assert_eq!(status, CdrBufferStatus::Underrun as i32);
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_encapsulated(read_buf) };
     }
 
     #[test]
-    fn test_int16_roundtrip() {
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val: i16 = -0x0123;
+    fn test_encapsulated_read_u16string_rejects_a_declared_count_longer_than_the_buffer() {
+        // A count of 100 elements (200 bytes) with no element bytes actually present.
+        let mut data = vec![0x00u8, 0x00, 0x00, 0x00];
+        data.extend_from_slice(&100u32.to_be_bytes());
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_encapsulated(data.as_ptr(), data.len()) };
+
+        let mut out: *mut u16 = std::ptr::null_mut();
+        let mut out_count: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_encapsulated_u16string(
+                read_buf,
+                &mut out,
+                &mut out_count as *mut usize,
+            )
+        };
+        assert_eq!(status, CdrBufferStatus::Underrun as i32);
+        assert!(out.is_null());
 
-        unsafe { rs_libp2p_cdr_buffer_write_int16(write_buf, test_val) };
+        unsafe { rs_libp2p_cdr_buffer_free_encapsulated(read_buf) };
+    }
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+    #[test]
+    fn test_versioned_buffer_xcdr2_narrows_eight_byte_alignment_to_four() {
+        // A uint32 followed by a uint64 leaves the cursor at offset 4 under both versions, but
+        // XCDR1 must pad the uint64 up to offset 8 while XCDR2 writes it immediately at offset 4.
+        let xcdr1_buf = rs_libp2p_cdr_buffer_write_new_v2(1, 0);
+        let xcdr2_buf = rs_libp2p_cdr_buffer_write_new_v2(1, 1);
+        unsafe {
+            rs_libp2p_cdr_buffer_write_v2_uint32(xcdr1_buf, 1);
+            rs_libp2p_cdr_buffer_write_v2_uint64(xcdr1_buf, 2);
+            rs_libp2p_cdr_buffer_write_v2_uint32(xcdr2_buf, 1);
+            rs_libp2p_cdr_buffer_write_v2_uint64(xcdr2_buf, 2);
+        }
 
-        let mut result: i16 = 0;
-        unsafe { rs_libp2p_cdr_buffer_read_int16(read_buf, &mut result as *mut i16) };
+        let xcdr1_data = unsafe { (*xcdr1_buf).cursor.get_ref().clone() };
+        let xcdr2_data = unsafe { (*xcdr2_buf).cursor.get_ref().clone() };
+        assert_eq!(xcdr1_data.len(), 16);
+        assert_eq!(xcdr2_data.len(), 12);
 
-        assert_eq!(result, test_val);
+        let read_xcdr1 = unsafe {
+            rs_libp2p_cdr_buffer_read_new_v2(xcdr1_data.as_ptr(), xcdr1_data.len(), 1, 0)
+        };
+        let read_xcdr2 = unsafe {
+            rs_libp2p_cdr_buffer_read_new_v2(xcdr2_data.as_ptr(), xcdr2_data.len(), 1, 1)
+        };
+        let mut u32_val: u32 = 0;
+        let mut u64_val: u64 = 0;
+        unsafe {
+            rs_libp2p_cdr_buffer_read_v2_uint32(read_xcdr1, &mut u32_val);
+            rs_libp2p_cdr_buffer_read_v2_uint64(read_xcdr1, &mut u64_val);
+        }
+        assert_eq!(u32_val, 1);
+        assert_eq!(u64_val, 2);
+        unsafe {
+            rs_libp2p_cdr_buffer_read_v2_uint32(read_xcdr2, &mut u32_val);
+            rs_libp2p_cdr_buffer_read_v2_uint64(read_xcdr2, &mut u64_val);
+        }
+        assert_eq!(u32_val, 1);
+        assert_eq!(u64_val, 2);
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        unsafe {
+            rs_libp2p_cdr_buffer_free_v2(xcdr1_buf);
+            rs_libp2p_cdr_buffer_free_v2(xcdr2_buf);
+            rs_libp2p_cdr_buffer_free_v2(read_xcdr1);
+            rs_libp2p_cdr_buffer_free_v2(read_xcdr2);
+        }
     }
 
     #[test]
-    fn test_int8_roundtrip() {
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val: i8 = -42;
+    fn test_versioned_buffer_v2_double_roundtrip() {
+        let write_buf = rs_libp2p_cdr_buffer_write_new_v2(0, 1);
+        unsafe { rs_libp2p_cdr_buffer_write_v2_double(write_buf, 2.5) };
+        let data = unsafe { (*write_buf).cursor.get_ref().clone() };
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_v2(data.as_ptr(), data.len(), 0, 1) };
+        let mut value: f64 = 0.0;
+        unsafe { rs_libp2p_cdr_buffer_read_v2_double(read_buf, &mut value) };
+        assert_eq!(value, 2.5);
+        unsafe {
+            rs_libp2p_cdr_buffer_free_v2(write_buf);
+            rs_libp2p_cdr_buffer_free_v2(read_buf);
+        }
+    }
 
-        unsafe { rs_libp2p_cdr_buffer_write_int8(write_buf, test_val) };
+    #[test]
+    fn test_dheader_roundtrip_skips_unknown_trailing_members() {
+        let write_buf = rs_libp2p_cdr_buffer_write_new_v2(1, 1);
+        unsafe {
+            rs_libp2p_cdr_buffer_begin_dheader(write_buf);
+            rs_libp2p_cdr_buffer_write_v2_uint32(write_buf, 0xAAAA);
+            // A trailing member a reader built against an older IDL revision won't recognize.
+            rs_libp2p_cdr_buffer_write_v2_uint32(write_buf, 0xBBBB);
+            rs_libp2p_cdr_buffer_end_dheader(write_buf);
+            // A sibling member following the DHEADER-delimited block, to prove skipping lands
+            // exactly at its boundary rather than overrunning into it.
+            rs_libp2p_cdr_buffer_write_v2_uint32(write_buf, 0xCCCC);
+        }
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+        let data = unsafe { (*write_buf).cursor.get_ref().clone() };
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_v2(data.as_ptr(), data.len(), 1, 1) };
+        let mut first_member: u32 = 0;
+        let mut sibling: u32 = 0;
+        unsafe {
+            rs_libp2p_cdr_buffer_read_dheader(read_buf);
+            rs_libp2p_cdr_buffer_read_v2_uint32(read_buf, &mut first_member);
+            // Deliberately not reading the second (unknown) member before skipping.
+            rs_libp2p_cdr_buffer_skip_to_dheader_end(read_buf);
+            rs_libp2p_cdr_buffer_read_v2_uint32(read_buf, &mut sibling);
+        }
 
-        let mut result: i8 = 0;
-        unsafe { rs_libp2p_cdr_buffer_read_int8(read_buf, &mut result as *mut i8) };
+        assert_eq!(first_member, 0xAAAA);
+        assert_eq!(sibling, 0xCCCC);
 
-        assert_eq!(result, test_val);
+        unsafe {
+            rs_libp2p_cdr_buffer_free_v2(write_buf);
+            rs_libp2p_cdr_buffer_free_v2(read_buf);
+        }
+    }
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    #[test]
+    fn test_v2_read_returns_underrun_instead_of_panicking_on_a_truncated_buffer() {
+        let data: [u8; 0] = [];
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_v2(data.as_ptr(), data.len(), 1, 1) };
+        let mut value: u32 = 0;
+        let status = unsafe { rs_libp2p_cdr_buffer_read_v2_uint32(read_buf, &mut value) };
+        assert_eq!(status, CdrBufferStatus::Underrun as i32);
+        unsafe { rs_libp2p_cdr_buffer_free_v2(read_buf) };
     }
 
-    // === Character Roundtrip Tests ===
+    #[test]
+    fn test_read_dheader_rejects_a_declared_member_length_past_the_end_of_the_buffer() {
+        // A DHEADER claiming a 1000-byte member block with nothing actually behind it: a hostile
+        // peer forging this must not let `skip_to_dheader_end` seek past the buffer's real end.
+        let mut data = 1000u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&[0u8; 4]);
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_v2(data.as_ptr(), data.len(), 1, 1) };
+
+        let status = unsafe { rs_libp2p_cdr_buffer_read_dheader(read_buf) };
+        assert_eq!(status, CdrBufferStatus::Underrun as i32);
+
+        // Since the DHEADER was rejected, no end offset was pushed, so skipping is a no-op
+        // instead of seeking anywhere.
+        let skip_status = unsafe { rs_libp2p_cdr_buffer_skip_to_dheader_end(read_buf) };
+        assert_eq!(skip_status, CdrBufferStatus::Ok as i32);
+
+        unsafe { rs_libp2p_cdr_buffer_free_v2(read_buf) };
+    }
 
     #[test]
-    fn test_char_roundtrip() {
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val: c_char = b'A' as c_char;
+    fn test_write_new_encapsulated_with_representation_selects_pl_cdr() {
+        let write_buf = rs_libp2p_cdr_buffer_write_new_encapsulated_with_representation(
+            CDR_REPRESENTATION_PL_CDR_LE,
+        );
+        unsafe { rs_libp2p_cdr_buffer_write_encapsulated_uint32(write_buf, 99) };
+
+        let data = unsafe { (*write_buf).cursor.get_ref().clone() };
+        assert_eq!(&data[0..2], &CDR_REPRESENTATION_PL_CDR_LE.to_be_bytes());
+        let representation_id = u16::from_be_bytes([data[0], data[1]]);
+        assert!(rs_libp2p_cdr_buffer_encapsulation_is_parameter_list(
+            representation_id
+        ));
+
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_encapsulated(data.as_ptr(), data.len()) };
+        let mut u32_val: u32 = 0;
+        unsafe { rs_libp2p_cdr_buffer_read_encapsulated_uint32(read_buf, &mut u32_val as *mut u32) };
+        assert_eq!(u32_val, 99);
 
-        unsafe { rs_libp2p_cdr_buffer_write_char(write_buf, test_val) };
+        unsafe { rs_libp2p_cdr_buffer_free_encapsulated(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_encapsulated(read_buf) };
+    }
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+    #[test]
+    fn test_encapsulation_is_parameter_list_distinguishes_plain_and_pl_cdr() {
+        assert!(!rs_libp2p_cdr_buffer_encapsulation_is_parameter_list(
+            CDR_REPRESENTATION_CDR_BE
+        ));
+        assert!(!rs_libp2p_cdr_buffer_encapsulation_is_parameter_list(
+            CDR_REPRESENTATION_CDR_LE
+        ));
+        assert!(rs_libp2p_cdr_buffer_encapsulation_is_parameter_list(
+            CDR_REPRESENTATION_PL_CDR_BE
+        ));
+        assert!(rs_libp2p_cdr_buffer_encapsulation_is_parameter_list(
+            CDR_REPRESENTATION_PL_CDR_LE
+        ));
+    }
 
-        let mut result: c_char = 0;
-        unsafe { rs_libp2p_cdr_buffer_read_char(read_buf, &mut result as *mut c_char) };
+    #[test]
+    fn test_encapsulated_buffer_alignment_accumulates_across_fields() {
+        // A u32 then a u8 then a double must see the double 8-byte aligned from the body start
+        // (4 + 1 = 5 bytes in, padded to 8), not realigned from its own call.
+        let write_buf = rs_libp2p_cdr_buffer_write_new_encapsulated(0);
+        unsafe {
+            rs_libp2p_cdr_buffer_write_encapsulated_uint32(write_buf, 1);
+            (*write_buf).cursor.get_mut().push(0xAB);
+            (*write_buf).cursor.set_position((*write_buf).cursor.position() + 1);
+            rs_libp2p_cdr_buffer_write_encapsulated_double(write_buf, 2.0);
+        }
 
-        assert_eq!(result, test_val);
+        let data = unsafe { (*write_buf).cursor.get_ref().clone() };
+        // header(4) + u32(4) + u8(1) + padding(3) + f64(8) = 20 bytes
+        assert_eq!(data.len(), 20);
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_encapsulated(write_buf) };
     }
 
     #[test]
-    fn test_char16_roundtrip() {
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val: u16 = 0x3042; // Japanese Hiragana '„ÅÇ'
-
-        unsafe { rs_libp2p_cdr_buffer_write_char16(write_buf, test_val) };
-
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+    fn test_write_new_with_endianness_matches_write_new_encapsulated() {
+        let write_buf = rs_libp2p_cdr_buffer_write_new_with_endianness(1);
+        unsafe { rs_libp2p_cdr_buffer_write_encapsulated_int32(write_buf, -42) };
 
-        let mut result: u16 = 0;
-        unsafe { rs_libp2p_cdr_buffer_read_char16(read_buf, &mut result as *mut u16) };
+        let data = unsafe { (*write_buf).cursor.get_ref().clone() };
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_encapsulated(data.as_ptr(), data.len()) };
 
-        assert_eq!(result, test_val);
+        let mut value: i32 = 0;
+        unsafe { rs_libp2p_cdr_buffer_read_encapsulated_int32(read_buf, &mut value as *mut i32) };
+        assert_eq!(value, -42);
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_encapsulated(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_encapsulated(read_buf) };
     }
 
-    // === Floating Point Roundtrip Tests ===
+    // === Bulk Array Roundtrip Tests ===
 
     #[test]
-    fn test_float_roundtrip() {
+    fn test_uint8_array_roundtrip() {
         let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val: f32 = std::f32::consts::PI;
+        let test_data: Vec<u8> = vec![1, 2, 3, 4, 5];
 
-        unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, test_val) };
+        unsafe {
+            rs_libp2p_cdr_buffer_write_uint8_array(write_buf, test_data.as_ptr(), test_data.len())
+        };
 
         let data = get_buffer_data(write_buf);
         let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        let mut result: f32 = 0.0;
-        unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut result as *mut f32) };
+        let mut out: *mut u8 = std::ptr::null_mut();
+        let mut out_count: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_uint8_array(read_buf, &mut out, &mut out_count as *mut usize)
+        };
 
-        assert_eq!(result, test_val);
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(out_count, test_data.len());
+        let result = unsafe { slice::from_raw_parts(out, out_count) };
+        assert_eq!(result, test_data.as_slice());
 
+        unsafe { rs_libp2p_cdr_buffer_free_uint8_array(out, out_count) };
         unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
         unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
     }
 
     #[test]
-    fn test_double_roundtrip() {
+    fn test_float_array_roundtrip() {
         let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val: f64 = std::f64::consts::E;
+        let test_data: Vec<f32> = vec![1.5, -2.25, 0.0, f32::MAX];
 
-        unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, test_val) };
+        unsafe {
+            rs_libp2p_cdr_buffer_write_float_array(write_buf, test_data.as_ptr(), test_data.len())
+        };
 
         let data = get_buffer_data(write_buf);
         let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        let mut result: f64 = 0.0;
-        unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut result as *mut f64) };
+        let mut out: *mut f32 = std::ptr::null_mut();
+        let mut out_count: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_float_array(read_buf, &mut out, &mut out_count as *mut usize)
+        };
 
-        assert_eq!(result, test_val);
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        let result = unsafe { slice::from_raw_parts(out, out_count) };
+        assert_eq!(result, test_data.as_slice());
 
+        unsafe { rs_libp2p_cdr_buffer_free_float_array(out, out_count) };
         unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
         unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
     }
 
-    // === Boolean Roundtrip Tests ===
-
     #[test]
-    fn test_bool_roundtrip_true() {
+    fn test_double_array_empty() {
         let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val = true;
+        let test_data: Vec<f64> = Vec::new();
 
-        unsafe { rs_libp2p_cdr_buffer_write_bool(write_buf, test_val) };
+        unsafe {
+            rs_libp2p_cdr_buffer_write_double_array(write_buf, test_data.as_ptr(), test_data.len())
+        };
 
         let data = get_buffer_data(write_buf);
         let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        let mut result = false;
-        unsafe { rs_libp2p_cdr_buffer_read_bool(read_buf, &mut result as *mut bool) };
+        let mut out: *mut f64 = std::ptr::null_mut();
+        let mut out_count: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_double_array(read_buf, &mut out, &mut out_count as *mut usize)
+        };
 
-        assert_eq!(result, test_val);
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(out_count, 0);
 
         unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
         unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
     }
 
     #[test]
-    fn test_bool_roundtrip_false() {
+    fn test_checked_array_readers_roundtrip() {
         let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val = false;
+        let uint32_data: Vec<u32> = vec![1, 2, 3, u32::MAX];
+        let float_data: Vec<f32> = vec![1.5, -2.25, 0.0];
+        let double_data: Vec<f64> = vec![3.14159, -1.0];
 
-        unsafe { rs_libp2p_cdr_buffer_write_bool(write_buf, test_val) };
+        unsafe {
+            rs_libp2p_cdr_buffer_write_uint32_array(
+                write_buf,
+                uint32_data.as_ptr(),
+                uint32_data.len(),
+            );
+            rs_libp2p_cdr_buffer_write_float_array(write_buf, float_data.as_ptr(), float_data.len());
+            rs_libp2p_cdr_buffer_write_double_array(
+                write_buf,
+                double_data.as_ptr(),
+                double_data.len(),
+            );
+        }
 
         let data = get_buffer_data(write_buf);
         let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        let mut result = true;
-        unsafe { rs_libp2p_cdr_buffer_read_bool(read_buf, &mut result as *mut bool) };
-
-        assert_eq!(result, test_val);
+        let mut uint32_out: *mut u32 = std::ptr::null_mut();
+        let mut uint32_count: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_uint32_array_checked(
+                read_buf,
+                &mut uint32_out,
+                &mut uint32_count as *mut usize,
+            )
+        };
+        assert_eq!(status, CdrStatus::Valid as i32);
+        assert_eq!(
+            unsafe { slice::from_raw_parts(uint32_out, uint32_count) },
+            uint32_data.as_slice()
+        );
+
+        let mut float_out: *mut f32 = std::ptr::null_mut();
+        let mut float_count: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_float_array_checked(
+                read_buf,
+                &mut float_out,
+                &mut float_count as *mut usize,
+            )
+        };
+        assert_eq!(status, CdrStatus::Valid as i32);
+        assert_eq!(
+            unsafe { slice::from_raw_parts(float_out, float_count) },
+            float_data.as_slice()
+        );
+
+        let mut double_out: *mut f64 = std::ptr::null_mut();
+        let mut double_count: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_double_array_checked(
+                read_buf,
+                &mut double_out,
+                &mut double_count as *mut usize,
+            )
+        };
+        assert_eq!(status, CdrStatus::Valid as i32);
+        assert_eq!(
+            unsafe { slice::from_raw_parts(double_out, double_count) },
+            double_data.as_slice()
+        );
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        unsafe {
+            rs_libp2p_cdr_buffer_free_uint32_array(uint32_out, uint32_count);
+            rs_libp2p_cdr_buffer_free_float_array(float_out, float_count);
+            rs_libp2p_cdr_buffer_free_double_array(double_out, double_count);
+            rs_libp2p_cdr_buffer_free(write_buf);
+            rs_libp2p_cdr_buffer_free(read_buf);
+        }
     }
 
-    // === String Roundtrip Tests ===
-
     #[test]
-    fn test_string_roundtrip() {
-        // Manually serialize a string using CDR
-        let test_string = CString::new("Hello, World!").unwrap();
-        let mut buffer = Cursor::new(Vec::<u8>::new());
-        cdr::serialize_into::<_, _, _, cdr::CdrBe>(&mut buffer, &test_string, cdr::Infinite)
-            .unwrap();
-
-        let data = buffer.get_ref().clone();
+    fn test_checked_array_reader_rejects_declared_length_exceeding_buffer() {
+        // A hostile peer claims 0x7fff_ffff elements, but only supplies a handful of real bytes.
+        let mut data: Vec<u8> = (0x7fff_ffffu32).to_be_bytes().to_vec();
+        data.extend_from_slice(&[0u8; 8]);
         let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        let mut s_ptr: *const c_char = std::ptr::null();
-        let mut size: usize = 0;
-
-        unsafe {
-            rs_libp2p_cdr_buffer_read_string(
+        let mut out: *mut u32 = std::ptr::null_mut();
+        let mut out_count: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_uint32_array_checked(
                 read_buf,
-                &mut s_ptr as *mut *const c_char,
-                &mut size as *mut usize,
+                &mut out,
+                &mut out_count as *mut usize,
             )
         };
 
-        assert!(!s_ptr.is_null());
-        assert_eq!(size, 13);
-
-        let result_str = unsafe { CStr::from_ptr(s_ptr) };
-        assert_eq!(result_str.to_str().unwrap(), "Hello, World!");
+        assert_eq!(status, CdrStatus::InvalidLength as i32);
+        assert!(out.is_null());
 
-        unsafe { rs_libp2p_cdr_buffer_free_string(s_ptr as *mut c_char) };
         unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
     }
 
     #[test]
-    fn test_empty_string() {
-        let test_string = CString::new("").unwrap();
-        let mut buffer = Cursor::new(Vec::<u8>::new());
-        cdr::serialize_into::<_, _, _, cdr::CdrBe>(&mut buffer, &test_string, cdr::Infinite)
-            .unwrap();
+    fn test_checked_array_read_rejects_null_pointer() {
+        let mut out: *mut f32 = std::ptr::null_mut();
+        let mut out_count: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_float_array_checked(
+                std::ptr::null_mut(),
+                &mut out,
+                &mut out_count as *mut usize,
+            )
+        };
+        assert_eq!(status, CdrStatus::NullBuffer as i32);
+    }
 
-        let data = buffer.get_ref().clone();
+    #[test]
+    fn test_array_read_rejects_null_pointer() {
+        let mut out: *mut u8 = std::ptr::null_mut();
+        let mut out_count: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_uint8_array(
+                std::ptr::null_mut(),
+                &mut out,
+                &mut out_count as *mut usize,
+            )
+        };
+        assert_eq!(status, CdrBufferStatus::NullPointer as i32);
+    }
+
+    // === Write Status Code Tests ===
+
+    #[test]
+    fn test_scalar_write_rejects_null_pointer() {
+        let status = unsafe { rs_libp2p_cdr_buffer_write_uint32(std::ptr::null_mut(), 42) };
+        assert_eq!(status, CdrBufferStatus::NullPointer as i32);
+    }
+
+    #[test]
+    fn test_scalar_write_returns_ok_and_roundtrips() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let status = unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, 123) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+
+        let data = unsafe { &*write_buf }.get_ref().clone();
         let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+        let mut n: u32 = 0;
+        let status = unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut n) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(n, 123);
 
-        let mut s_ptr: *const c_char = std::ptr::null();
-        let mut size: usize = 0;
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
 
-        unsafe {
-            rs_libp2p_cdr_buffer_read_string(
-                read_buf,
-                &mut s_ptr as *mut *const c_char,
-                &mut size as *mut usize,
+    #[test]
+    fn test_write_string_returns_ok_and_roundtrips() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_string = CString::new("hello").unwrap();
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_write_string(
+                write_buf,
+                test_string.as_ptr(),
+                test_string.as_bytes().len(),
             )
         };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+    }
 
-        assert_eq!(size, 0);
-        // Empty strings should not set the pointer
-
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    #[test]
+    fn test_array_write_rejects_null_pointer() {
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_write_uint8_array(std::ptr::null_mut(), std::ptr::null(), 0)
+        };
+        assert_eq!(status, CdrBufferStatus::NullPointer as i32);
     }
 
-    // === Write/Read String Roundtrip Tests ===
+    // === Bounded Deserialization Tests ===
 
     #[test]
-    fn test_string_write_read_roundtrip() {
-        // Test basic ASCII string roundtrip through write/read functions
+    fn test_bounded_string_within_limit_roundtrips() {
         let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_string = CString::new("Hello, World!").unwrap();
-
+        let test_string = CString::new("hello").unwrap();
         unsafe {
             rs_libp2p_cdr_buffer_write_string(
                 write_buf,
@@ -1307,36 +7850,27 @@ mod tests {
         };
 
         let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_bounded(data.as_ptr(), data.len(), 1024) };
 
         let mut s_ptr: *const c_char = std::ptr::null();
         let mut size: usize = 0;
-
-        unsafe {
-            rs_libp2p_cdr_buffer_read_string(
-                read_buf,
-                &mut s_ptr as *mut *const c_char,
-                &mut size as *mut usize,
-            )
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_string_bounded(read_buf, &mut s_ptr, &mut size as *mut usize)
         };
 
-        assert!(!s_ptr.is_null());
-        assert_eq!(size, 13);
-
-        let result_str = unsafe { CStr::from_ptr(s_ptr) };
-        assert_eq!(result_str.to_str().unwrap(), "Hello, World!");
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(size, 5);
 
         unsafe { rs_libp2p_cdr_buffer_free_string(s_ptr as *mut c_char) };
         unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_bounded(read_buf) };
     }
 
     #[test]
-    fn test_empty_string_write_read_roundtrip() {
-        // Test empty string edge case through write/read functions
+    fn test_bounded_string_exceeding_cap_is_rejected() {
         let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_string = CString::new("").unwrap();
-
+        let test_string = CString::new("this string is too long for the cap").unwrap();
         unsafe {
             rs_libp2p_cdr_buffer_write_string(
                 write_buf,
@@ -1346,72 +7880,83 @@ mod tests {
         };
 
         let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+        // Cap smaller than the declared string length: must be rejected, not allocated.
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_bounded(data.as_ptr(), data.len(), 4) };
 
         let mut s_ptr: *const c_char = std::ptr::null();
         let mut size: usize = 0;
-
-        unsafe {
-            rs_libp2p_cdr_buffer_read_string(
-                read_buf,
-                &mut s_ptr as *mut *const c_char,
-                &mut size as *mut usize,
-            )
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_string_bounded(read_buf, &mut s_ptr, &mut size as *mut usize)
         };
 
-        assert_eq!(size, 0);
+        assert_ne!(status, CdrBufferStatus::Ok as i32);
+        assert!(s_ptr.is_null());
 
         unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_bounded(read_buf) };
     }
 
     #[test]
-    fn test_long_string_write_read_roundtrip() {
-        // Test 10KB string stress test through write/read functions
+    fn test_bounded_uint8_array_exceeding_cap_is_rejected() {
         let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_data = "A".repeat(10240); // 10KB string
-        let test_string = CString::new(test_data.as_str()).unwrap();
-
+        let test_data: Vec<u8> = vec![1, 2, 3, 4, 5, 6, 7, 8];
         unsafe {
-            rs_libp2p_cdr_buffer_write_string(
-                write_buf,
-                test_string.as_ptr(),
-                test_string.to_bytes().len(),
-            )
+            rs_libp2p_cdr_buffer_write_uint8_array(write_buf, test_data.as_ptr(), test_data.len())
         };
 
         let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
-
-        let mut s_ptr: *const c_char = std::ptr::null();
-        let mut size: usize = 0;
+        let read_buf =
+            unsafe { rs_libp2p_cdr_buffer_read_new_bounded(data.as_ptr(), data.len(), 2) };
 
-        unsafe {
-            rs_libp2p_cdr_buffer_read_string(
+        let mut out: *mut u8 = std::ptr::null_mut();
+        let mut out_count: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_uint8_array_bounded(
                 read_buf,
-                &mut s_ptr as *mut *const c_char,
-                &mut size as *mut usize,
+                &mut out,
+                &mut out_count as *mut usize,
             )
         };
 
-        assert!(!s_ptr.is_null());
-        assert_eq!(size, 10240);
-
-        let result_str = unsafe { CStr::from_ptr(s_ptr) };
-        assert_eq!(result_str.to_str().unwrap(), test_data);
+        assert_ne!(status, CdrBufferStatus::Ok as i32);
+        assert!(out.is_null());
 
-        unsafe { rs_libp2p_cdr_buffer_free_string(s_ptr as *mut c_char) };
         unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_bounded(read_buf) };
     }
 
+    // === Borrowed (Zero-Copy) Read Buffer Tests ===
+
     #[test]
-    fn test_unicode_string_write_read_roundtrip() {
-        // Test UTF-8 emoji/multibyte characters through write/read functions
+    fn test_borrowed_buffer_roundtrip() {
         let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_data = "Hello üëã ‰∏ñÁïå üåç";
-        let test_string = CString::new(test_data).unwrap();
+        unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, 0xDEADBEEF) };
+        unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, std::f64::consts::E) };
+
+        let data = get_buffer_data(write_buf);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_borrowed(data.as_ptr(), data.len()) };
+
+        let mut u32_val: u32 = 0;
+        let mut f64_val: f64 = 0.0;
+        let status1 = unsafe { rs_libp2p_cdr_buffer_read_borrowed_uint32(read_buf, &mut u32_val) };
+        let status2 = unsafe { rs_libp2p_cdr_buffer_read_borrowed_double(read_buf, &mut f64_val) };
 
+        assert_eq!(status1, CdrBufferStatus::Ok as i32);
+        assert_eq!(status2, CdrBufferStatus::Ok as i32);
+        assert_eq!(u32_val, 0xDEADBEEF);
+        assert_eq!(f64_val, std::f64::consts::E);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_borrowed(read_buf) };
+    }
+
+    #[test]
+    fn test_borrowed_buffer_does_not_copy_on_creation() {
+        // A borrowed buffer over a caller-owned byte slice must read back exactly what's there without the
+        // caller ever handing over ownership of a Vec.
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_string = CString::new("borrowed!").unwrap();
         unsafe {
             rs_libp2p_cdr_buffer_write_string(
                 write_buf,
@@ -1419,209 +7964,519 @@ mod tests {
                 test_string.to_bytes().len(),
             )
         };
-
         let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_borrowed(data.as_ptr(), data.len()) };
         let mut s_ptr: *const c_char = std::ptr::null();
         let mut size: usize = 0;
+        let status =
+            unsafe { rs_libp2p_cdr_buffer_read_borrowed_string(read_buf, &mut s_ptr, &mut size) };
 
-        unsafe {
-            rs_libp2p_cdr_buffer_read_string(
-                read_buf,
-                &mut s_ptr as *mut *const c_char,
-                &mut size as *mut usize,
-            )
-        };
-
-        assert!(!s_ptr.is_null());
-
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(size, 9);
         let result_str = unsafe { CStr::from_ptr(s_ptr) };
-        assert_eq!(result_str.to_str().unwrap(), test_data);
+        assert_eq!(result_str.to_str().unwrap(), "borrowed!");
 
         unsafe { rs_libp2p_cdr_buffer_free_string(s_ptr as *mut c_char) };
         unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_borrowed(read_buf) };
     }
 
-    // === Write/Read U16String Roundtrip Tests ===
-
     #[test]
-    fn test_u16string_write_read_roundtrip() {
-        // Test basic u16 array roundtrip through write/read functions
+    fn test_map_u16string_points_into_backing_buffer_without_copying() {
         let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_data: Vec<u16> = vec![0x0041, 0x0042, 0x0043]; // ABC
-
+        let test_data: Vec<u16> = vec![0x41, 0x42, 0x43];
         unsafe {
             rs_libp2p_cdr_buffer_write_u16string(write_buf, test_data.as_ptr(), test_data.len())
         };
-
         let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        let mut s_ptr: *const u16 = std::ptr::null();
-        let mut size: usize = 0;
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_borrowed(data.as_ptr(), data.len()) };
+        let mut out_ptr: *const u16 = std::ptr::null();
+        let mut out_len: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_map_u16string(read_buf, &mut out_ptr, &mut out_len as *mut usize)
+        };
+
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(out_len, test_data.len());
+        let mapped = unsafe { slice::from_raw_parts(out_ptr, out_len) };
+        assert_eq!(mapped, test_data.as_slice());
+        // The mapped pointer aliases the caller's own `data` buffer rather than a fresh allocation.
+        assert!(out_ptr as usize >= data.as_ptr() as usize);
+        assert!((out_ptr as usize) < data.as_ptr() as usize + data.len());
 
+        unsafe { rs_libp2p_cdr_buffer_unmap(out_ptr as *const std::ffi::c_void, out_len) };
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_borrowed(read_buf) };
+    }
+
+    #[test]
+    fn test_map_sequence_uint32_points_into_backing_buffer_without_copying() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_data: Vec<u32> = vec![10, 20, 30, 40];
         unsafe {
-            rs_libp2p_cdr_buffer_read_u16string(
+            rs_libp2p_cdr_buffer_write_uint32_array(write_buf, test_data.as_ptr(), test_data.len())
+        };
+        let data = get_buffer_data(write_buf);
+
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_borrowed(data.as_ptr(), data.len()) };
+        let mut out_ptr: *const u32 = std::ptr::null();
+        let mut out_len: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_map_sequence_uint32(
                 read_buf,
-                &mut s_ptr as *mut *const u16,
-                &mut size as *mut usize,
+                &mut out_ptr,
+                &mut out_len as *mut usize,
             )
         };
 
-        assert!(!s_ptr.is_null());
-        assert_eq!(size, 3);
-
-        let result_slice = unsafe { slice::from_raw_parts(s_ptr, size) };
-        assert_eq!(result_slice, test_data.as_slice());
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(out_len, test_data.len());
+        let mapped = unsafe { slice::from_raw_parts(out_ptr, out_len) };
+        assert_eq!(mapped, test_data.as_slice());
 
-        unsafe { rs_libp2p_cdr_buffer_free_u16string(s_ptr as *mut u16, size) };
+        unsafe { rs_libp2p_cdr_buffer_unmap(out_ptr as *const std::ffi::c_void, out_len) };
         unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_borrowed(read_buf) };
     }
 
     #[test]
-    fn test_empty_u16string_write_read_roundtrip() {
-        // Test empty Vec<u16> edge case through write/read functions
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_data: Vec<u16> = Vec::new();
-
-        unsafe {
-            rs_libp2p_cdr_buffer_write_u16string(write_buf, test_data.as_ptr(), test_data.len())
+    fn test_map_u16string_rejects_declared_length_exceeding_buffer() {
+        let mut data: Vec<u8> = (0x7fff_ffffu32).to_be_bytes().to_vec();
+        data.extend_from_slice(&[0u8; 4]);
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_borrowed(data.as_ptr(), data.len()) };
+
+        let mut out_ptr: *const u16 = std::ptr::null();
+        let mut out_len: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_map_u16string(read_buf, &mut out_ptr, &mut out_len as *mut usize)
         };
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+        assert_eq!(status, CdrBufferStatus::Underrun as i32);
+        assert!(out_ptr.is_null());
 
-        let mut s_ptr: *const u16 = std::ptr::null();
-        let mut size: usize = 0;
+        unsafe { rs_libp2p_cdr_buffer_free_borrowed(read_buf) };
+    }
 
-        unsafe {
-            rs_libp2p_cdr_buffer_read_u16string(
-                read_buf,
-                &mut s_ptr as *mut *const u16,
-                &mut size as *mut usize,
+    #[test]
+    fn test_map_u16string_rejects_null_pointer() {
+        let mut out_ptr: *const u16 = std::ptr::null();
+        let mut out_len: usize = 0;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_map_u16string(
+                std::ptr::null_mut(),
+                &mut out_ptr,
+                &mut out_len as *mut usize,
             )
         };
+        assert_eq!(status, CdrBufferStatus::NullPointer as i32);
+    }
 
-        assert_eq!(size, 0);
+    // === Segmented (Scatter/Gather) Reader Tests ===
+
+    #[test]
+    fn test_segmented_reader_spans_segment_boundaries() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, 0xCAFEF00D) };
+        let data = get_buffer_data(write_buf);
+
+        // Split the encoded bytes across three frames, none of which line up with the u32's
+        // own 4-byte boundary, to exercise a read spanning multiple segments.
+        let split_points = [1, 3];
+        let mut segments = Vec::new();
+        let mut start = 0;
+        for &point in &split_points {
+            segments.push(CdrSegmentView {
+                data: unsafe { data.as_ptr().add(start) },
+                len: point - start,
+            });
+            start = point;
+        }
+        segments.push(CdrSegmentView {
+            data: unsafe { data.as_ptr().add(start) },
+            len: data.len() - start,
+        });
+
+        let reader =
+            unsafe { rs_libp2p_cdr_buffer_read_new_segmented(segments.as_ptr(), segments.len()) };
+        let mut n: u32 = 0;
+        let status = unsafe { rs_libp2p_cdr_buffer_read_segmented_uint32(reader, &mut n) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(n, 0xCAFEF00D);
 
         unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_segmented(reader) };
     }
 
     #[test]
-    fn test_long_u16string_write_read_roundtrip() {
-        // Test 10000 element u16 array stress test through write/read functions
+    fn test_segmented_reader_seek_variants() {
         let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_data: Vec<u16> = (0..10000).map(|i| (i % 65536) as u16).collect();
+        unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, 1) };
+        unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, 2) };
+        let data = get_buffer_data(write_buf);
+
+        let segments = [
+            CdrSegmentView {
+                data: data.as_ptr(),
+                len: 4,
+            },
+            CdrSegmentView {
+                data: unsafe { data.as_ptr().add(4) },
+                len: 4,
+            },
+        ];
+        let reader =
+            unsafe { rs_libp2p_cdr_buffer_read_new_segmented(segments.as_ptr(), segments.len()) };
+
+        let mut pos: u64 = 0;
+        let status = unsafe { rs_libp2p_cdr_buffer_seek_segmented(reader, 2, 0, &mut pos) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(pos, 8);
+
+        let status = unsafe { rs_libp2p_cdr_buffer_seek_segmented(reader, 0, 4, &mut pos) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(pos, 4);
+        let mut n: u32 = 0;
+        assert_eq!(
+            unsafe { rs_libp2p_cdr_buffer_read_segmented_uint32(reader, &mut n) },
+            CdrBufferStatus::Ok as i32
+        );
+        assert_eq!(n, 2);
+
+        let status = unsafe { rs_libp2p_cdr_buffer_seek_segmented(reader, 1, -100, &mut pos) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(pos, 0);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        unsafe { rs_libp2p_cdr_buffer_free_segmented(reader) };
+    }
+
+    #[test]
+    fn test_segmented_read_rejects_null_pointer() {
+        let mut n: u32 = 0;
+        let status =
+            unsafe { rs_libp2p_cdr_buffer_read_segmented_uint32(std::ptr::null_mut(), &mut n) };
+        assert_eq!(status, CdrBufferStatus::NullPointer as i32);
+    }
+
+    // === Chunked (Scatter/Gather) Write Buffer Tests ===
+
+    #[test]
+    fn test_chunked_buffer_grows_without_copying_existing_chunks() {
+        let buf = unsafe { rs_libp2p_cdr_buffer_write_new_chunked() };
+        // Enough uint32s to force several chunk boundaries at the 4 KiB chunk size.
+        for i in 0..2000u32 {
+            let status = unsafe { rs_libp2p_cdr_buffer_write_chunked_uint32(buf, i) };
+            assert_eq!(status, CdrBufferStatus::Ok as i32);
+        }
+        assert!(unsafe { rs_libp2p_cdr_buffer_chunked_scatter_count(buf) } > 1);
+
+        let flat = unsafe { &*buf }.as_contiguous();
+        assert_eq!(flat.len(), 2000 * 4);
+        assert_eq!(u32::from_be_bytes(flat[0..4].try_into().unwrap()), 0);
+        assert_eq!(u32::from_be_bytes(flat[7996..8000].try_into().unwrap()), 1999);
+
+        unsafe { rs_libp2p_cdr_buffer_free_chunked(buf) };
+    }
 
+    #[test]
+    fn test_chunked_buffer_flatten_matches_scatter_views() {
+        let buf = unsafe { rs_libp2p_cdr_buffer_write_new_chunked() };
+        unsafe { rs_libp2p_cdr_buffer_write_chunked_uint32(buf, 0xABCD1234) };
+        let test_string = CString::new("chunked!").unwrap();
         unsafe {
-            rs_libp2p_cdr_buffer_write_u16string(write_buf, test_data.as_ptr(), test_data.len())
+            rs_libp2p_cdr_buffer_write_chunked_string(
+                buf,
+                test_string.as_ptr(),
+                test_string.as_bytes().len(),
+            )
         };
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+        let mut out: *mut u8 = std::ptr::null_mut();
+        let mut out_len: usize = 0;
+        let status = unsafe { rs_libp2p_cdr_buffer_flatten_chunked(buf, &mut out, &mut out_len) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        let flattened = unsafe { slice::from_raw_parts(out, out_len) }.to_vec();
+
+        let mut scattered = Vec::new();
+        let count = unsafe { rs_libp2p_cdr_buffer_chunked_scatter_count(buf) };
+        for index in 0..count {
+            let mut data: *const u8 = std::ptr::null();
+            let mut len: usize = 0;
+            let status =
+                unsafe { rs_libp2p_cdr_buffer_chunked_scatter_view(buf, index, &mut data, &mut len) };
+            assert_eq!(status, CdrBufferStatus::Ok as i32);
+            scattered.extend_from_slice(unsafe { slice::from_raw_parts(data, len) });
+        }
+        assert_eq!(flattened, scattered);
 
-        let mut s_ptr: *const u16 = std::ptr::null();
-        let mut size: usize = 0;
+        unsafe { rs_libp2p_cdr_buffer_free_flattened_chunked(out, out_len) };
+        unsafe { rs_libp2p_cdr_buffer_free_chunked(buf) };
+    }
+
+    #[test]
+    fn test_chunked_write_rejects_null_pointer() {
+        let status = unsafe { rs_libp2p_cdr_buffer_write_chunked_uint32(std::ptr::null_mut(), 1) };
+        assert_eq!(status, CdrBufferStatus::NullPointer as i32);
+    }
+
+    // === Compression Tests ===
 
+    #[test]
+    fn test_compress_decompress_zlib_roundtrips() {
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        let test_string = CString::new("a".repeat(4096)).unwrap();
         unsafe {
-            rs_libp2p_cdr_buffer_read_u16string(
-                read_buf,
-                &mut s_ptr as *mut *const u16,
-                &mut size as *mut usize,
+            rs_libp2p_cdr_buffer_write_string(
+                write_buf,
+                test_string.as_ptr(),
+                test_string.as_bytes().len(),
             )
         };
+        let original = get_buffer_data(write_buf);
 
-        assert!(!s_ptr.is_null());
-        assert_eq!(size, 10000);
+        let status = unsafe { rs_libp2p_cdr_buffer_compress(write_buf, 1) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        let compressed = get_buffer_data(write_buf);
+        assert!(compressed.len() < original.len());
+        assert_eq!(compressed[0], CdrCompressionCodec::Zlib as u8);
 
-        let result_slice = unsafe { slice::from_raw_parts(s_ptr, size) };
-        assert_eq!(result_slice, test_data.as_slice());
+        let status = unsafe { rs_libp2p_cdr_buffer_decompress(write_buf) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(get_buffer_data(write_buf), original);
 
-        unsafe { rs_libp2p_cdr_buffer_free_u16string(s_ptr as *mut u16, size) };
         unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
     }
 
     #[test]
-    fn test_unicode_u16string_write_read_roundtrip() {
-        // Test Japanese hiragana/kanji through write/read functions
+    fn test_compress_falls_back_to_none_when_not_smaller() {
         let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_data: Vec<u16> = vec![0x3042, 0x3044, 0x3046, 0x4E00, 0x4E8C]; // „ÅÇ„ÅÑ„ÅÜ‰∏Ä‰∫å
+        unsafe { rs_libp2p_cdr_buffer_write_uint8(write_buf, 7) };
+        let original = get_buffer_data(write_buf);
 
-        unsafe {
-            rs_libp2p_cdr_buffer_write_u16string(write_buf, test_data.as_ptr(), test_data.len())
-        };
+        let status = unsafe { rs_libp2p_cdr_buffer_compress(write_buf, 1) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        let framed = get_buffer_data(write_buf);
+        assert_eq!(framed[0], CdrCompressionCodec::None as u8);
+        assert_eq!(&framed[9..], &original[..]);
 
-        let data = get_buffer_data(write_buf);
+        let status = unsafe { rs_libp2p_cdr_buffer_decompress(write_buf) };
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(get_buffer_data(write_buf), original);
+
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+    }
+
+    #[test]
+    fn test_compress_rejects_null_pointer() {
+        let status = unsafe { rs_libp2p_cdr_buffer_compress(std::ptr::null_mut(), 1) };
+        assert_eq!(status, CdrBufferStatus::NullPointer as i32);
+    }
+
+    // === LEB128 Varint Tests ===
+
+    #[test]
+    fn test_varint_u64_roundtrip_small_and_large_values() {
+        for &value in &[0u64, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            let status = unsafe { rs_libp2p_cdr_buffer_write_varint_u64(write_buf, value) };
+            assert_eq!(status, CdrBufferStatus::Ok as i32);
+
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+            let mut decoded: u64 = 0;
+            let status = unsafe { rs_libp2p_cdr_buffer_read_varint_u64(read_buf, &mut decoded) };
+            assert_eq!(status, CdrBufferStatus::Ok as i32);
+            assert_eq!(decoded, value);
+
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
+        // Small values should encode to a single byte.
+        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        unsafe { rs_libp2p_cdr_buffer_write_varint_u64(write_buf, 100) };
+        assert_eq!(get_buffer_data(write_buf).len(), 1);
+        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+    }
+
+    #[test]
+    fn test_varint_i64_roundtrip_positive_negative_and_zero() {
+        for &value in &[0i64, 1, -1, 63, -64, 64, -65, i32::MIN as i64, i64::MIN, i64::MAX] {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            let status = unsafe { rs_libp2p_cdr_buffer_write_varint_i64(write_buf, value) };
+            assert_eq!(status, CdrBufferStatus::Ok as i32);
+
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+            let mut decoded: i64 = 0;
+            let status = unsafe { rs_libp2p_cdr_buffer_read_varint_i64(read_buf, &mut decoded) };
+            assert_eq!(status, CdrBufferStatus::Ok as i32);
+            assert_eq!(decoded, value);
+
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
+    }
+
+    #[test]
+    fn test_varint_read_rejects_overrun_continuation_bit() {
+        // 11 bytes, every one with the continuation bit set, never terminates.
+        let data = [0x80u8; 11];
         let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+        let mut decoded: u64 = 0;
+        let status = unsafe { rs_libp2p_cdr_buffer_read_varint_u64(read_buf, &mut decoded) };
+        assert_eq!(status, CdrBufferStatus::Underrun as i32);
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
 
-        let mut s_ptr: *const u16 = std::ptr::null();
-        let mut size: usize = 0;
+    #[test]
+    fn test_varint_write_rejects_null_pointer() {
+        let status = unsafe { rs_libp2p_cdr_buffer_write_varint_u64(std::ptr::null_mut(), 1) };
+        assert_eq!(status, CdrBufferStatus::NullPointer as i32);
+    }
 
-        unsafe {
-            rs_libp2p_cdr_buffer_read_u16string(
-                read_buf,
-                &mut s_ptr as *mut *const u16,
-                &mut size as *mut usize,
-            )
+    // === Safe String Decoding Tests ===
+
+    #[test]
+    fn test_read_string_bytes_preserves_interior_nul() {
+        // CDR length-prefix + raw bytes with an interior NUL + the CDR string's own NUL
+        // terminator, built by hand since CString can't represent this.
+        let mut raw: Vec<u8> = Vec::new();
+        let content: &[u8] = b"a\0b";
+        let declared_len = (content.len() + 1) as u32; // + terminator
+        raw.extend_from_slice(&declared_len.to_be_bytes());
+        raw.extend_from_slice(content);
+        raw.push(0);
+
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(raw.as_ptr(), raw.len()) };
+
+        let mut s_ptr: *const u8 = std::ptr::null();
+        let mut size: usize = 0;
+        let mut is_valid_utf8 = false;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_string_bytes(read_buf, &mut s_ptr, &mut size, &mut is_valid_utf8)
         };
 
-        assert!(!s_ptr.is_null());
-        assert_eq!(size, 5);
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert_eq!(size, 3);
+        assert!(is_valid_utf8);
+        let result = unsafe { slice::from_raw_parts(s_ptr, size) };
+        assert_eq!(result, content);
+
+        unsafe { rs_libp2p_cdr_buffer_free_string_bytes(s_ptr as *mut u8, size) };
+        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+    }
+
+    #[test]
+    fn test_read_string_bytes_flags_invalid_utf8() {
+        let mut raw: Vec<u8> = Vec::new();
+        let content: &[u8] = &[0xFF, 0xFE];
+        let declared_len = (content.len() + 1) as u32;
+        raw.extend_from_slice(&declared_len.to_be_bytes());
+        raw.extend_from_slice(content);
+        raw.push(0);
 
-        let result_slice = unsafe { slice::from_raw_parts(s_ptr, size) };
-        assert_eq!(result_slice, test_data.as_slice());
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(raw.as_ptr(), raw.len()) };
 
-        unsafe { rs_libp2p_cdr_buffer_free_u16string(s_ptr as *mut u16, size) };
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+        let mut s_ptr: *const u8 = std::ptr::null();
+        let mut size: usize = 0;
+        let mut is_valid_utf8 = true;
+        let status = unsafe {
+            rs_libp2p_cdr_buffer_read_string_bytes(read_buf, &mut s_ptr, &mut size, &mut is_valid_utf8)
+        };
+
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
+        assert!(!is_valid_utf8);
+
+        unsafe { rs_libp2p_cdr_buffer_free_string_bytes(s_ptr as *mut u8, size) };
         unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
     }
 
     #[test]
-    fn test_u16string_roundtrip() {
-        // Manually serialize a u16 string using CDR
-        let test_string: Vec<u16> = vec![0x3042, 0x3044, 0x3046]; // Japanese hiragana
-        let mut buffer = Cursor::new(Vec::<u8>::new());
-        cdr::serialize_into::<_, _, _, cdr::CdrBe>(&mut buffer, &test_string, cdr::Infinite)
-            .unwrap();
+    fn test_read_string_lossy_replaces_invalid_utf8() {
+        let mut raw: Vec<u8> = Vec::new();
+        let content: &[u8] = &[0xFF, 0xFE];
+        let declared_len = (content.len() + 1) as u32;
+        raw.extend_from_slice(&declared_len.to_be_bytes());
+        raw.extend_from_slice(content);
+        raw.push(0);
 
-        let data = buffer.get_ref().clone();
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(raw.as_ptr(), raw.len()) };
 
-        let mut s_ptr: *const u16 = std::ptr::null();
+        let mut s_ptr: *const c_char = std::ptr::null();
         let mut size: usize = 0;
+        let status =
+            unsafe { rs_libp2p_cdr_buffer_read_string_lossy(read_buf, &mut s_ptr, &mut size) };
 
-        unsafe {
-            rs_libp2p_cdr_buffer_read_u16string(
-                read_buf,
-                &mut s_ptr as *mut *const u16,
-                &mut size as *mut usize,
-            )
-        };
-
+        assert_eq!(status, CdrBufferStatus::Ok as i32);
         assert!(!s_ptr.is_null());
-        assert_eq!(size, 3);
-
-        let result_slice = unsafe { std::slice::from_raw_parts(s_ptr, size) };
-        assert_eq!(result_slice, &test_string[..]);
+        let result_str = unsafe { CStr::from_ptr(s_ptr) }.to_str().unwrap();
+        assert!(result_str.contains('\u{FFFD}'));
 
-        unsafe { rs_libp2p_cdr_buffer_free_u16string(s_ptr as *mut u16, size) };
+        unsafe { rs_libp2p_cdr_buffer_free_string(s_ptr as *mut c_char) };
         unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
     }
 
-    // === Boundary Value Tests ===
+    #[test]
+    fn test_compare_float_total_orders_signed_zero_and_nan() {
+        assert_eq!(rs_libp2p_cdr_compare_float_total(-0.0, 0.0), -1);
+        assert_eq!(rs_libp2p_cdr_compare_float_total(0.0, -0.0), 1);
+        assert_eq!(rs_libp2p_cdr_compare_float_total(1.0, 1.0), 0);
+        assert_eq!(rs_libp2p_cdr_compare_float_total(-1.0, 1.0), -1);
+        assert_eq!(
+            rs_libp2p_cdr_compare_float_total(f32::NEG_INFINITY, f32::INFINITY),
+            -1
+        );
+        // Negative NaN orders before negative infinity, positive NaN after positive infinity.
+        assert_eq!(
+            rs_libp2p_cdr_compare_float_total(-f32::NAN, f32::NEG_INFINITY),
+            -1
+        );
+        assert_eq!(
+            rs_libp2p_cdr_compare_float_total(f32::NAN, f32::INFINITY),
+            1
+        );
+    }
 
     #[test]
-    fn test_uint64_boundary_values() {
-        let test_values = vec![0u64, u64::MAX, u64::MIN, u64::MAX / 2];
+    fn test_compare_double_total_orders_signed_zero_and_nan() {
+        assert_eq!(rs_libp2p_cdr_compare_double_total(-0.0, 0.0), -1);
+        assert_eq!(rs_libp2p_cdr_compare_double_total(0.0, -0.0), 1);
+        assert_eq!(rs_libp2p_cdr_compare_double_total(2.5, 2.5), 0);
+        assert_eq!(
+            rs_libp2p_cdr_compare_double_total(f64::NEG_INFINITY, f64::INFINITY),
+            -1
+        );
+        assert_eq!(
+            rs_libp2p_cdr_compare_double_total(-f64::NAN, f64::NEG_INFINITY),
+            -1
+        );
+        assert_eq!(
+            rs_libp2p_cdr_compare_double_total(f64::NAN, f64::INFINITY),
+            1
+        );
+    }
+}
 
-        for test_val in test_values {
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Helper to get buffer data
+    fn get_buffer_data(ptr: *mut Cursor<Vec<u8>>) -> Vec<u8> {
+        unsafe {
+            let cursor = &*ptr;
+            cursor.get_ref().clone()
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_uint64_roundtrip(value: u64) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_uint64(write_buf, test_val) };
+            unsafe { rs_libp2p_cdr_buffer_write_uint64(write_buf, value) };
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
@@ -1629,715 +8484,882 @@ mod tests {
             let mut result: u64 = 0;
             unsafe { rs_libp2p_cdr_buffer_read_uint64(read_buf, &mut result as *mut u64) };
 
-            assert_eq!(result, test_val, "Failed for value: {}", test_val);
+            prop_assert_eq!(result, value);
 
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
-    }
-
-    #[test]
-    fn test_int64_boundary_values() {
-        let test_values = vec![0i64, i64::MAX, i64::MIN, -1, 1];
 
-        for test_val in test_values {
+        #[test]
+        fn prop_uint32_roundtrip(value: u32) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_int64(write_buf, test_val) };
+            unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, value) };
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: i64 = 0;
-            unsafe { rs_libp2p_cdr_buffer_read_int64(read_buf, &mut result as *mut i64) };
+            let mut result: u32 = 0;
+            unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut result as *mut u32) };
 
-            assert_eq!(result, test_val, "Failed for value: {}", test_val);
+            prop_assert_eq!(result, value);
 
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
-    }
-
-    #[test]
-    fn test_float_special_values() {
-        let test_values = vec![
-            0.0f32,
-            -0.0f32,
-            f32::INFINITY,
-            f32::NEG_INFINITY,
-            f32::MIN,
-            f32::MAX,
-        ];
 
-        for test_val in test_values {
+        #[test]
+        fn prop_uint16_roundtrip(value: u16) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, test_val) };
+            unsafe { rs_libp2p_cdr_buffer_write_uint16(write_buf, value) };
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: f32 = 0.0;
-            unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut result as *mut f32) };
+            let mut result: u16 = 0;
+            unsafe { rs_libp2p_cdr_buffer_read_uint16(read_buf, &mut result as *mut u16) };
 
-            assert_eq!(result, test_val, "Failed for value: {}", test_val);
+            prop_assert_eq!(result, value);
 
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
-    }
 
-    #[test]
-    fn test_float_nan() {
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val = f32::NAN;
+        #[test]
+        fn prop_uint8_roundtrip(value: u8) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            unsafe { rs_libp2p_cdr_buffer_write_uint8(write_buf, value) };
 
-        unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, test_val) };
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+            let mut result: u8 = 0;
+            unsafe { rs_libp2p_cdr_buffer_read_uint8(read_buf, &mut result as *mut u8) };
 
-        let mut result: f32 = 0.0;
-        unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut result as *mut f32) };
+            prop_assert_eq!(result, value);
 
-        assert!(result.is_nan(), "Expected NaN");
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
-    }
+        #[test]
+        fn prop_int64_roundtrip(value: i64) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            unsafe { rs_libp2p_cdr_buffer_write_int64(write_buf, value) };
 
-    #[test]
-    fn test_double_special_values() {
-        let test_values = vec![
-            0.0f64,
-            -0.0f64,
-            f64::INFINITY,
-            f64::NEG_INFINITY,
-            f64::MIN,
-            f64::MAX,
-        ];
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        for test_val in test_values {
+            let mut result: i64 = 0;
+            unsafe { rs_libp2p_cdr_buffer_read_int64(read_buf, &mut result as *mut i64) };
+
+            prop_assert_eq!(result, value);
+
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
+
+        #[test]
+        fn prop_int32_roundtrip(value: i32) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, test_val) };
+            unsafe { rs_libp2p_cdr_buffer_write_int32(write_buf, value) };
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: f64 = 0.0;
-            unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut result as *mut f64) };
+            let mut result: i32 = 0;
+            unsafe { rs_libp2p_cdr_buffer_read_int32(read_buf, &mut result as *mut i32) };
 
-            assert_eq!(result, test_val, "Failed for value: {}", test_val);
+            prop_assert_eq!(result, value);
 
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
-    }
 
-    #[test]
-    fn test_double_nan() {
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        let test_val = f64::NAN;
+        #[test]
+        fn prop_int16_roundtrip(value: i16) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            unsafe { rs_libp2p_cdr_buffer_write_int16(write_buf, value) };
 
-        unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, test_val) };
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+            let mut result: i16 = 0;
+            unsafe { rs_libp2p_cdr_buffer_read_int16(read_buf, &mut result as *mut i16) };
 
-        let mut result: f64 = 0.0;
-        unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut result as *mut f64) };
+            prop_assert_eq!(result, value);
 
-        assert!(result.is_nan(), "Expected NaN");
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
-    }
+        #[test]
+        fn prop_int8_roundtrip(value: i8) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            unsafe { rs_libp2p_cdr_buffer_write_int8(write_buf, value) };
 
-    // === Multiple Values Test ===
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-    #[test]
-    fn test_multiple_values_sequence() {
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            let mut result: i8 = 0;
+            unsafe { rs_libp2p_cdr_buffer_read_int8(read_buf, &mut result as *mut i8) };
 
-        // Write multiple values
-        unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, 42) };
-        unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, std::f32::consts::PI) };
-        unsafe { rs_libp2p_cdr_buffer_write_bool(write_buf, true) };
-        unsafe { rs_libp2p_cdr_buffer_write_int16(write_buf, -100) };
+            prop_assert_eq!(result, value);
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
 
-        // Read them back in order
-        let mut val1: u32 = 0;
-        let mut val2: f32 = 0.0;
-        let mut val3: bool = false;
-        let mut val4: i16 = 0;
+        #[test]
+        fn prop_float_roundtrip(value in prop::num::f32::NORMAL) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, value) };
 
-        unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut val1 as *mut u32) };
-        unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut val2 as *mut f32) };
-        unsafe { rs_libp2p_cdr_buffer_read_bool(read_buf, &mut val3 as *mut bool) };
-        unsafe { rs_libp2p_cdr_buffer_read_int16(read_buf, &mut val4 as *mut i16) };
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        assert_eq!(val1, 42);
-        assert_eq!(val2, std::f32::consts::PI);
-        assert!(val3);
-        assert_eq!(val4, -100);
+            let mut result: f32 = 0.0;
+            unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut result as *mut f32) };
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
-    }
+            prop_assert_eq!(result, value);
 
-    // === Large Data Stress Test ===
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
 
-    #[test]
-    fn test_large_data_sequence() {
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        #[test]
+        fn prop_double_roundtrip(value in prop::num::f64::NORMAL) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, value) };
+
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+            let mut result: f64 = 0.0;
+            unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut result as *mut f64) };
 
-        // Write 10000 values to stress buffer growth
-        for i in 0..10000u32 {
-            unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, i) };
+            prop_assert_eq!(result, value);
+
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+        #[test]
+        fn prop_bool_roundtrip(value: bool) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            unsafe { rs_libp2p_cdr_buffer_write_bool(write_buf, value) };
 
-        // Read them back and verify
-        for i in 0..10000u32 {
-            let mut val: u32 = 0;
-            unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut val as *mut u32) };
-            assert_eq!(val, i, "Mismatch at index {}", i);
-        }
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
-    }
+            let mut result = false;
+            unsafe { rs_libp2p_cdr_buffer_read_bool(read_buf, &mut result as *mut bool) };
 
-    #[test]
-    fn test_long_string() {
-        // Test with a 10KB string
-        let long_str = "A".repeat(10000);
-        let test_string = CString::new(long_str.clone()).unwrap();
-        let mut buffer = Cursor::new(Vec::<u8>::new());
-        cdr::serialize_into::<_, _, _, cdr::CdrBe>(&mut buffer, &test_string, cdr::Infinite)
-            .unwrap();
+            prop_assert_eq!(result, value);
 
-        let data = buffer.get_ref().clone();
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
 
-        let mut s_ptr: *const c_char = std::ptr::null();
-        let mut size: usize = 0;
+        #[test]
+        fn prop_char_roundtrip(value: i8) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            unsafe { rs_libp2p_cdr_buffer_write_char(write_buf, value as c_char) };
 
-        unsafe {
-            rs_libp2p_cdr_buffer_read_string(
-                read_buf,
-                &mut s_ptr as *mut *const c_char,
-                &mut size as *mut usize,
-            )
-        };
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        assert!(!s_ptr.is_null());
-        assert_eq!(size, 10000);
+            let mut result: c_char = 0;
+            unsafe { rs_libp2p_cdr_buffer_read_char(read_buf, &mut result as *mut c_char) };
 
-        let result_str = unsafe { CStr::from_ptr(s_ptr) };
-        assert_eq!(result_str.to_str().unwrap(), long_str);
+            prop_assert_eq!(result, value as c_char);
 
-        unsafe { rs_libp2p_cdr_buffer_free_string(s_ptr as *mut c_char) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
-    }
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
 
-    // === Null Pointer String Write Tests ===
+        #[test]
+        fn prop_char16_roundtrip(value: u16) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            unsafe { rs_libp2p_cdr_buffer_write_char16(write_buf, value) };
 
-    #[test]
-    fn test_write_null_string() {
-        // Test writing null string pointer with zero size
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        unsafe { rs_libp2p_cdr_buffer_write_string(write_buf, std::ptr::null(), 0) };
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+            let mut result: u16 = 0;
+            unsafe { rs_libp2p_cdr_buffer_read_char16(read_buf, &mut result as *mut u16) };
 
-        let mut s_ptr: *const c_char = std::ptr::null();
-        let mut size: usize = 0;
+            prop_assert_eq!(result, value);
 
-        unsafe {
-            rs_libp2p_cdr_buffer_read_string(
-                read_buf,
-                &mut s_ptr as *mut *const c_char,
-                &mut size as *mut usize,
-            )
-        };
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
 
-        assert_eq!(size, 0);
+        #[test]
+        fn prop_u16string_roundtrip(value in prop::collection::vec(any::<u16>(), 0..100)) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
-    }
+            if !value.is_empty() {
+                unsafe { rs_libp2p_cdr_buffer_write_u16string(write_buf, value.as_ptr(), value.len()) };
+            } else {
+                unsafe { rs_libp2p_cdr_buffer_write_u16string(write_buf, std::ptr::null(), 0) };
+            }
 
-    #[test]
-    fn test_write_null_u16string() {
-        // Test writing null u16 string pointer with zero size
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        unsafe { rs_libp2p_cdr_buffer_write_u16string(write_buf, std::ptr::null(), 0) };
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+            let mut s_ptr: *const u16 = std::ptr::null();
+            let mut size: usize = 0;
 
-        let mut s_ptr: *const u16 = std::ptr::null();
-        let mut size: usize = 0;
+            unsafe {
+                rs_libp2p_cdr_buffer_read_u16string(
+                    read_buf,
+                    &mut s_ptr as *mut *const u16,
+                    &mut size as *mut usize,
+                )
+            };
 
-        unsafe {
-            rs_libp2p_cdr_buffer_read_u16string(
-                read_buf,
-                &mut s_ptr as *mut *const u16,
-                &mut size as *mut usize,
-            )
-        };
+            prop_assert_eq!(size, value.len());
 
-        assert_eq!(size, 0);
+            if !value.is_empty() {
+                prop_assert!(!s_ptr.is_null());
+                let result_slice = unsafe { slice::from_raw_parts(s_ptr, size) };
+                prop_assert_eq!(result_slice, value.as_slice());
+                unsafe { rs_libp2p_cdr_buffer_free_u16string(s_ptr as *mut u16, size) };
+            }
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
-    }
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
 
-    // === Mixed Type Sequence Tests ===
+        // === Corrupted-buffer / hostile-peer error-handling properties ===
+        //
+        // These feed deliberately truncated or bogus-length payloads and assert a clean
+        // `CdrBufferStatus`/`CdrStatus` error is returned rather than a panic or out-of-bounds
+        // read, covering the scalar readers, the `*_bounded` allocation-capped readers, and the
+        // `*_array_checked` pre-validated readers.
 
-    #[test]
-    fn test_complex_mixed_sequence() {
-        // Test complex real-world scenario with mixed types
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        #[test]
+        fn prop_read_uint32_on_truncated_buffer_never_panics(len in 0usize..4) {
+            let data = vec![0u8; len];
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        // Simulate a ROS 2 message with header and data
-        unsafe { rs_libp2p_cdr_buffer_write_uint64(write_buf, 1234567890) }; // timestamp
-        unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, 42) }; // sequence number
-        let frame_id = CString::new("base_link").unwrap();
-        unsafe {
-            rs_libp2p_cdr_buffer_write_string(
-                write_buf,
-                frame_id.as_ptr(),
-                frame_id.to_bytes().len(),
-            )
-        };
-        unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, 1.23456789) }; // position x
-        unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, 9.87654321) }; // position y
-        unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, 0.0) }; // position z
-        unsafe { rs_libp2p_cdr_buffer_write_bool(write_buf, true) }; // active flag
+            let mut result: u32 = 0;
+            let status = unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut result as *mut u32) };
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+            prop_assert_eq!(status, CdrBufferStatus::Underrun as i32);
 
-        // Read back in order
-        let mut timestamp: u64 = 0;
-        let mut seq: u32 = 0;
-        let mut frame_ptr: *const c_char = std::ptr::null();
-        let mut frame_len: usize = 0;
-        let mut pos_x: f64 = 0.0;
-        let mut pos_y: f64 = 0.0;
-        let mut pos_z: f64 = 0.0;
-        let mut active: bool = false;
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
 
-        unsafe { rs_libp2p_cdr_buffer_read_uint64(read_buf, &mut timestamp as *mut u64) };
-        unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut seq as *mut u32) };
-        unsafe {
-            rs_libp2p_cdr_buffer_read_string(
-                read_buf,
-                &mut frame_ptr as *mut *const c_char,
-                &mut frame_len as *mut usize,
-            )
-        };
-        unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut pos_x as *mut f64) };
-        unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut pos_y as *mut f64) };
-        unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut pos_z as *mut f64) };
-        unsafe { rs_libp2p_cdr_buffer_read_bool(read_buf, &mut active as *mut bool) };
+        #[test]
+        fn prop_read_string_bounded_rejects_declared_length_over_cap(
+            declared_len in 1u32..=u32::MAX,
+            max_alloc in 0usize..64,
+        ) {
+            // A string prefix claiming more bytes than either the configured cap or the
+            // actually-remaining buffer allows must be rejected before allocating.
+            let mut data = declared_len.to_be_bytes().to_vec();
+            data.extend_from_slice(&[0u8; 8]);
+            let read_buf =
+                unsafe { rs_libp2p_cdr_buffer_read_new_bounded(data.as_ptr(), data.len(), max_alloc) };
+
+            let mut s_ptr: *const c_char = std::ptr::null();
+            let mut size: usize = 0;
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_string_bounded(
+                    read_buf,
+                    &mut s_ptr as *mut *const c_char,
+                    &mut size as *mut usize,
+                )
+            };
 
-        assert_eq!(timestamp, 1234567890);
-        assert_eq!(seq, 42);
-        assert!(!frame_ptr.is_null());
-        let frame_str = unsafe { CStr::from_ptr(frame_ptr) };
-        assert_eq!(frame_str.to_str().unwrap(), "base_link");
-        assert_eq!(pos_x, 1.23456789);
-        assert_eq!(pos_y, 9.87654321);
-        assert_eq!(pos_z, 0.0);
-        assert!(active);
+            if (declared_len as usize) > max_alloc.min(data.len() - 4) {
+                prop_assert_eq!(status, CdrBufferStatus::Underrun as i32);
+                prop_assert!(s_ptr.is_null());
+            }
 
-        unsafe { rs_libp2p_cdr_buffer_free_string(frame_ptr as *mut c_char) };
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
-    }
+            unsafe { rs_libp2p_cdr_buffer_free_bounded(read_buf) };
+        }
 
-    // === Zero/Boundary Value Edge Cases ===
+        #[test]
+        fn prop_read_uint32_array_checked_rejects_implausible_length(declared_len in 0x1000_0000u32..=u32::MAX) {
+            // However small the trailing payload, a length this large can never be satisfied by
+            // the handful of real bytes that follow it, so validation must reject it up front.
+            let mut data = declared_len.to_be_bytes().to_vec();
+            data.extend_from_slice(&[0u8; 16]);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-    #[test]
-    fn test_zero_values() {
-        // Test that zero values serialize correctly
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            let mut out: *mut u32 = std::ptr::null_mut();
+            let mut out_count: usize = 0;
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_uint32_array_checked(
+                    read_buf,
+                    &mut out,
+                    &mut out_count as *mut usize,
+                )
+            };
 
-        unsafe { rs_libp2p_cdr_buffer_write_uint64(write_buf, 0) };
-        unsafe { rs_libp2p_cdr_buffer_write_int64(write_buf, 0) };
-        unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, 0.0) };
-        unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, 0.0) };
+            prop_assert_eq!(status, CdrStatus::InvalidLength as i32);
+            prop_assert!(out.is_null());
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
 
-        let mut u: u64 = 999;
-        let mut i: i64 = 999;
-        let mut f: f32 = 999.0;
-        let mut d: f64 = 999.0;
+        // === Bulk sequence and fixed-array roundtrip properties ===
+        //
+        // The scalar paths above each get a dedicated roundtrip property; these extend the same
+        // coverage to the bulk `*_array` (u32-length-prefixed sequence) and `*_fixed_array`
+        // (no-prefix, caller-supplied count) paths added for every arithmetic element type.
 
-        unsafe { rs_libp2p_cdr_buffer_read_uint64(read_buf, &mut u as *mut u64) };
-        unsafe { rs_libp2p_cdr_buffer_read_int64(read_buf, &mut i as *mut i64) };
-        unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut f as *mut f32) };
-        unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut d as *mut f64) };
+        #[test]
+        fn prop_int8_array_roundtrip(values in proptest::collection::vec(any::<i8>(), 0..16)) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_int8_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-        assert_eq!(u, 0);
-        assert_eq!(i, 0);
-        assert_eq!(f, 0.0);
-        assert_eq!(d, 0.0);
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+            let mut out: *mut i8 = std::ptr::null_mut();
+            let mut out_count: usize = 0;
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_int8_array(read_buf, &mut out, &mut out_count as *mut usize)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
-    }
+            let result = unsafe { slice::from_raw_parts(out, out_count) };
+            prop_assert_eq!(result, values.as_slice());
 
-    #[test]
-    fn test_negative_zero_float() {
-        // Test that -0.0 is preserved correctly
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-        unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, -0.0f32) };
+            unsafe { rs_libp2p_cdr_buffer_free_int8_array(out, out_count) };
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+        #[test]
+        fn prop_int8_fixed_array_roundtrip(values in proptest::collection::vec(any::<i8>(), 0..16)) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_int8_fixed_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-        let mut result: f32 = 0.0;
-        unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut result as *mut f32) };
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        // -0.0 should equal 0.0 but have different bit pattern
-        assert_eq!(result, -0.0f32);
-        assert_eq!(result.to_bits(), (-0.0f32).to_bits());
+            let mut out: *mut i8 = std::ptr::null_mut();
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_int8_fixed_array(read_buf, values.len(), &mut out)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
-    }
+            if values.is_empty() {
+                prop_assert!(out.is_null());
+            } else {
+                let result = unsafe { slice::from_raw_parts(out, values.len()) };
+                prop_assert_eq!(result, values.as_slice());
+                unsafe { rs_libp2p_cdr_buffer_free_int8_array(out, values.len()) };
+            }
 
-    // === All Integer Types Comprehensive Test ===
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
 
-    #[test]
-    fn test_all_integer_types_together() {
-        // Test all integer types in a single buffer
-        let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+        #[test]
+        fn prop_int16_array_roundtrip(values in proptest::collection::vec(any::<i16>(), 0..16)) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_int16_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-        unsafe { rs_libp2p_cdr_buffer_write_uint64(write_buf, u64::MAX) };
-        unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, u32::MAX) };
-        unsafe { rs_libp2p_cdr_buffer_write_uint16(write_buf, u16::MAX) };
-        unsafe { rs_libp2p_cdr_buffer_write_uint8(write_buf, u8::MAX) };
-        unsafe { rs_libp2p_cdr_buffer_write_int64(write_buf, i64::MIN) };
-        unsafe { rs_libp2p_cdr_buffer_write_int32(write_buf, i32::MIN) };
-        unsafe { rs_libp2p_cdr_buffer_write_int16(write_buf, i16::MIN) };
-        unsafe { rs_libp2p_cdr_buffer_write_int8(write_buf, i8::MIN) };
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-        let data = get_buffer_data(write_buf);
-        let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+            let mut out: *mut i16 = std::ptr::null_mut();
+            let mut out_count: usize = 0;
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_int16_array(read_buf, &mut out, &mut out_count as *mut usize)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-        let mut u64_val: u64 = 0;
-        let mut u32_val: u32 = 0;
-        let mut u16_val: u16 = 0;
-        let mut u8_val: u8 = 0;
-        let mut i64_val: i64 = 0;
-        let mut i32_val: i32 = 0;
-        let mut i16_val: i16 = 0;
-        let mut i8_val: i8 = 0;
+            let result = unsafe { slice::from_raw_parts(out, out_count) };
+            prop_assert_eq!(result, values.as_slice());
 
-        unsafe { rs_libp2p_cdr_buffer_read_uint64(read_buf, &mut u64_val as *mut u64) };
-        unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut u32_val as *mut u32) };
-        unsafe { rs_libp2p_cdr_buffer_read_uint16(read_buf, &mut u16_val as *mut u16) };
-        unsafe { rs_libp2p_cdr_buffer_read_uint8(read_buf, &mut u8_val as *mut u8) };
-        unsafe { rs_libp2p_cdr_buffer_read_int64(read_buf, &mut i64_val as *mut i64) };
-        unsafe { rs_libp2p_cdr_buffer_read_int32(read_buf, &mut i32_val as *mut i32) };
-        unsafe { rs_libp2p_cdr_buffer_read_int16(read_buf, &mut i16_val as *mut i16) };
-        unsafe { rs_libp2p_cdr_buffer_read_int8(read_buf, &mut i8_val as *mut i8) };
+            unsafe { rs_libp2p_cdr_buffer_free_int16_array(out, out_count) };
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
 
-        assert_eq!(u64_val, u64::MAX);
-        assert_eq!(u32_val, u32::MAX);
-        assert_eq!(u16_val, u16::MAX);
-        assert_eq!(u8_val, u8::MAX);
-        assert_eq!(i64_val, i64::MIN);
-        assert_eq!(i32_val, i32::MIN);
-        assert_eq!(i16_val, i16::MIN);
-        assert_eq!(i8_val, i8::MIN);
+        #[test]
+        fn prop_int16_fixed_array_roundtrip(values in proptest::collection::vec(any::<i16>(), 0..16)) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_int16_fixed_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-        unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
-        unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
-    }
-}
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-#[cfg(test)]
-mod proptests {
-    use super::*;
-    use proptest::prelude::*;
+            let mut out: *mut i16 = std::ptr::null_mut();
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_int16_fixed_array(read_buf, values.len(), &mut out)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-    // Helper to get buffer data
-    fn get_buffer_data(ptr: *mut Cursor<Vec<u8>>) -> Vec<u8> {
-        unsafe {
-            let cursor = &*ptr;
-            cursor.get_ref().clone()
+            if values.is_empty() {
+                prop_assert!(out.is_null());
+            } else {
+                let result = unsafe { slice::from_raw_parts(out, values.len()) };
+                prop_assert_eq!(result, values.as_slice());
+                unsafe { rs_libp2p_cdr_buffer_free_int16_array(out, values.len()) };
+            }
+
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
-    }
 
-    proptest! {
         #[test]
-        fn prop_uint64_roundtrip(value: u64) {
+        fn prop_int32_array_roundtrip(values in proptest::collection::vec(any::<i32>(), 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_uint64(write_buf, value) };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_int32_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: u64 = 0;
-            unsafe { rs_libp2p_cdr_buffer_read_uint64(read_buf, &mut result as *mut u64) };
+            let mut out: *mut i32 = std::ptr::null_mut();
+            let mut out_count: usize = 0;
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_int32_array(read_buf, &mut out, &mut out_count as *mut usize)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(result, value);
+            let result = unsafe { slice::from_raw_parts(out, out_count) };
+            prop_assert_eq!(result, values.as_slice());
 
+            unsafe { rs_libp2p_cdr_buffer_free_int32_array(out, out_count) };
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
         #[test]
-        fn prop_uint32_roundtrip(value: u32) {
+        fn prop_int32_fixed_array_roundtrip(values in proptest::collection::vec(any::<i32>(), 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_uint32(write_buf, value) };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_int32_fixed_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: u32 = 0;
-            unsafe { rs_libp2p_cdr_buffer_read_uint32(read_buf, &mut result as *mut u32) };
+            let mut out: *mut i32 = std::ptr::null_mut();
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_int32_fixed_array(read_buf, values.len(), &mut out)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(result, value);
+            if values.is_empty() {
+                prop_assert!(out.is_null());
+            } else {
+                let result = unsafe { slice::from_raw_parts(out, values.len()) };
+                prop_assert_eq!(result, values.as_slice());
+                unsafe { rs_libp2p_cdr_buffer_free_int32_array(out, values.len()) };
+            }
 
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
         #[test]
-        fn prop_uint16_roundtrip(value: u16) {
+        fn prop_int64_array_roundtrip(values in proptest::collection::vec(any::<i64>(), 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_uint16(write_buf, value) };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_int64_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: u16 = 0;
-            unsafe { rs_libp2p_cdr_buffer_read_uint16(read_buf, &mut result as *mut u16) };
+            let mut out: *mut i64 = std::ptr::null_mut();
+            let mut out_count: usize = 0;
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_int64_array(read_buf, &mut out, &mut out_count as *mut usize)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(result, value);
+            let result = unsafe { slice::from_raw_parts(out, out_count) };
+            prop_assert_eq!(result, values.as_slice());
 
+            unsafe { rs_libp2p_cdr_buffer_free_int64_array(out, out_count) };
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
         #[test]
-        fn prop_uint8_roundtrip(value: u8) {
+        fn prop_int64_fixed_array_roundtrip(values in proptest::collection::vec(any::<i64>(), 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_uint8(write_buf, value) };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_int64_fixed_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: u8 = 0;
-            unsafe { rs_libp2p_cdr_buffer_read_uint8(read_buf, &mut result as *mut u8) };
+            let mut out: *mut i64 = std::ptr::null_mut();
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_int64_fixed_array(read_buf, values.len(), &mut out)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(result, value);
+            if values.is_empty() {
+                prop_assert!(out.is_null());
+            } else {
+                let result = unsafe { slice::from_raw_parts(out, values.len()) };
+                prop_assert_eq!(result, values.as_slice());
+                unsafe { rs_libp2p_cdr_buffer_free_int64_array(out, values.len()) };
+            }
 
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
         #[test]
-        fn prop_int64_roundtrip(value: i64) {
+        fn prop_uint8_array_roundtrip(values in proptest::collection::vec(any::<u8>(), 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_int64(write_buf, value) };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_uint8_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: i64 = 0;
-            unsafe { rs_libp2p_cdr_buffer_read_int64(read_buf, &mut result as *mut i64) };
+            let mut out: *mut u8 = std::ptr::null_mut();
+            let mut out_count: usize = 0;
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_uint8_array(read_buf, &mut out, &mut out_count as *mut usize)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(result, value);
+            let result = unsafe { slice::from_raw_parts(out, out_count) };
+            prop_assert_eq!(result, values.as_slice());
 
+            unsafe { rs_libp2p_cdr_buffer_free_uint8_array(out, out_count) };
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
         #[test]
-        fn prop_int32_roundtrip(value: i32) {
+        fn prop_uint8_fixed_array_roundtrip(values in proptest::collection::vec(any::<u8>(), 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_int32(write_buf, value) };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_uint8_fixed_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: i32 = 0;
-            unsafe { rs_libp2p_cdr_buffer_read_int32(read_buf, &mut result as *mut i32) };
+            let mut out: *mut u8 = std::ptr::null_mut();
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_uint8_fixed_array(read_buf, values.len(), &mut out)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(result, value);
+            if values.is_empty() {
+                prop_assert!(out.is_null());
+            } else {
+                let result = unsafe { slice::from_raw_parts(out, values.len()) };
+                prop_assert_eq!(result, values.as_slice());
+                unsafe { rs_libp2p_cdr_buffer_free_uint8_array(out, values.len()) };
+            }
 
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
         #[test]
-        fn prop_int16_roundtrip(value: i16) {
+        fn prop_uint16_array_roundtrip(values in proptest::collection::vec(any::<u16>(), 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_int16(write_buf, value) };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_uint16_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: i16 = 0;
-            unsafe { rs_libp2p_cdr_buffer_read_int16(read_buf, &mut result as *mut i16) };
+            let mut out: *mut u16 = std::ptr::null_mut();
+            let mut out_count: usize = 0;
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_uint16_array(read_buf, &mut out, &mut out_count as *mut usize)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(result, value);
+            let result = unsafe { slice::from_raw_parts(out, out_count) };
+            prop_assert_eq!(result, values.as_slice());
 
+            unsafe { rs_libp2p_cdr_buffer_free_uint16_array(out, out_count) };
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
         #[test]
-        fn prop_int8_roundtrip(value: i8) {
+        fn prop_uint16_fixed_array_roundtrip(values in proptest::collection::vec(any::<u16>(), 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_int8(write_buf, value) };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_uint16_fixed_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: i8 = 0;
-            unsafe { rs_libp2p_cdr_buffer_read_int8(read_buf, &mut result as *mut i8) };
+            let mut out: *mut u16 = std::ptr::null_mut();
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_uint16_fixed_array(read_buf, values.len(), &mut out)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(result, value);
+            if values.is_empty() {
+                prop_assert!(out.is_null());
+            } else {
+                let result = unsafe { slice::from_raw_parts(out, values.len()) };
+                prop_assert_eq!(result, values.as_slice());
+                unsafe { rs_libp2p_cdr_buffer_free_uint16_array(out, values.len()) };
+            }
 
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
         #[test]
-        fn prop_float_roundtrip(value in prop::num::f32::NORMAL) {
+        fn prop_uint32_array_roundtrip(values in proptest::collection::vec(any::<u32>(), 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_float(write_buf, value) };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_uint32_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: f32 = 0.0;
-            unsafe { rs_libp2p_cdr_buffer_read_float(read_buf, &mut result as *mut f32) };
+            let mut out: *mut u32 = std::ptr::null_mut();
+            let mut out_count: usize = 0;
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_uint32_array(read_buf, &mut out, &mut out_count as *mut usize)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(result, value);
+            let result = unsafe { slice::from_raw_parts(out, out_count) };
+            prop_assert_eq!(result, values.as_slice());
 
+            unsafe { rs_libp2p_cdr_buffer_free_uint32_array(out, out_count) };
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
         #[test]
-        fn prop_double_roundtrip(value in prop::num::f64::NORMAL) {
+        fn prop_uint32_fixed_array_roundtrip(values in proptest::collection::vec(any::<u32>(), 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_double(write_buf, value) };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_uint32_fixed_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: f64 = 0.0;
-            unsafe { rs_libp2p_cdr_buffer_read_double(read_buf, &mut result as *mut f64) };
+            let mut out: *mut u32 = std::ptr::null_mut();
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_uint32_fixed_array(read_buf, values.len(), &mut out)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(result, value);
+            if values.is_empty() {
+                prop_assert!(out.is_null());
+            } else {
+                let result = unsafe { slice::from_raw_parts(out, values.len()) };
+                prop_assert_eq!(result, values.as_slice());
+                unsafe { rs_libp2p_cdr_buffer_free_uint32_array(out, values.len()) };
+            }
 
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
         #[test]
-        fn prop_bool_roundtrip(value: bool) {
+        fn prop_uint64_array_roundtrip(values in proptest::collection::vec(any::<u64>(), 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_bool(write_buf, value) };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_uint64_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result = false;
-            unsafe { rs_libp2p_cdr_buffer_read_bool(read_buf, &mut result as *mut bool) };
+            let mut out: *mut u64 = std::ptr::null_mut();
+            let mut out_count: usize = 0;
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_uint64_array(read_buf, &mut out, &mut out_count as *mut usize)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(result, value);
+            let result = unsafe { slice::from_raw_parts(out, out_count) };
+            prop_assert_eq!(result, values.as_slice());
 
+            unsafe { rs_libp2p_cdr_buffer_free_uint64_array(out, out_count) };
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
         #[test]
-        fn prop_char_roundtrip(value: i8) {
+        fn prop_uint64_fixed_array_roundtrip(values in proptest::collection::vec(any::<u64>(), 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_char(write_buf, value as c_char) };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_uint64_fixed_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: c_char = 0;
-            unsafe { rs_libp2p_cdr_buffer_read_char(read_buf, &mut result as *mut c_char) };
+            let mut out: *mut u64 = std::ptr::null_mut();
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_uint64_fixed_array(read_buf, values.len(), &mut out)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(result, value as c_char);
+            if values.is_empty() {
+                prop_assert!(out.is_null());
+            } else {
+                let result = unsafe { slice::from_raw_parts(out, values.len()) };
+                prop_assert_eq!(result, values.as_slice());
+                unsafe { rs_libp2p_cdr_buffer_free_uint64_array(out, values.len()) };
+            }
 
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
         #[test]
-        fn prop_char16_roundtrip(value: u16) {
+        fn prop_float_array_roundtrip(values in proptest::collection::vec(prop::num::f32::NORMAL, 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
-            unsafe { rs_libp2p_cdr_buffer_write_char16(write_buf, value) };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_float_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut result: u16 = 0;
-            unsafe { rs_libp2p_cdr_buffer_read_char16(read_buf, &mut result as *mut u16) };
+            let mut out: *mut f32 = std::ptr::null_mut();
+            let mut out_count: usize = 0;
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_float_array(read_buf, &mut out, &mut out_count as *mut usize)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(result, value);
+            let result = unsafe { slice::from_raw_parts(out, out_count) };
+            prop_assert_eq!(result, values.as_slice());
 
+            unsafe { rs_libp2p_cdr_buffer_free_float_array(out, out_count) };
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
 
         #[test]
-        fn prop_u16string_roundtrip(value in prop::collection::vec(any::<u16>(), 0..100)) {
+        fn prop_float_fixed_array_roundtrip(values in proptest::collection::vec(prop::num::f32::NORMAL, 0..16)) {
             let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_float_fixed_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            if !value.is_empty() {
-                unsafe { rs_libp2p_cdr_buffer_write_u16string(write_buf, value.as_ptr(), value.len()) };
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
+
+            let mut out: *mut f32 = std::ptr::null_mut();
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_float_fixed_array(read_buf, values.len(), &mut out)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
+
+            if values.is_empty() {
+                prop_assert!(out.is_null());
             } else {
-                unsafe { rs_libp2p_cdr_buffer_write_u16string(write_buf, std::ptr::null(), 0) };
+                let result = unsafe { slice::from_raw_parts(out, values.len()) };
+                prop_assert_eq!(result, values.as_slice());
+                unsafe { rs_libp2p_cdr_buffer_free_float_array(out, values.len()) };
             }
 
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
+
+        #[test]
+        fn prop_double_array_roundtrip(values in proptest::collection::vec(prop::num::f64::NORMAL, 0..16)) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_double_array(write_buf, values.as_ptr(), values.len())
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
+
             let data = get_buffer_data(write_buf);
             let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            let mut s_ptr: *const u16 = std::ptr::null();
-            let mut size: usize = 0;
+            let mut out: *mut f64 = std::ptr::null_mut();
+            let mut out_count: usize = 0;
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_double_array(read_buf, &mut out, &mut out_count as *mut usize)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            unsafe {
-                rs_libp2p_cdr_buffer_read_u16string(
-                    read_buf,
-                    &mut s_ptr as *mut *const u16,
-                    &mut size as *mut usize,
-                )
+            let result = unsafe { slice::from_raw_parts(out, out_count) };
+            prop_assert_eq!(result, values.as_slice());
+
+            unsafe { rs_libp2p_cdr_buffer_free_double_array(out, out_count) };
+            unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
+            unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
+        }
+
+        #[test]
+        fn prop_double_fixed_array_roundtrip(values in proptest::collection::vec(prop::num::f64::NORMAL, 0..16)) {
+            let write_buf = unsafe { rs_libp2p_cdr_buffer_write_new() };
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_write_double_fixed_array(write_buf, values.as_ptr(), values.len())
             };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
 
-            prop_assert_eq!(size, value.len());
+            let data = get_buffer_data(write_buf);
+            let read_buf = unsafe { rs_libp2p_cdr_buffer_read_new(data.as_ptr(), data.len()) };
 
-            if !value.is_empty() {
-                prop_assert!(!s_ptr.is_null());
-                let result_slice = unsafe { slice::from_raw_parts(s_ptr, size) };
-                prop_assert_eq!(result_slice, value.as_slice());
-                unsafe { rs_libp2p_cdr_buffer_free_u16string(s_ptr as *mut u16, size) };
+            let mut out: *mut f64 = std::ptr::null_mut();
+            let status = unsafe {
+                rs_libp2p_cdr_buffer_read_double_fixed_array(read_buf, values.len(), &mut out)
+            };
+            prop_assert_eq!(status, CdrBufferStatus::Ok as i32);
+
+            if values.is_empty() {
+                prop_assert!(out.is_null());
+            } else {
+                let result = unsafe { slice::from_raw_parts(out, values.len()) };
+                prop_assert_eq!(result, values.as_slice());
+                unsafe { rs_libp2p_cdr_buffer_free_double_array(out, values.len()) };
             }
 
             unsafe { rs_libp2p_cdr_buffer_free(write_buf) };
             unsafe { rs_libp2p_cdr_buffer_free(read_buf) };
         }
+
     }
 }
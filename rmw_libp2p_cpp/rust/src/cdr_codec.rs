@@ -0,0 +1,119 @@
+// Copyright 2024 Esteve Fernandez
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! CDR encapsulation-header codec shared by the publisher and subscriber data path.
+//!
+//! ROS 2/DDS peers prefix every CDR payload with a 4-byte encapsulation header (representation
+//! id + options) so a receiver can tell which endianness the body was serialized with. This
+//! module writes and parses that header so messages produced by this node's publisher are
+//! wire-compatible with a real DDS/`rmw_fastrtps` peer, instead of assuming `cdr::CdrBe`.
+
+/// Byte order of a CDR body, as selected by the encapsulation header's representation
+/// identifier (RTPS 10.2.3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Endianness {
+    Big,
+    Little,
+}
+
+const REP_ID_CDR_BE: u16 = 0x0000;
+const REP_ID_CDR_LE: u16 = 0x0001;
+
+/// Writes the 4-byte encapsulation header followed by the message timestamp and payload,
+/// serializing the timestamp with the chosen endianness.
+pub(crate) fn encode_message(endianness: Endianness, secs: u64, usecs: u32, payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(4 + 8 + 4 + payload.len());
+    let rep_id: u16 = match endianness {
+        Endianness::Big => REP_ID_CDR_BE,
+        Endianness::Little => REP_ID_CDR_LE,
+    };
+    out.extend_from_slice(&rep_id.to_be_bytes());
+    out.extend_from_slice(&[0x00, 0x00]); // representation options, normally unused
+
+    match endianness {
+        Endianness::Big => {
+            cdr::serialize_into::<_, _, _, cdr::CdrBe>(&mut out, &secs, cdr::Infinite).unwrap();
+            cdr::serialize_into::<_, _, _, cdr::CdrBe>(&mut out, &usecs, cdr::Infinite).unwrap();
+        }
+        Endianness::Little => {
+            cdr::serialize_into::<_, _, _, cdr::CdrLe>(&mut out, &secs, cdr::Infinite).unwrap();
+            cdr::serialize_into::<_, _, _, cdr::CdrLe>(&mut out, &usecs, cdr::Infinite).unwrap();
+        }
+    }
+
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Parses the leading encapsulation header and returns the endianness to decode the rest of
+/// the message with, along with the remaining bytes after the header. Returns `None` if the
+/// buffer is too short or the representation id is not a plain CDR identifier.
+pub(crate) fn decode_header(data: &[u8]) -> Option<(Endianness, &[u8])> {
+    if data.len() < 4 {
+        return None;
+    }
+    let rep_id = u16::from_be_bytes([data[0], data[1]]);
+    let endianness = match rep_id {
+        REP_ID_CDR_BE => Endianness::Big,
+        REP_ID_CDR_LE => Endianness::Little,
+        _ => return None,
+    };
+    Some((endianness, &data[4..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_big_endian() {
+        let payload = b"hello ros2".to_vec();
+        let encoded = encode_message(Endianness::Big, 42, 7, &payload);
+
+        let (endianness, rest) = decode_header(&encoded).unwrap();
+        assert_eq!(endianness, Endianness::Big);
+
+        let mut cursor = std::io::Cursor::new(rest.to_vec());
+        let secs: u64 = cdr::deserialize_from::<_, u64, _>(&mut cursor, cdr::Infinite).unwrap();
+        let usecs: u32 = cdr::deserialize_from::<_, u32, _>(&mut cursor, cdr::Infinite).unwrap();
+        assert_eq!(secs, 42);
+        assert_eq!(usecs, 7);
+    }
+
+    #[test]
+    fn test_round_trip_little_endian() {
+        let payload = b"hello ros2".to_vec();
+        let encoded = encode_message(Endianness::Little, 42, 7, &payload);
+
+        let (endianness, rest) = decode_header(&encoded).unwrap();
+        assert_eq!(endianness, Endianness::Little);
+
+        let mut cursor = std::io::Cursor::new(rest.to_vec());
+        let secs: u64 = cdr::deserialize_from::<_, u64, _>(&mut cursor, cdr::Infinite).unwrap();
+        let usecs: u32 = cdr::deserialize_from::<_, u32, _>(&mut cursor, cdr::Infinite).unwrap();
+        assert_eq!(secs, 42);
+        assert_eq!(usecs, 7);
+    }
+
+    #[test]
+    fn test_decode_header_rejects_short_buffer() {
+        assert!(decode_header(&[0x00, 0x01]).is_none());
+    }
+
+    #[test]
+    fn test_decode_header_rejects_unknown_representation() {
+        // 0x0002 is PL_CDR_BE, which this codec does not speak.
+        assert!(decode_header(&[0x00, 0x02, 0x00, 0x00, 1, 2, 3]).is_none());
+    }
+}
@@ -0,0 +1,602 @@
+// Copyright 2024 Esteve Fernandez
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! DDS-SQL-like content-filter expressions for ROS 2 content-filtered topics.
+//!
+//! `libp2p_c__rmw_subscription_set_content_filter`/`_get_content_filter` in `bindings.rs` are
+//! declarations for a C rmw surface whose implementation lives on the C++ side of this RMW,
+//! which is not part of this Rust tree; there is no `rosidl` type-support introspection
+//! available here to walk a message's fields. This module is the Rust-owned piece of the
+//! feature instead: a parser, compact AST, and evaluator for the grammar (`=`, `<`, `>`, `<=`,
+//! `>=`, `<>`, `AND`, `OR`, `NOT`, `LIKE`, `BETWEEN`, and `%n` positional parameters), exposed
+//! through [`FieldResolver`] so a caller that *does* have field values in hand (e.g. glue code
+//! that has already walked rosidl introspection) can evaluate a compiled filter without
+//! re-parsing the expression per message. `subscription.rs` wires `evaluate` into actual message
+//! delivery via `CallbackFieldResolver`, which forwards field lookups to a host-registered
+//! `FieldResolveCallback` (see `rs_libp2p_custom_subscription_set_field_resolver`) instead of
+//! walking rosidl introspection itself. Publisher-side predicate propagation over the discovery
+//! channel is a further optimization layered on top of `evaluate`, left for whichever side ends
+//! up owning discovery metadata.
+
+use crate::c_types::Libp2pRetT;
+
+use std::os::raw::c_char;
+
+/// A single resolved field's value, the two kinds DDS-SQL filter comparisons operate over.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FieldValue {
+    Number(f64),
+    Text(String),
+}
+
+/// Resolves a dot-separated ROS message field path (e.g. `["pose", "position", "x"]`) to its
+/// value. Implemented by whoever has already walked the message's `rosidl` introspection tree;
+/// this module only owns the filter grammar and its evaluation against already-resolved values.
+///
+/// `subscription.rs`'s `CallbackFieldResolver` is the one implementation in this crate, forwarding
+/// `resolve` across the FFI boundary to a host-registered `FieldResolveCallback` (see
+/// `rs_libp2p_custom_subscription_set_field_resolver`) that does have `rosidl` introspection.
+pub(crate) trait FieldResolver {
+    fn resolve(&self, path: &[String]) -> Option<FieldValue>;
+}
+
+/// C-ABI mirror of [`FieldValue`] a `FieldResolveCallback` writes a resolved field into, without
+/// requiring an owned `String` across the FFI boundary: `text_ptr`/`text_len` only need to stay
+/// valid for the duration of the call, since the Rust side copies them out before the callback
+/// returns.
+#[repr(C)]
+pub(crate) struct FfiFieldValue {
+    pub is_text: bool,
+    pub number: f64,
+    pub text_ptr: *const c_char,
+    pub text_len: usize,
+}
+
+impl Default for FfiFieldValue {
+    fn default() -> Self {
+        Self {
+            is_text: false,
+            number: 0.0,
+            text_ptr: std::ptr::null(),
+            text_len: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    Number(f64),
+    Text(String),
+    /// A `%n` positional parameter, resolved against `ContentFilter::parameters` at evaluation
+    /// time so the same compiled filter can be reused across `_set_content_filter` calls that
+    /// only change parameter values.
+    Param(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Compare(Vec<String>, CompareOp, Literal),
+    Like(Vec<String>, String),
+    Between(Vec<String>, Literal, Literal),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Str(String),
+    Param(usize),
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+    Not,
+    Like,
+    Between,
+    LParen,
+    RParen,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, Libp2pRetT> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '=' => {
+                tokens.push(Token::Eq);
+                i += 1;
+            }
+            '<' => {
+                if chars.get(i + 1) == Some(&'>') {
+                    tokens.push(Token::Ne);
+                    i += 2;
+                } else if chars.get(i + 1) == Some(&'=') {
+                    tokens.push(Token::Le);
+                    i += 2;
+                } else {
+                    tokens.push(Token::Lt);
+                    i += 1;
+                }
+            }
+            '>' => {
+                if chars.get(i + 1) == Some(&'=') {
+                    tokens.push(Token::Ge);
+                    i += 2;
+                } else {
+                    tokens.push(Token::Gt);
+                    i += 1;
+                }
+            }
+            '\'' => {
+                i += 1;
+                let mut s = String::new();
+                loop {
+                    match chars.get(i) {
+                        Some('\'') => {
+                            i += 1;
+                            break;
+                        }
+                        Some(ch) => {
+                            s.push(*ch);
+                            i += 1;
+                        }
+                        None => return Err(Libp2pRetT::InvalidArgument),
+                    }
+                }
+                tokens.push(Token::Str(s));
+            }
+            '%' => {
+                i += 1;
+                let start = i;
+                while chars.get(i).is_some_and(|c| c.is_ascii_digit()) {
+                    i += 1;
+                }
+                if start == i {
+                    return Err(Libp2pRetT::InvalidArgument);
+                }
+                let n: usize = chars[start..i]
+                    .iter()
+                    .collect::<String>()
+                    .parse()
+                    .map_err(|_| Libp2pRetT::InvalidArgument)?;
+                tokens.push(Token::Param(n));
+            }
+            c if c.is_ascii_digit() => {
+                let start = i;
+                while chars.get(i).is_some_and(|c| c.is_ascii_digit() || *c == '.') {
+                    i += 1;
+                }
+                let n: f64 = chars[start..i]
+                    .iter()
+                    .collect::<String>()
+                    .parse()
+                    .map_err(|_| Libp2pRetT::InvalidArgument)?;
+                tokens.push(Token::Number(n));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while chars
+                    .get(i)
+                    .is_some_and(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                tokens.push(match word.to_ascii_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    "LIKE" => Token::Like,
+                    "BETWEEN" => Token::Between,
+                    _ => Token::Ident(word),
+                });
+            }
+            _ => return Err(Libp2pRetT::InvalidArgument),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive-descent parser over the filter grammar, lowest to highest precedence: `OR`, `AND`,
+/// `NOT`, then a predicate or a parenthesized sub-expression.
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, Libp2pRetT> {
+        let mut left = self.parse_and()?;
+        while matches!(self.peek(), Some(Token::Or)) {
+            self.advance();
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, Libp2pRetT> {
+        let mut left = self.parse_not()?;
+        while matches!(self.peek(), Some(Token::And)) {
+            self.advance();
+            let right = self.parse_not()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, Libp2pRetT> {
+        if matches!(self.peek(), Some(Token::Not)) {
+            self.advance();
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, Libp2pRetT> {
+        if matches!(self.peek(), Some(Token::LParen)) {
+            self.advance();
+            let inner = self.parse_or()?;
+            match self.advance() {
+                Some(Token::RParen) => Ok(inner),
+                _ => Err(Libp2pRetT::InvalidArgument),
+            }
+        } else {
+            self.parse_predicate()
+        }
+    }
+
+    fn parse_field_path(&mut self) -> Result<Vec<String>, Libp2pRetT> {
+        match self.advance() {
+            Some(Token::Ident(name)) => Ok(name.split('.').map(str::to_string).collect()),
+            _ => Err(Libp2pRetT::InvalidArgument),
+        }
+    }
+
+    fn parse_literal(&mut self) -> Result<Literal, Libp2pRetT> {
+        match self.advance() {
+            Some(Token::Number(n)) => Ok(Literal::Number(*n)),
+            Some(Token::Str(s)) => Ok(Literal::Text(s.clone())),
+            Some(Token::Param(n)) => Ok(Literal::Param(*n)),
+            _ => Err(Libp2pRetT::InvalidArgument),
+        }
+    }
+
+    fn parse_predicate(&mut self) -> Result<Expr, Libp2pRetT> {
+        let path = self.parse_field_path()?;
+        match self.peek() {
+            Some(Token::Like) => {
+                self.advance();
+                match self.advance() {
+                    Some(Token::Str(pattern)) => Ok(Expr::Like(path, pattern.clone())),
+                    _ => Err(Libp2pRetT::InvalidArgument),
+                }
+            }
+            Some(Token::Between) => {
+                self.advance();
+                let lo = self.parse_literal()?;
+                match self.advance() {
+                    Some(Token::And) => {}
+                    _ => return Err(Libp2pRetT::InvalidArgument),
+                }
+                let hi = self.parse_literal()?;
+                Ok(Expr::Between(path, lo, hi))
+            }
+            Some(token) => {
+                let op = match token {
+                    Token::Eq => CompareOp::Eq,
+                    Token::Ne => CompareOp::Ne,
+                    Token::Lt => CompareOp::Lt,
+                    Token::Le => CompareOp::Le,
+                    Token::Gt => CompareOp::Gt,
+                    Token::Ge => CompareOp::Ge,
+                    _ => return Err(Libp2pRetT::InvalidArgument),
+                };
+                self.advance();
+                let literal = self.parse_literal()?;
+                Ok(Expr::Compare(path, op, literal))
+            }
+            None => Err(Libp2pRetT::InvalidArgument),
+        }
+    }
+}
+
+fn resolve_literal(literal: &Literal, parameters: &[String]) -> Option<FieldValue> {
+    match literal {
+        Literal::Number(n) => Some(FieldValue::Number(*n)),
+        Literal::Text(s) => Some(FieldValue::Text(s.clone())),
+        Literal::Param(i) => {
+            let raw = parameters.get(*i)?;
+            match raw.parse::<f64>() {
+                Ok(n) => Some(FieldValue::Number(n)),
+                Err(_) => Some(FieldValue::Text(raw.clone())),
+            }
+        }
+    }
+}
+
+fn compare(field: &FieldValue, op: &CompareOp, value: &FieldValue) -> bool {
+    use std::cmp::Ordering;
+    let ordering = match (field, value) {
+        (FieldValue::Number(a), FieldValue::Number(b)) => a.partial_cmp(b),
+        (FieldValue::Text(a), FieldValue::Text(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    let Some(ordering) = ordering else {
+        return false;
+    };
+    match op {
+        CompareOp::Eq => ordering == Ordering::Equal,
+        CompareOp::Ne => ordering != Ordering::Equal,
+        CompareOp::Lt => ordering == Ordering::Less,
+        CompareOp::Le => ordering != Ordering::Greater,
+        CompareOp::Gt => ordering == Ordering::Greater,
+        CompareOp::Ge => ordering != Ordering::Less,
+    }
+}
+
+/// Matches `text` against a SQL `LIKE` pattern (`%` = any run of characters, `_` = any single
+/// character).
+///
+/// `text` comes from a deserialized field of an untrusted peer's ROS message, so this is an
+/// iterative two-pointer matcher (the standard `*`/`?` wildcard-matching algorithm) rather than
+/// the naive backtracking recursion: backtracking re-explores both branches of every `%` with no
+/// memoization, so it's worst-case exponential in the number of wildcards and recurses to a
+/// depth proportional to `text.len()`, letting one hostile message field pin a CPU core or blow
+/// the stack. This runs in O(text.len() * pattern.len()) worst case, with constant extra space
+/// and no recursion.
+fn like_match(text: &str, pattern: &str) -> bool {
+    let text = text.as_bytes();
+    let pattern = pattern.as_bytes();
+
+    let (mut ti, mut pi) = (0usize, 0usize);
+    // Index into `pattern` of the most recent unconsumed `%`, and how far into `text` we'd
+    // matched up to when we hit it, so a later mismatch can backtrack to "let the `%` eat one
+    // more character" instead of recursing.
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < text.len() {
+        if pi < pattern.len() && pattern[pi] == b'%' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if pi < pattern.len() && (pattern[pi] == b'_' || pattern[pi] == text[ti]) {
+            ti += 1;
+            pi += 1;
+        } else if let Some((star_pi, star_ti)) = star {
+            pi = star_pi + 1;
+            ti = star_ti + 1;
+            star = Some((star_pi, ti));
+        } else {
+            return false;
+        }
+    }
+
+    while pi < pattern.len() && pattern[pi] == b'%' {
+        pi += 1;
+    }
+    pi == pattern.len()
+}
+
+fn eval(expr: &Expr, resolver: &dyn FieldResolver, parameters: &[String]) -> bool {
+    match expr {
+        Expr::And(l, r) => eval(l, resolver, parameters) && eval(r, resolver, parameters),
+        Expr::Or(l, r) => eval(l, resolver, parameters) || eval(r, resolver, parameters),
+        Expr::Not(e) => !eval(e, resolver, parameters),
+        Expr::Compare(path, op, literal) => {
+            let (Some(field), Some(value)) = (
+                resolver.resolve(path),
+                resolve_literal(literal, parameters),
+            ) else {
+                return false;
+            };
+            compare(&field, op, &value)
+        }
+        Expr::Like(path, pattern) => match resolver.resolve(path) {
+            Some(FieldValue::Text(text)) => like_match(&text, pattern),
+            _ => false,
+        },
+        Expr::Between(path, lo, hi) => {
+            let Some(field) = resolver.resolve(path) else {
+                return false;
+            };
+            let (Some(lo), Some(hi)) =
+                (resolve_literal(lo, parameters), resolve_literal(hi, parameters))
+            else {
+                return false;
+            };
+            compare(&field, &CompareOp::Ge, &lo) && compare(&field, &CompareOp::Le, &hi)
+        }
+    }
+}
+
+/// A compiled DDS-SQL-like content filter, as would be installed by
+/// `libp2p_c__rmw_subscription_set_content_filter`.
+pub(crate) struct ContentFilter {
+    expression: String,
+    parameters: Vec<String>,
+    ast: Expr,
+}
+
+impl ContentFilter {
+    /// Parses `expression` into an AST, ready for repeated [`ContentFilter::evaluate`] calls
+    /// without re-parsing per message.
+    pub(crate) fn compile(expression: &str, parameters: &[String]) -> Result<Self, Libp2pRetT> {
+        let tokens = tokenize(expression)?;
+        let mut parser = Parser { tokens: &tokens, pos: 0 };
+        let ast = parser.parse_or()?;
+        if parser.pos != tokens.len() {
+            return Err(Libp2pRetT::InvalidArgument);
+        }
+        Ok(Self {
+            expression: expression.to_string(),
+            parameters: parameters.to_vec(),
+            ast,
+        })
+    }
+
+    /// The filter expression this instance was compiled from, for `_get_content_filter` to
+    /// round-trip back out through the caller's allocator.
+    pub(crate) fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// The positional `%n` parameter values this instance was compiled with.
+    pub(crate) fn parameters(&self) -> &[String] {
+        &self.parameters
+    }
+
+    /// Evaluates the compiled filter against `resolver`, returning `true` if the message should
+    /// be delivered.
+    pub(crate) fn evaluate(&self, resolver: &dyn FieldResolver) -> bool {
+        eval(&self.ast, resolver, &self.parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MapResolver(std::collections::HashMap<&'static str, FieldValue>);
+
+    impl FieldResolver for MapResolver {
+        fn resolve(&self, path: &[String]) -> Option<FieldValue> {
+            self.0.get(path.join(".").as_str()).cloned()
+        }
+    }
+
+    #[test]
+    fn test_simple_numeric_comparison() {
+        let filter = ContentFilter::compile("pose.position.x > 1.5", &[]).unwrap();
+        let resolver = MapResolver(std::collections::HashMap::from([(
+            "pose.position.x",
+            FieldValue::Number(2.0),
+        )]));
+        assert!(filter.evaluate(&resolver));
+
+        let resolver = MapResolver(std::collections::HashMap::from([(
+            "pose.position.x",
+            FieldValue::Number(1.0),
+        )]));
+        assert!(!filter.evaluate(&resolver));
+    }
+
+    #[test]
+    fn test_and_or_not_precedence() {
+        // NOT binds tighter than AND, which binds tighter than OR.
+        let filter =
+            ContentFilter::compile("a = 1 OR NOT b = 2 AND c = 3", &[]).unwrap();
+        let resolver = MapResolver(std::collections::HashMap::from([
+            ("a", FieldValue::Number(0.0)),
+            ("b", FieldValue::Number(5.0)),
+            ("c", FieldValue::Number(3.0)),
+        ]));
+        // a = 1 is false; NOT (b = 2) is true; c = 3 is true -> true AND true -> true overall.
+        assert!(filter.evaluate(&resolver));
+    }
+
+    #[test]
+    fn test_between() {
+        let filter = ContentFilter::compile("temperature BETWEEN 10 AND 20", &[]).unwrap();
+        let resolver = MapResolver(std::collections::HashMap::from([(
+            "temperature",
+            FieldValue::Number(15.0),
+        )]));
+        assert!(filter.evaluate(&resolver));
+
+        let resolver = MapResolver(std::collections::HashMap::from([(
+            "temperature",
+            FieldValue::Number(25.0),
+        )]));
+        assert!(!filter.evaluate(&resolver));
+    }
+
+    #[test]
+    fn test_like() {
+        let filter = ContentFilter::compile("frame_id LIKE 'base_%'", &[]).unwrap();
+        let resolver = MapResolver(std::collections::HashMap::from([(
+            "frame_id",
+            FieldValue::Text("base_link".to_string()),
+        )]));
+        assert!(filter.evaluate(&resolver));
+
+        let resolver = MapResolver(std::collections::HashMap::from([(
+            "frame_id",
+            FieldValue::Text("odom".to_string()),
+        )]));
+        assert!(!filter.evaluate(&resolver));
+    }
+
+    #[test]
+    fn test_positional_parameter() {
+        let filter =
+            ContentFilter::compile("priority = %0", &["3".to_string()]).unwrap();
+        let resolver = MapResolver(std::collections::HashMap::from([(
+            "priority",
+            FieldValue::Number(3.0),
+        )]));
+        assert!(filter.evaluate(&resolver));
+    }
+
+    #[test]
+    fn test_round_trips_expression_and_parameters() {
+        let filter =
+            ContentFilter::compile("x = %0", &["42".to_string()]).unwrap();
+        assert_eq!(filter.expression(), "x = %0");
+        assert_eq!(filter.parameters(), ["42".to_string()]);
+    }
+
+    #[test]
+    fn test_malformed_expression_is_rejected() {
+        assert!(ContentFilter::compile("x = ", &[]).is_err());
+        assert!(ContentFilter::compile("(x = 1", &[]).is_err());
+    }
+}
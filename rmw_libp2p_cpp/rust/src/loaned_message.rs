@@ -0,0 +1,315 @@
+// Copyright 2024 Esteve Fernandez
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Zero-copy loaned messages backed by a POSIX shared-memory ring.
+//!
+//! `libp2p_c__rmw_borrow_loaned_message`/`_publish_loaned_message`/`_take_loaned_message` (see
+//! `bindings.rs`) are declared for a C++ rmw implementation this tree does not contain, so there
+//! is no `rosidl` message pointer upstream of this module to actually loan out. What this module
+//! does implement for real is a same-*process* zero-copy mechanism those entry points would need: a
+//! per-publisher named shared-memory segment carved into fixed-size slots, with a free list and a
+//! per-slot generation counter a subscriber can use to detect that its mapping is stale. A
+//! hypothetical C++ `libp2p_c__rmw_borrow_loaned_message` would call
+//! `rs_libp2p_custom_publisher_borrow_loaned_message` (`publisher.rs`) and hand `rosidl` the
+//! returned pointer directly instead of allocating on the heap.
+//!
+//! Despite the POSIX segment itself being nameable from any process on the host, this crate has
+//! no discovery signal that tells it whether a *different* process on the same host has a
+//! matching subscriber — only `Libp2pCustomNode::local_subscribers`, which is scoped to this
+//! process's own in-memory registrations. So `Libp2pCustomPublisher::publish_loaned_message`
+//! only ever hands the segment out (as a [`LoanHandle`]) to a subscriber in this same process;
+//! a subscriber in another process on the same host looks identical to a remote peer from here,
+//! and gets a normal serialized copy over gossipsub like any other match. Extending this to real
+//! cross-process same-host delivery would need a host-local discovery channel (e.g. a shared
+//! directory or socket subscribers and publishers both register with) to actually gate the loan
+//! path on, which does not exist in this crate today.
+
+use std::collections::VecDeque;
+use std::ffi::CString;
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex as SyncMutex;
+
+/// The slot size and count a `Libp2pCustomPublisher` carves its ring into. Not yet configurable
+/// per-publisher since there is no QoS policy in this crate to drive it from.
+pub(crate) const DEFAULT_LOAN_SLOT_SIZE: usize = 4096;
+pub(crate) const DEFAULT_LOAN_SLOT_COUNT: usize = 8;
+
+const GENERATION_SIZE: usize = std::mem::size_of::<u64>();
+
+/// A POSIX `shm_open`-backed ring of fixed-size slots, shared between a publisher and any
+/// same-process subscribers that mapped its name (see the module doc comment for why this
+/// doesn't currently extend across processes on the same host).
+///
+/// The segment is laid out as `slot_count` `u64` generation counters followed by `slot_count`
+/// slots of `slot_size` payload bytes each.
+pub(crate) struct ShmRing {
+    name: String,
+    slot_size: usize,
+    slot_count: usize,
+    fd: libc::c_int,
+    base: *mut u8,
+    mapped_len: usize,
+    free_slots: SyncMutex<VecDeque<usize>>,
+    owns_segment: bool,
+}
+
+// `base` only ever points into memory this ring itself mapped with `MAP_SHARED`; the generation
+// counters are accessed atomically, and `free_slots` guards the free-list.
+unsafe impl Send for ShmRing {}
+unsafe impl Sync for ShmRing {}
+
+impl ShmRing {
+    /// Creates a new read-write segment. `name` must be unique enough to not collide with
+    /// another publisher's ring; callers derive it from their GID.
+    pub(crate) fn create(name: &str, slot_size: usize, slot_count: usize) -> io::Result<Self> {
+        Self::open(name, slot_size, slot_count, true)
+    }
+
+    /// Maps an existing segment read-only, for a same-process subscriber taking a loaned message.
+    pub(crate) fn open_read_only(
+        name: &str,
+        slot_size: usize,
+        slot_count: usize,
+    ) -> io::Result<Self> {
+        Self::open(name, slot_size, slot_count, false)
+    }
+
+    fn open(name: &str, slot_size: usize, slot_count: usize, create: bool) -> io::Result<Self> {
+        let c_name =
+            CString::new(name).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+        let oflag = if create {
+            libc::O_CREAT | libc::O_RDWR
+        } else {
+            libc::O_RDONLY
+        };
+        let fd = unsafe { libc::shm_open(c_name.as_ptr(), oflag, 0o600) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mapped_len = GENERATION_SIZE * slot_count + slot_size * slot_count;
+        if create && unsafe { libc::ftruncate(fd, mapped_len as libc::off_t) } != 0 {
+            let err = io::Error::last_os_error();
+            unsafe { libc::close(fd) };
+            return Err(err);
+        }
+
+        let prot = if create {
+            libc::PROT_READ | libc::PROT_WRITE
+        } else {
+            libc::PROT_READ
+        };
+        let base =
+            unsafe { libc::mmap(std::ptr::null_mut(), mapped_len, prot, libc::MAP_SHARED, fd, 0) };
+        if base == libc::MAP_FAILED {
+            let err = io::Error::last_os_error();
+            unsafe { libc::close(fd) };
+            return Err(err);
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            slot_size,
+            slot_count,
+            fd,
+            base: base as *mut u8,
+            mapped_len,
+            free_slots: SyncMutex::new((0..slot_count).collect()),
+            owns_segment: create,
+        })
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn generation_ptr(&self, index: usize) -> *const AtomicU64 {
+        unsafe { self.base.add(index * GENERATION_SIZE) as *const AtomicU64 }
+    }
+
+    fn slot_offset(&self, index: usize) -> usize {
+        GENERATION_SIZE * self.slot_count + index * self.slot_size
+    }
+
+    /// Hands out the next free slot, or `None` if every slot is currently on loan.
+    pub(crate) fn borrow(&self) -> Option<(usize, *mut u8)> {
+        let index = self.free_slots.lock().unwrap().pop_front()?;
+        Some((index, unsafe { self.base.add(self.slot_offset(index)) }))
+    }
+
+    /// Returns a pointer to `index`'s payload bytes, valid for `slot_size` bytes, or `None` if
+    /// `index` is out of range for this ring.
+    pub(crate) fn slot(&self, index: usize) -> Option<*const u8> {
+        if index >= self.slot_count {
+            return None;
+        }
+        Some(unsafe { self.base.add(self.slot_offset(index)) })
+    }
+
+    /// Bumps `index`'s generation counter and returns the new value, to be sent alongside the
+    /// segment name and slot index so a subscriber can detect a stale mapping.
+    pub(crate) fn publish(&self, index: usize) -> u64 {
+        let generation = unsafe { &*self.generation_ptr(index) };
+        generation.fetch_add(1, Ordering::Release) + 1
+    }
+
+    /// Reads `index`'s current generation, to compare against the generation carried in a
+    /// [`LoanHandle`] before trusting the slot's contents. Returns `None` if `index` is out of
+    /// range for this ring, same as `slot`.
+    pub(crate) fn generation(&self, index: usize) -> Option<u64> {
+        if index >= self.slot_count {
+            return None;
+        }
+        let generation = unsafe { &*self.generation_ptr(index) };
+        Some(generation.load(Ordering::Acquire))
+    }
+
+    /// Returns `index` to the free list, for a publisher that borrowed a slot but never
+    /// published it (or fell back to a serialized copy instead).
+    pub(crate) fn release(&self, index: usize) {
+        self.free_slots.lock().unwrap().push_back(index);
+    }
+}
+
+impl Drop for ShmRing {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.base as *mut libc::c_void, self.mapped_len);
+            libc::close(self.fd);
+        }
+        if self.owns_segment {
+            if let Ok(c_name) = CString::new(self.name.as_str()) {
+                unsafe {
+                    libc::shm_unlink(c_name.as_ptr());
+                }
+            }
+        }
+    }
+}
+
+/// What gets published over gossipsub in place of a serialized message when
+/// `Libp2pCustomPublisher::publish_loaned_message` can confirm a same-process subscriber, telling
+/// it where to find the real payload instead of sending it inline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LoanHandle {
+    pub(crate) segment_name: String,
+    pub(crate) slot_index: u32,
+    pub(crate) slot_size: u32,
+    pub(crate) slot_count: u32,
+    pub(crate) generation: u64,
+}
+
+/// Prefix distinguishing an encoded [`LoanHandle`] from an ordinary serialized message on the
+/// wire. CDR does not reserve an envelope byte for this in this crate, so this is a best-effort
+/// marker rather than a real framing guarantee: a message that happens to start with these four
+/// bytes would be misread as a loan handle. Acceptable for same-process delivery, where both ends
+/// agree on this out of band.
+const LOAN_HANDLE_MAGIC: [u8; 4] = *b"L2PL";
+
+impl LoanHandle {
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let mut buffer = LOAN_HANDLE_MAGIC.to_vec();
+        let name_bytes = self.segment_name.as_bytes();
+        buffer.extend_from_slice(&(name_bytes.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(name_bytes);
+        buffer.extend_from_slice(&self.slot_index.to_le_bytes());
+        buffer.extend_from_slice(&self.slot_size.to_le_bytes());
+        buffer.extend_from_slice(&self.slot_count.to_le_bytes());
+        buffer.extend_from_slice(&self.generation.to_le_bytes());
+        buffer
+    }
+
+    pub(crate) fn decode(buffer: &[u8]) -> Option<Self> {
+        let body = buffer.strip_prefix(&LOAN_HANDLE_MAGIC)?;
+        let name_len = u32::from_le_bytes(body.get(0..4)?.try_into().ok()?) as usize;
+        let mut offset = 4;
+        let segment_name = std::str::from_utf8(body.get(offset..offset + name_len)?)
+            .ok()?
+            .to_string();
+        offset += name_len;
+        let slot_index = u32::from_le_bytes(body.get(offset..offset + 4)?.try_into().ok()?);
+        offset += 4;
+        let slot_size = u32::from_le_bytes(body.get(offset..offset + 4)?.try_into().ok()?);
+        offset += 4;
+        let slot_count = u32::from_le_bytes(body.get(offset..offset + 4)?.try_into().ok()?);
+        offset += 4;
+        let generation = u64::from_le_bytes(body.get(offset..offset + 8)?.try_into().ok()?);
+        Some(Self {
+            segment_name,
+            slot_index,
+            slot_size,
+            slot_count,
+            generation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrow_publish_and_read_back_through_a_second_mapping() {
+        let name = "/rmw_libp2p_test_ring_roundtrip";
+        let ring = ShmRing::create(name, 64, 2).expect("create segment");
+        let (index, ptr) = ring.borrow().expect("a free slot");
+        unsafe {
+            std::ptr::copy_nonoverlapping(b"hello".as_ptr(), ptr, 5);
+        }
+        let generation = ring.publish(index);
+
+        let reader = ShmRing::open_read_only(name, 64, 2).expect("map read-only");
+        assert_eq!(reader.generation(index), Some(generation));
+        let slot = reader.slot(index).expect("in-range slot");
+        let bytes = unsafe { std::slice::from_raw_parts(slot, 5) };
+        assert_eq!(bytes, b"hello");
+    }
+
+    #[test]
+    fn generation_rejects_an_out_of_range_index_like_slot_does() {
+        let ring = ShmRing::create("/rmw_libp2p_test_ring_generation_bounds", 16, 2)
+            .expect("create segment");
+        assert!(ring.slot(2).is_none());
+        assert!(ring.generation(2).is_none());
+    }
+
+    #[test]
+    fn borrow_returns_none_once_every_slot_is_on_loan() {
+        let ring = ShmRing::create("/rmw_libp2p_test_ring_exhaustion", 16, 1)
+            .expect("create segment");
+        let (index, _) = ring.borrow().expect("first borrow succeeds");
+        assert!(ring.borrow().is_none());
+        ring.release(index);
+        assert!(ring.borrow().is_some());
+    }
+
+    #[test]
+    fn loan_handle_round_trips_through_encode_decode() {
+        let handle = LoanHandle {
+            segment_name: "/rmw_libp2p_abc123".to_string(),
+            slot_index: 3,
+            slot_size: 4096,
+            slot_count: 8,
+            generation: 42,
+        };
+        let encoded = handle.encode();
+        assert_eq!(LoanHandle::decode(&encoded), Some(handle));
+    }
+
+    #[test]
+    fn decode_rejects_a_buffer_without_the_loan_handle_magic() {
+        assert_eq!(LoanHandle::decode(b"not a loan handle"), None);
+    }
+}
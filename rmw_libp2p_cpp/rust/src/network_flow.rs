@@ -0,0 +1,126 @@
+// Copyright 2024 Esteve Fernandez
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Translates libp2p multiaddrs into the transport/internet-protocol/port/address shape that
+//! `rmw_network_flow_endpoint_t` wants.
+//!
+//! `libp2p_c__rmw_publisher_get_network_flow_endpoints`/`_subscription_get_network_flow_endpoints`
+//! in `bindings.rs` (and the `rmw_network_flow_endpoint_array_t` struct they fill) belong to the
+//! C++ side of this RMW, which this tree doesn't contain, so there is nothing here to fill an
+//! `rmw_network_flow_endpoint_array_t` directly. What this module does instead is the
+//! libp2p-specific part: given a node's listen and observed-external multiaddrs (tracked in
+//! `node.rs` from `SwarmEvent::NewListenAddr` and `identify::Event::Received`'s `observed_addr`),
+//! decompose each one into an [`Endpoint`] a caller can copy field-by-field into that struct.
+
+use libp2p::multiaddr::Protocol;
+use libp2p::Multiaddr;
+
+/// Mirrors the two transports `rmw_network_flow_endpoint_t::transport_protocol` distinguishes.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TransportProtocol {
+    Tcp = 0,
+    /// Also reported for QUIC, which rides over UDP.
+    Udp = 1,
+    Unknown = 2,
+}
+
+/// Mirrors `rmw_network_flow_endpoint_t::internet_protocol`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InternetProtocol {
+    V4 = 0,
+    V6 = 1,
+    Unknown = 2,
+}
+
+/// One multiaddr, decomposed into the fields `rmw_network_flow_endpoint_t` reports.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Endpoint {
+    pub(crate) transport_protocol: TransportProtocol,
+    pub(crate) internet_protocol: InternetProtocol,
+    pub(crate) transport_port: u16,
+    pub(crate) internet_address: String,
+}
+
+/// Decomposes a multiaddr such as `/ip4/192.168.1.2/tcp/7070` or
+/// `/ip6/::1/udp/7070/quic-v1` into an [`Endpoint`]. Returns `None` for multiaddrs that don't
+/// carry both an IP and a transport component, e.g. a bare `/p2p/<peer-id>` relay address.
+pub(crate) fn decompose(addr: &Multiaddr) -> Option<Endpoint> {
+    let mut internet_protocol = None;
+    let mut internet_address = None;
+    let mut transport_protocol = None;
+    let mut transport_port = None;
+
+    for protocol in addr.iter() {
+        match protocol {
+            Protocol::Ip4(ip) => {
+                internet_protocol = Some(InternetProtocol::V4);
+                internet_address = Some(ip.to_string());
+            }
+            Protocol::Ip6(ip) => {
+                internet_protocol = Some(InternetProtocol::V6);
+                internet_address = Some(ip.to_string());
+            }
+            Protocol::Tcp(port) => {
+                transport_protocol = Some(TransportProtocol::Tcp);
+                transport_port = Some(port);
+            }
+            Protocol::Udp(port) => {
+                transport_protocol = Some(TransportProtocol::Udp);
+                transport_port = Some(port);
+            }
+            // quic-v1 always rides over a preceding /udp/<port> component, already captured above.
+            _ => {}
+        }
+    }
+
+    Some(Endpoint {
+        transport_protocol: transport_protocol.unwrap_or(TransportProtocol::Unknown),
+        internet_protocol: internet_protocol?,
+        transport_port: transport_port?,
+        internet_address: internet_address?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decomposes_tcp_ipv4() {
+        let addr: Multiaddr = "/ip4/192.168.1.2/tcp/7070".parse().unwrap();
+        let endpoint = decompose(&addr).unwrap();
+        assert_eq!(endpoint.transport_protocol, TransportProtocol::Tcp);
+        assert_eq!(endpoint.internet_protocol, InternetProtocol::V4);
+        assert_eq!(endpoint.transport_port, 7070);
+        assert_eq!(endpoint.internet_address, "192.168.1.2");
+    }
+
+    #[test]
+    fn test_decomposes_quic_ipv6() {
+        let addr: Multiaddr = "/ip6/::1/udp/7071/quic-v1".parse().unwrap();
+        let endpoint = decompose(&addr).unwrap();
+        assert_eq!(endpoint.transport_protocol, TransportProtocol::Udp);
+        assert_eq!(endpoint.internet_protocol, InternetProtocol::V6);
+        assert_eq!(endpoint.transport_port, 7071);
+        assert_eq!(endpoint.internet_address, "::1");
+    }
+
+    #[test]
+    fn test_rejects_addr_without_ip() {
+        let addr: Multiaddr = "/p2p-circuit".parse().unwrap();
+        assert!(decompose(&addr).is_none());
+    }
+}
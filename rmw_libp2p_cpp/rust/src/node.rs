@@ -15,23 +15,51 @@
 use std::collections::hash_map::DefaultHasher;
 use std::ffi::c_void;
 use std::hash::{Hash, Hasher};
+use std::os::raw::c_char;
 use std::sync::Arc;
+use std::sync::Mutex as SyncMutex;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 
 use libp2p::{
-    futures::StreamExt, gossipsub, identity, mdns, swarm::NetworkBehaviour, swarm::SwarmEvent,
-    PeerId,
+    autonat, dcutr, futures::StreamExt, gossipsub, identify, identity, kad, mdns, relay,
+    request_response, swarm::behaviour::toggle::Toggle, swarm::NetworkBehaviour, swarm::SwarmEvent,
+    Multiaddr, PeerId, StreamProtocol,
 };
 
+use crate::bounded_queue::{BoundedQueue, OverflowPolicy};
+use crate::network_flow::{self, Endpoint};
+
 use tokio::runtime::Runtime;
 use tokio::sync::Notify;
 use tokio::sync::Mutex;
 use tokio::{select, task};
 
-use deadqueue::unlimited::Queue;
+/// The transport-security upgrade negotiated for a node's swarm.
+///
+/// The concrete protocol is picked at compile time via the `security-noise`
+/// (default) and `security-tls` Cargo features, mirroring how other libp2p
+/// embedders let operators swap the whole cryptographic stack without
+/// touching application code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityProtocol {
+    Noise,
+    Tls,
+}
+
+impl SecurityProtocol {
+    #[cfg(feature = "security-tls")]
+    const NEGOTIATED: SecurityProtocol = SecurityProtocol::Tls;
+
+    #[cfg(not(feature = "security-tls"))]
+    const NEGOTIATED: SecurityProtocol = SecurityProtocol::Noise;
+}
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub(crate) struct CustomSubscriptionHandle{
     pub ptr: *const c_void
 }
@@ -44,12 +72,40 @@ unsafe impl Sync for CustomSubscriptionHandle {}
 struct RosNetworkBehaviour {
     gossipsub: gossipsub::Behaviour,
     mdns: mdns::tokio::Behaviour,
+    // Discovers peers across routed networks that mDNS can't see, since mDNS only reaches
+    // peers on the same LAN segment.
+    kad: kad::Behaviour<kad::store::MemoryStore>,
+    identify: identify::Behaviour,
+    autonat: autonat::Behaviour,
+    dcutr: dcutr::Behaviour,
+    relay_client: relay::client::Behaviour,
+    // Only publicly-reachable nodes actually serve as relays; unreachable nodes keep this
+    // disabled and instead make reservations on a configured relay.
+    relay_server: Toggle<relay::Behaviour>,
+    // Backs ROS services and action clients, which are inherently request/reply rather than
+    // pub/sub. Requests and responses are addressed to a specific peer via a `ResponseChannel`
+    // instead of being broadcast the way gossipsub messages are.
+    request_response: request_response::cbor::Behaviour<Vec<u8>, Vec<u8>>,
+    // Answers a `TRANSIENT_LOCAL` subscriber's request for the samples a matching publisher
+    // buffered before the subscriber joined, since gossipsub itself is fire-and-forget and never
+    // replays anything to a late joiner. Request is the topic name; response is the publisher's
+    // buffered samples, oldest first, or an empty `Vec` if it has no (or a volatile) history for
+    // that topic.
+    history: request_response::cbor::Behaviour<String, Vec<Vec<u8>>>,
 }
 
 #[derive(Debug)]
 enum OutEvent {
     Gossipsub(gossipsub::Event),
     Mdns(mdns::Event),
+    Kad(kad::Event),
+    Identify(identify::Event),
+    Autonat(autonat::Event),
+    Dcutr(dcutr::Event),
+    RelayClient(relay::client::Event),
+    RelayServer(relay::Event),
+    RequestResponse(request_response::Event<Vec<u8>, Vec<u8>>),
+    History(request_response::Event<String, Vec<Vec<u8>>>),
 }
 
 impl From<mdns::Event> for OutEvent {
@@ -64,6 +120,596 @@ impl From<gossipsub::Event> for OutEvent {
     }
 }
 
+impl From<identify::Event> for OutEvent {
+    fn from(v: identify::Event) -> Self {
+        Self::Identify(v)
+    }
+}
+
+impl From<kad::Event> for OutEvent {
+    fn from(v: kad::Event) -> Self {
+        Self::Kad(v)
+    }
+}
+
+impl From<autonat::Event> for OutEvent {
+    fn from(v: autonat::Event) -> Self {
+        Self::Autonat(v)
+    }
+}
+
+impl From<dcutr::Event> for OutEvent {
+    fn from(v: dcutr::Event) -> Self {
+        Self::Dcutr(v)
+    }
+}
+
+impl From<relay::client::Event> for OutEvent {
+    fn from(v: relay::client::Event) -> Self {
+        Self::RelayClient(v)
+    }
+}
+
+impl From<relay::Event> for OutEvent {
+    fn from(v: relay::Event) -> Self {
+        Self::RelayServer(v)
+    }
+}
+
+impl From<request_response::Event<Vec<u8>, Vec<u8>>> for OutEvent {
+    fn from(v: request_response::Event<Vec<u8>, Vec<u8>>) -> Self {
+        Self::RequestResponse(v)
+    }
+}
+
+impl From<request_response::Event<String, Vec<Vec<u8>>>> for OutEvent {
+    fn from(v: request_response::Event<String, Vec<Vec<u8>>>) -> Self {
+        Self::History(v)
+    }
+}
+
+/// A ROS service/action request or response framed with the service name it targets, since a
+/// single `request_response` protocol is shared by every service registered on a node.
+///
+/// Wire format: a 2-byte big-endian service-name length, the UTF-8 service name, then the raw
+/// payload bytes.
+fn encode_service_message(service_name: &str, payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(2 + service_name.len() + payload.len());
+    out.extend_from_slice(&(service_name.len() as u16).to_be_bytes());
+    out.extend_from_slice(service_name.as_bytes());
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Inverse of [`encode_service_message`]. Returns `None` if `data` is too short or the embedded
+/// service name is not valid UTF-8.
+fn decode_service_message(data: &[u8]) -> Option<(&str, &[u8])> {
+    if data.len() < 2 {
+        return None;
+    }
+    let name_len = u16::from_be_bytes([data[0], data[1]]) as usize;
+    if data.len() < 2 + name_len {
+        return None;
+    }
+    let name = std::str::from_utf8(&data[2..2 + name_len]).ok()?;
+    Some((name, &data[2 + name_len..]))
+}
+
+/// Loads a peer set previously written by [`save_known_peers`], one multiaddr per line,
+/// each already including its trailing `/p2p/<peer-id>` component.
+///
+/// Returns an empty set if `path` does not exist yet (e.g. a node's first run) rather than
+/// treating a missing peerstore file as an error. Lines that fail to parse as a multiaddr are
+/// skipped.
+fn load_known_peers(path: &Path) -> HashSet<Multiaddr> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return HashSet::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| line.trim().parse::<Multiaddr>().ok())
+        .collect()
+}
+
+/// Persists `peers` to `path`, one multiaddr per line, so a future node started with
+/// `new_with_peerstore` can reconnect to them without waiting for mDNS or a DHT bootstrap query
+/// to rediscover them from scratch.
+fn save_known_peers(path: &Path, peers: &HashSet<Multiaddr>) {
+    let contents = peers
+        .iter()
+        .map(|addr| addr.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if let Err(e) = std::fs::write(path, contents) {
+        println!("Failed to persist peer routing table to {path:?}: {e:?}");
+    }
+}
+
+/// Handler registered by a ROS service server, invoked synchronously from the event loop with
+/// the request bytes. Writes the response into the caller-allocated `resp_buf` (of capacity
+/// `resp_capacity`), writes the number of bytes written into `out_resp_len`, and returns `true`
+/// if the response fit; `false` if `resp_capacity` was too small.
+pub(crate) type ServiceCallback = unsafe extern "C" fn(
+    &CustomSubscriptionHandle,
+    *const u8,
+    usize,
+    *mut u8,
+    usize,
+    *mut usize,
+) -> bool;
+
+/// Handler registered by a ROS service/action client, invoked with the response bytes once the
+/// server for its outstanding request replies.
+pub(crate) type ClientCallback = unsafe extern "C" fn(&CustomSubscriptionHandle, *mut u8, len: usize);
+
+/// A response buffer size generous enough for typical ROS service/action replies without
+/// requiring the C side to pre-negotiate a size.
+const SERVICE_RESPONSE_SCRATCH_CAPACITY: usize = 65536;
+
+/// Whether a node believes itself to be reachable from the public internet, as reported by
+/// `autonat`. Nodes that are `Private` make a Circuit Relay v2 reservation on a configured
+/// relay and advertise their `/p2p-circuit` address instead of serving as a relay themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reachability {
+    Unknown,
+    Public,
+    Private,
+}
+
+/// Who drives the swarm: the crate itself, or a host that already owns an executor.
+///
+/// Borrowed from the "event-loop-factory" injection idea, where the runtime is a replaceable
+/// component supplied by the embedder rather than baked into the library. `Managed` keeps the
+/// existing behavior of spawning a private tokio task that loops forever. `External` instead
+/// leaves the swarm parked between calls, to be driven one step at a time by the host calling
+/// [`Libp2pCustomNode::step`] (exposed over FFI as `rs_libp2p_custom_node_step`) from its own
+/// loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriverMode {
+    Managed,
+    External,
+}
+
+/// Verdict an application's subscription callback returns for a received gossipsub message,
+/// mirroring `gossipsub::MessageAcceptance`. Gossipsub runs in
+/// `ValidationMode::Permissive` with manual validation, so nothing propagates past this node
+/// until the callback's verdict is reported back via `report_message_validation_result`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageAcceptance {
+    /// The message is valid and should be forwarded to other peers.
+    Accept = 0,
+    /// The message is invalid; the sending peer is penalized and the message is dropped.
+    Reject = 1,
+    /// The message should be dropped without forwarding or penalizing the sender.
+    Ignore = 2,
+}
+
+impl From<MessageAcceptance> for gossipsub::MessageAcceptance {
+    fn from(verdict: MessageAcceptance) -> Self {
+        match verdict {
+            MessageAcceptance::Accept => gossipsub::MessageAcceptance::Accept,
+            MessageAcceptance::Reject => gossipsub::MessageAcceptance::Reject,
+            MessageAcceptance::Ignore => gossipsub::MessageAcceptance::Ignore,
+        }
+    }
+}
+
+/// Handler registered by a ROS topic subscriber, invoked with each received message's bytes.
+/// Its return value gates whether gossipsub forwards the message to other peers.
+pub(crate) type SubscriptionMessageCallback =
+    unsafe extern "C" fn(&CustomSubscriptionHandle, *mut u8, len: usize) -> MessageAcceptance;
+
+/// Resolves one dot-separated field path (e.g. `pose.position.x`, passed as a null-terminated C
+/// string) out of a subscription's raw deserialized message bytes, for
+/// `crate::content_filter::ContentFilter::evaluate` to consult via `subscription.rs`'s
+/// `CallbackFieldResolver`. Writes the resolved value into `*mut FfiFieldValue` and returns
+/// `true`, or returns `false` if the path does not resolve (e.g. an optional member that is
+/// unset, or a path this message type does not have).
+///
+/// This exists because `rosidl` type-support introspection — needed to walk an arbitrary
+/// message's fields by name — is only available on the C++ side of this RMW, not in this Rust
+/// tree; a host that does have it registers one of these via
+/// `rs_libp2p_custom_subscription_set_field_resolver` instead of this crate attempting to decode
+/// CDR generically.
+pub(crate) type FieldResolveCallback = unsafe extern "C" fn(
+    &CustomSubscriptionHandle,
+    *const u8,
+    usize,
+    *const c_char,
+    *mut crate::content_filter::FfiFieldValue,
+) -> bool;
+
+/// Where a message queued on a subscription's `IncomingQueue` actually came from.
+///
+/// `take_loaned_message` (`subscription.rs`) only trusts a payload's bytes enough to attempt
+/// `LoanHandle::decode` on it when this is `Local`: a [`LoanHandle`](crate::loaned_message::LoanHandle)
+/// names a shared-memory segment to `mmap`, so treating an untrusted remote peer's bytes as one
+/// is a DoS vector (a forged segment name/size can be mapped and read past its real backing
+/// segment). `Remote` covers both live gossipsub delivery and `TRANSIENT_LOCAL` history replies,
+/// since both originate from another process across the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MessageOrigin {
+    /// Delivered via this same process's own `publish_message` loopback (see
+    /// `Libp2pCustomNode::local_subscribers`), never touching the network.
+    Local,
+    /// Delivered by gossipsub from another peer, or replayed from another peer's
+    /// `TRANSIENT_LOCAL` history.
+    Remote,
+}
+
+/// A subscription's poll-based delivery queue, fed by the event loop alongside (not instead of)
+/// the synchronous `SubscriptionMessageCallback`, so a host can implement `rmw_wait`/`rmw_take`
+/// by draining this queue from its own thread instead of handling messages from inside the
+/// swarm task.
+pub(crate) type IncomingQueue = Arc<BoundedQueue<(gossipsub::IdentTopic, Vec<u8>, MessageOrigin)>>;
+
+type SubscriptionCallback = (CustomSubscriptionHandle, SubscriptionMessageCallback, IncomingQueue);
+
+/// A registered ROS service server: the handle/callback pair invoked with each decoded request.
+type ServiceCallbackEntry = (CustomSubscriptionHandle, ServiceCallback);
+
+/// A ROS service/action client's outstanding request, kept around until the matching response
+/// (or failure) arrives so the right `ClientCallback` can be invoked.
+type PendingRequestEntry = (CustomSubscriptionHandle, ClientCallback);
+
+/// The swarm and the per-topic/per-service callback tables it dispatches inbound messages to,
+/// grouped so `Libp2pCustomNode::step` can lock and drive all of them together without tearing
+/// them apart across FFI calls.
+struct EventLoopState {
+    swarm: libp2p::Swarm<RosNetworkBehaviour>,
+    subscription_callback: HashMap<String, SubscriptionCallback>,
+    service_callback: HashMap<String, ServiceCallbackEntry>,
+    pending_requests: HashMap<request_response::OutboundRequestId, PendingRequestEntry>,
+    /// A subscription's outstanding `TRANSIENT_LOCAL` history query, kept around until the
+    /// matching response (or failure) arrives so the replayed samples can be pushed into the
+    /// right subscription's `incoming_queue`, tagged with its topic.
+    pending_history_requests: HashMap<request_response::OutboundRequestId, (IncomingQueue, gossipsub::IdentTopic)>,
+}
+
+/// Drives the swarm through exactly one `select!` iteration: one popped queue entry, or one
+/// swarm event, whichever is ready first. Returns `false` once `stop_notify` fires, at which
+/// point the caller should stop calling this again.
+///
+/// This is the single "step" both the built-in managed loop and the externally-driven `step()`
+/// API are built on, so the two driver modes can never drift apart in behavior.
+async fn drive_swarm_once(
+    state: &mut EventLoopState,
+    stop_notify: &Arc<Notify>,
+    outgoing_queue: &Arc<BoundedQueue<(gossipsub::IdentTopic, Vec<u8>)>>,
+    new_subscribers_queue: &Arc<BoundedQueue<(
+        gossipsub::IdentTopic,
+        CustomSubscriptionHandle,
+        SubscriptionMessageCallback,
+        IncomingQueue,
+    )>>,
+    relay_addrs_queue: &Arc<deadqueue::unlimited::Queue<Multiaddr>>,
+    reachability: &Arc<std::sync::atomic::AtomicU8>,
+    bootstrap_peers_queue: &Arc<deadqueue::unlimited::Queue<Multiaddr>>,
+    new_services_queue: &Arc<deadqueue::unlimited::Queue<(String, CustomSubscriptionHandle, ServiceCallback)>>,
+    outgoing_requests_queue: &Arc<deadqueue::unlimited::Queue<(
+        PeerId,
+        String,
+        Vec<u8>,
+        CustomSubscriptionHandle,
+        ClientCallback,
+    )>>,
+    known_peers: &Arc<SyncMutex<HashSet<Multiaddr>>>,
+    listen_addrs: &Arc<SyncMutex<HashSet<Multiaddr>>>,
+    external_addrs: &Arc<SyncMutex<HashSet<Multiaddr>>>,
+    history_queries_queue: &Arc<deadqueue::unlimited::Queue<(gossipsub::IdentTopic, IncomingQueue)>>,
+    publisher_history: &Arc<SyncMutex<HashMap<String, VecDeque<Vec<u8>>>>>,
+) -> bool {
+    select! {
+        // use a Notify that will be triggered to stop the swarm
+        // select! will wait on any future
+        _ = stop_notify.notified() => {
+            println!("Exit loop");
+            return false;
+        },
+
+        (topic, obj, callback, incoming_queue) = new_subscribers_queue.pop() => {
+            // println!("Subscribing to topic: {}", topic);
+            state.swarm.behaviour_mut().gossipsub.subscribe(&topic).unwrap();
+            state.subscription_callback.insert(topic.hash().into_string(), (obj, callback, incoming_queue));
+        },
+
+        // register a relay and make a reservation on it so this node can be
+        // dialed behind a NAT via a `/p2p-circuit` address
+        relay_addr = relay_addrs_queue.pop() => {
+            if let Some(relay_peer_id) = relay_addr.iter().find_map(|p| match p {
+                libp2p::multiaddr::Protocol::P2p(peer_id) => Some(peer_id),
+                _ => None,
+            }) {
+                state.swarm.behaviour_mut().autonat.add_server(relay_peer_id, Some(relay_addr.clone()));
+            }
+            let circuit_addr = relay_addr.clone().with(libp2p::multiaddr::Protocol::P2pCircuit);
+            if let Err(e) = state.swarm.listen_on(circuit_addr) {
+                println!("Relay reservation error: {e:?}");
+            }
+        },
+
+        // register a bootstrap peer's address in the Kademlia routing table and kick off a
+        // bootstrap query so the DHT can find peers beyond this one
+        bootstrap_addr = bootstrap_peers_queue.pop() => {
+            if let Some(peer_id) = bootstrap_addr.iter().find_map(|p| match p {
+                libp2p::multiaddr::Protocol::P2p(peer_id) => Some(peer_id),
+                _ => None,
+            }) {
+                state.swarm.behaviour_mut().kad.add_address(&peer_id, bootstrap_addr);
+                if let Err(e) = state.swarm.behaviour_mut().kad.bootstrap() {
+                    println!("Kademlia bootstrap error: {e:?}");
+                }
+            } else {
+                println!("Bootstrap multiaddr missing a /p2p/<peer-id> suffix: {bootstrap_addr}");
+            }
+        },
+
+        // a subscription with TRANSIENT_LOCAL durability was just created; ask the topic's mesh
+        // peers for any samples they have buffered from before this subscriber joined
+        (topic, incoming_queue) = history_queries_queue.pop() => {
+            let peers: Vec<PeerId> = state.swarm.behaviour().gossipsub.mesh_peers(&topic.hash()).copied().collect();
+            if peers.is_empty() {
+                println!("No mesh peers known for {topic} yet; TRANSIENT_LOCAL history query dropped");
+            }
+            for peer in peers {
+                let request_id = state.swarm.behaviour_mut().history.send_request(&peer, topic.to_string());
+                state.pending_history_requests.insert(request_id, (Arc::clone(&incoming_queue), topic.clone()));
+            }
+        },
+
+        (service_name, obj, callback) = new_services_queue.pop() => {
+            state.service_callback.insert(service_name, (obj, callback));
+        },
+
+        (peer_id, service_name, payload, obj, callback) = outgoing_requests_queue.pop() => {
+            let request = encode_service_message(&service_name, &payload);
+            let request_id = state.swarm.behaviour_mut().request_response.send_request(&peer_id, request);
+            state.pending_requests.insert(request_id, (obj, callback));
+        },
+
+        // pop messages from the queue and publish them to the network
+        (topic, buffer) = outgoing_queue.pop() => {
+            // TODO(esteve): use some sort of debug log
+            // println!("Publishing message on topic {} : {:?}", topic, buffer);
+            if let Err(e) = state.swarm.behaviour_mut().gossipsub.publish(topic.clone(), buffer.clone()) {
+                println!("Publish error: {e:?}");
+            }
+        },
+
+        event = state.swarm.select_next_some() => match event {
+            SwarmEvent::Behaviour(OutEvent::Gossipsub(gossipsub::Event::Message {
+                propagation_source: peer_id,
+                message_id: id,
+                message,
+            })) => {
+                // TODO(esteve): use some sort of debug log
+                // println!(
+                //     "Got message: {:?} with id: {} from peer: {:?} topic: {}",
+                //     message.data,
+                //     id,
+                //     peer_id,
+                //     message.topic.as_str(),
+                // );
+                // Strip the CDR encapsulation header so the callback only ever sees
+                // the timestamp + payload, regardless of which endianness the peer
+                // that published this message used.
+                let payload = match crate::cdr_codec::decode_header(&message.data) {
+                    Some((_endianness, rest)) => rest.to_vec(),
+                    None => message.data,
+                };
+                let topic = message.topic.into_string();
+                let verdict = match state.subscription_callback.get(&topic) {
+                    Some((obj, callback, incoming_queue)) => {
+                        // Poll-based consumers (`rs_libp2p_custom_subscription_take_message`)
+                        // drain this queue independently of the synchronous callback below, so a
+                        // slow `rmw_take` caller never stalls gossipsub's heartbeat.
+                        incoming_queue
+                            .push((
+                                gossipsub::IdentTopic::new(topic.clone()),
+                                payload.clone(),
+                                MessageOrigin::Remote,
+                            ))
+                            .await;
+                        let mut vec = payload.clone();
+                        vec.shrink_to_fit();
+                        let ptr: *mut u8 = vec.as_mut_ptr();
+                        let len: usize = vec.len();
+                        std::mem::forget(vec);
+                        unsafe { callback(obj, ptr, len) }
+                    }
+                    None => MessageAcceptance::Ignore,
+                };
+                let _ = state.swarm.behaviour_mut().gossipsub.report_message_validation_result(
+                    &id,
+                    &peer_id,
+                    verdict.into(),
+                );
+            }
+            SwarmEvent::NewListenAddr { address, .. } => {
+                println!("Listening on {:?}", address);
+                listen_addrs.lock().unwrap().insert(address);
+            }
+            SwarmEvent::Behaviour(OutEvent::Mdns(
+                mdns::Event::Discovered(list)
+            )) => {
+                println!("Discovered peers: {:?}", list);
+                for (peer, addr) in list {
+                    state.swarm
+                        .behaviour_mut()
+                        .gossipsub
+                        .add_explicit_peer(&peer);
+                    state.swarm.behaviour_mut().kad.add_address(&peer, addr);
+                }
+            }
+            SwarmEvent::Behaviour(OutEvent::Mdns(mdns::Event::Expired(
+                list
+            ))) => {
+                for (peer, _) in list {
+                    if !state.swarm.behaviour_mut().mdns.has_node(&peer) {
+                        state.swarm
+                            .behaviour_mut()
+                            .gossipsub
+                            .remove_explicit_peer(&peer);
+                    }
+                }
+            },
+            SwarmEvent::Behaviour(OutEvent::Autonat(autonat::Event::StatusChanged {
+                new, ..
+            })) => {
+                let (new_state, should_serve_relay) = match new {
+                    autonat::NatStatus::Public(_) => (REACHABILITY_PUBLIC, true),
+                    autonat::NatStatus::Private => (REACHABILITY_PRIVATE, false),
+                    autonat::NatStatus::Unknown => (REACHABILITY_UNKNOWN, false),
+                };
+                reachability.store(new_state, std::sync::atomic::Ordering::SeqCst);
+                // Only publicly-reachable nodes serve the Circuit Relay v2 protocol
+                // for others; unreachable nodes rely on a configured relay instead.
+                if should_serve_relay && state.swarm.behaviour().relay_server.as_ref().is_none() {
+                    state.swarm.behaviour_mut().relay_server = Toggle::from(Some(relay::Behaviour::new(
+                        *state.swarm.local_peer_id(),
+                        relay::Config::default(),
+                    )));
+                } else if !should_serve_relay {
+                    state.swarm.behaviour_mut().relay_server = Toggle::from(None);
+                }
+            },
+            SwarmEvent::Behaviour(OutEvent::Dcutr(dcutr::Event { remote_peer_id, result })) => {
+                match result {
+                    Ok(_) => println!("DCUtR hole punch to {:?} succeeded", remote_peer_id),
+                    Err(e) => println!("DCUtR hole punch to {:?} failed: {e:?}", remote_peer_id),
+                }
+            },
+            SwarmEvent::Behaviour(OutEvent::Identify(identify::Event::Received { peer_id, info, .. })) => {
+                // `observed_addr` is the address the remote peer sees us dialing from, i.e. our
+                // own externally-visible address as reported by someone else on the network.
+                external_addrs.lock().unwrap().insert(info.observed_addr);
+                for addr in info.listen_addrs {
+                    state.swarm.behaviour_mut().kad.add_address(&peer_id, addr);
+                }
+            },
+            SwarmEvent::Behaviour(OutEvent::Kad(kad::Event::RoutingUpdated { peer, addresses, .. })) => {
+                // A peer just entered the DHT routing table, whether via a bootstrap query or a
+                // lookup result; fold it into the gossip mesh the same way mDNS-discovered peers
+                // are, so the ROS graph can span routed networks.
+                state.swarm.behaviour_mut().gossipsub.add_explicit_peer(&peer);
+                // Remember the peer's address so it can be persisted to the peerstore file and
+                // reconnected to on a future warm restart instead of waiting on rediscovery.
+                if let Some(address) = addresses.iter().next() {
+                    let combined = address.clone().with(libp2p::multiaddr::Protocol::P2p(peer));
+                    known_peers.lock().unwrap().insert(combined);
+                }
+            },
+            SwarmEvent::Behaviour(OutEvent::RequestResponse(request_response::Event::Message {
+                message, ..
+            })) => match message {
+                request_response::Message::Request { request, channel, .. } => {
+                    match decode_service_message(&request) {
+                        Some((service_name, request_payload)) => {
+                            if let Some((obj, callback)) = state.service_callback.get(service_name) {
+                                let mut resp_buf = vec![0u8; SERVICE_RESPONSE_SCRATCH_CAPACITY];
+                                let mut resp_len: usize = 0;
+                                let fit = unsafe {
+                                    callback(
+                                        obj,
+                                        request_payload.as_ptr(),
+                                        request_payload.len(),
+                                        resp_buf.as_mut_ptr(),
+                                        resp_buf.len(),
+                                        &mut resp_len,
+                                    )
+                                };
+                                if fit {
+                                    resp_buf.truncate(resp_len);
+                                    if let Err(e) = state.swarm.behaviour_mut().request_response.send_response(channel, resp_buf) {
+                                        println!("Failed to send service response: {e:?}");
+                                    }
+                                } else {
+                                    println!("Service response for {service_name} did not fit the scratch buffer");
+                                }
+                            } else {
+                                println!("No service registered for {service_name}");
+                            }
+                        }
+                        None => println!("Received malformed service request"),
+                    }
+                }
+                request_response::Message::Response { request_id, response } => {
+                    if let Some((obj, callback)) = state.pending_requests.remove(&request_id) {
+                        let mut response = response;
+                        response.shrink_to_fit();
+                        let ptr = response.as_mut_ptr();
+                        let len = response.len();
+                        std::mem::forget(response);
+                        unsafe {
+                            callback(&obj, ptr, len);
+                        }
+                    }
+                }
+            },
+            SwarmEvent::Behaviour(OutEvent::RequestResponse(request_response::Event::OutboundFailure {
+                request_id, error, ..
+            })) => {
+                state.pending_requests.remove(&request_id);
+                println!("Outbound service request failed: {error:?}");
+            },
+            SwarmEvent::Behaviour(OutEvent::RequestResponse(request_response::Event::InboundFailure {
+                error, ..
+            })) => {
+                println!("Inbound service request failed: {error:?}");
+            },
+            SwarmEvent::Behaviour(OutEvent::History(request_response::Event::Message {
+                message, ..
+            })) => match message {
+                request_response::Message::Request { request: topic, channel, .. } => {
+                    // An empty reply covers both "no publisher here has this topic" and "this
+                    // topic's publisher is VOLATILE", since either way the requester should fall
+                    // back to subscriber-side-only, gossipsub-delivered samples.
+                    let samples = publisher_history
+                        .lock()
+                        .unwrap()
+                        .get(&topic)
+                        .map(|history| history.iter().cloned().collect())
+                        .unwrap_or_default();
+                    if let Err(e) = state.swarm.behaviour_mut().history.send_response(channel, samples) {
+                        println!("Failed to send history response for {topic}: {e:?}");
+                    }
+                }
+                request_response::Message::Response { request_id, response } => {
+                    if let Some((incoming_queue, topic)) = state.pending_history_requests.remove(&request_id) {
+                        for sample in response {
+                            // Pushed through the same queue live gossipsub traffic uses, so a
+                            // late-joining subscriber sees history first, then live messages, in
+                            // one FIFO order. Not deduplicated against a live copy of the same
+                            // sample arriving via gossipsub in the same race window; doing so
+                            // would need a per-sample identifier this crate has no source for.
+                            incoming_queue
+                                .push((topic.clone(), sample, MessageOrigin::Remote))
+                                .await;
+                        }
+                    }
+                }
+            },
+            SwarmEvent::Behaviour(OutEvent::History(request_response::Event::OutboundFailure {
+                request_id, error, ..
+            })) => {
+                state.pending_history_requests.remove(&request_id);
+                println!("TRANSIENT_LOCAL history query failed: {error:?}");
+            },
+            SwarmEvent::Behaviour(OutEvent::History(request_response::Event::InboundFailure {
+                error, ..
+            })) => {
+                println!("Inbound history query failed: {error:?}");
+            },
+            _ => {
+                // TODO(esteve): use some sort of debug log
+                // println!("UNKNOWN EVENT");
+            }
+        },
+    }
+    true
+}
+
 /// This module contains the implementation of a custom node in the Libp2p network.
 /// The `Libp2pCustomNode` struct represents a custom node and provides methods for creating and interacting with the node.
 /// The node uses the `RosNetworkBehaviour` struct as its network behavior, which combines the `gossipsub` and `mdns` behaviors.
@@ -75,26 +721,110 @@ impl From<gossipsub::Event> for OutEvent {
 pub struct Libp2pCustomNode {
     thread_handle: Option<task::JoinHandle<()>>,
     stop_notify: Arc<Notify>,
-    outgoing_queue: Arc<deadqueue::unlimited::Queue<(gossipsub::IdentTopic, Vec<u8>)>>,
-    new_subscribers_queue: Arc<deadqueue::unlimited::Queue<(
+    outgoing_queue: Arc<BoundedQueue<(gossipsub::IdentTopic, Vec<u8>)>>,
+    new_subscribers_queue: Arc<BoundedQueue<(
         gossipsub::IdentTopic,
         CustomSubscriptionHandle,
-        unsafe extern "C" fn(&CustomSubscriptionHandle, *mut u8, len: usize),)
-    >>,
+        SubscriptionMessageCallback,
+        IncomingQueue,
+    )>>,
+    /// Capacity new queues are created with; maps onto RMW's QoS `depth` setting.
+    queue_capacity: usize,
+    /// Mirrors the event loop's `subscription_callback` map so `publish_message` can deliver a
+    /// message straight to a same-node subscriber without waiting a full round trip through
+    /// gossipsub, which never echoes a message back to its own publisher.
+    local_subscribers: Arc<SyncMutex<HashMap<String, SubscriptionCallback>>>,
     reactor: Runtime,
+    security_protocol: SecurityProtocol,
+    relay_addrs_queue: Arc<deadqueue::unlimited::Queue<Multiaddr>>,
+    reachability: Arc<std::sync::atomic::AtomicU8>,
+    bootstrap_peers_queue: Arc<deadqueue::unlimited::Queue<Multiaddr>>,
+    new_services_queue: Arc<deadqueue::unlimited::Queue<(String, CustomSubscriptionHandle, ServiceCallback)>>,
+    outgoing_requests_queue: Arc<deadqueue::unlimited::Queue<(
+        PeerId,
+        String,
+        Vec<u8>,
+        CustomSubscriptionHandle,
+        ClientCallback,
+    )>>,
+    /// Endianness used to encode the CDR encapsulation header on publish, so messages produced
+    /// here are wire-compatible with a real DDS/`rmw_fastrtps` peer.
+    endianness: crate::cdr_codec::Endianness,
+    driver_mode: DriverMode,
+    /// Populated only in `DriverMode::External`, where nothing else polls the swarm for us.
+    event_loop_state: Option<Arc<Mutex<EventLoopState>>>,
+    /// Peers this node has learned about via Kademlia's routing table, kept up to date by the
+    /// event loop so they can be written to `peerstore_path` on drop and reloaded on the next
+    /// `new_with_peerstore` call.
+    known_peers: Arc<SyncMutex<HashSet<Multiaddr>>>,
+    /// Where `known_peers` is persisted on drop. `None` for a node created with `new` or
+    /// `new_with_config`, which starts and ends each run with no saved peer history.
+    peerstore_path: Option<PathBuf>,
+    /// Addresses this node is listening on, as reported by `SwarmEvent::NewListenAddr`. Source
+    /// data for `rs_libp2p_custom_node_get_listen_endpoint`.
+    listen_addrs: Arc<SyncMutex<HashSet<Multiaddr>>>,
+    /// Addresses at which peers have observed this node, as reported in `identify::Event`'s
+    /// `info.observed_addr`. Source data for `rs_libp2p_custom_node_get_external_endpoint`.
+    external_addrs: Arc<SyncMutex<HashSet<Multiaddr>>>,
+    /// A new `TRANSIENT_LOCAL` subscription's queued request for its topic's buffered history,
+    /// consumed by the event loop which queries the topic's mesh peers over
+    /// `/rmw-libp2p/history/1.0.0`.
+    history_queries_queue: Arc<deadqueue::unlimited::Queue<(gossipsub::IdentTopic, IncomingQueue)>>,
+    /// Each `TRANSIENT_LOCAL` publisher's last `history_depth` samples, oldest first, keyed by
+    /// topic hash, answered out to querying subscribers by the event loop.
+    publisher_history: Arc<SyncMutex<HashMap<String, VecDeque<Vec<u8>>>>>,
 }
 
+const REACHABILITY_UNKNOWN: u8 = 0;
+const REACHABILITY_PUBLIC: u8 = 1;
+const REACHABILITY_PRIVATE: u8 = 2;
+
+/// Default capacity for a node's bounded queues, used unless a caller opts into
+/// `rs_libp2p_custom_node_new_with_config`.
+const DEFAULT_QUEUE_CAPACITY: usize = 1024;
+
 /// Creates a new instance of the `Libp2pCustomNode`.
 /// This method initializes the necessary components for the node, including the network behavior, transport, and swarm.
 /// It also starts the node's thread and listens on a random TCP port.
 /// Returns the created `Libp2pCustomNode` instance.
 impl Libp2pCustomNode {
+    /// Builds the base TCP transport upgraded with the security protocol selected by the
+    /// `security-noise` (default) / `security-tls` Cargo features.
+    ///
+    /// Only one of the two features should be enabled at a time; dropping the unused crypto
+    /// backend out of the build is the whole point of making this pluggable, e.g. for
+    /// size-constrained targets.
+    #[cfg(not(feature = "security-tls"))]
+    fn build_transport(
+        keypair: &identity::Keypair,
+    ) -> libp2p::core::transport::Boxed<(PeerId, libp2p::core::muxing::StreamMuxerBox)> {
+        libp2p::tokio_development_transport(keypair.clone()).unwrap()
+    }
+
+    #[cfg(feature = "security-tls")]
+    fn build_transport(
+        keypair: &identity::Keypair,
+    ) -> libp2p::core::transport::Boxed<(PeerId, libp2p::core::muxing::StreamMuxerBox)> {
+        use libp2p::core::{muxing::StreamMuxerBox, transport::Transport, upgrade};
+
+        libp2p::tcp::tokio::Transport::new(libp2p::tcp::Config::default())
+            .upgrade(upgrade::Version::V1)
+            .authenticate(libp2p::tls::Config::new(keypair).expect("Valid TLS config"))
+            .multiplex(libp2p::yamux::Config::default())
+            .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)))
+            .boxed()
+    }
+
     fn create_swarm() -> libp2p::Swarm<RosNetworkBehaviour> {
         let keypair = identity::Keypair::generate_ed25519();
 
         let peer_id = PeerId::from(keypair.public());
 
-        let transport = libp2p::tokio_development_transport(keypair.clone()).unwrap();
+        let (relay_transport, relay_client) = relay::client::new(peer_id);
+        let transport =
+            libp2p::core::transport::OrTransport::new(relay_transport, Self::build_transport(&keypair))
+                .map(|either, _| either.into_inner())
+                .boxed();
 
         let message_id_fn = |message: &gossipsub::Message| {
             let mut s = DefaultHasher::new();
@@ -104,23 +834,65 @@ impl Libp2pCustomNode {
 
         let gossipsub_config = gossipsub::ConfigBuilder::default()
             .heartbeat_interval(Duration::from_secs(10))
-            .validation_mode(gossipsub::ValidationMode::Strict)
+            .validation_mode(gossipsub::ValidationMode::Permissive)
             .message_id_fn(message_id_fn)
-            // same content will be propagated.
+            // Hold each message back from the mesh until the application-level subscription
+            // callback reports a verdict via `report_message_validation_result`, instead of
+            // gossipsub forwarding it immediately on arrival.
+            .validate_messages()
             .build()
             .expect("Valid config");
 
         let gossipsub: gossipsub::Behaviour = gossipsub::Behaviour::new(
-            gossipsub::MessageAuthenticity::Signed(keypair),
+            gossipsub::MessageAuthenticity::Signed(keypair.clone()),
             gossipsub_config,
         )
         .expect("Correct configuration");
 
         let mdns = mdns::tokio::Behaviour::new(mdns::Config::default(), peer_id).unwrap();
 
+        let kad = kad::Behaviour::new(peer_id, kad::store::MemoryStore::new(peer_id));
+
+        let identify = identify::Behaviour::new(identify::Config::new(
+            "/rmw-libp2p/0.1.0".to_string(),
+            keypair.public(),
+        ));
+
+        let autonat = autonat::Behaviour::new(peer_id, autonat::Config::default());
+
+        let dcutr = dcutr::Behaviour::new(peer_id);
+
+        // No relay is known to reach via yet and reachability is unknown, so start with the
+        // relay server disabled; it is switched on once autonat confirms public reachability.
+        let relay_server = Toggle::from(None);
+
+        let request_response = request_response::cbor::Behaviour::new(
+            [(
+                StreamProtocol::new("/rmw-libp2p/request-response/0.1.0"),
+                request_response::ProtocolSupport::Full,
+            )],
+            request_response::Config::default(),
+        );
+
+        let history = request_response::cbor::Behaviour::new(
+            [(
+                StreamProtocol::new("/rmw-libp2p/history/1.0.0"),
+                request_response::ProtocolSupport::Full,
+            )],
+            request_response::Config::default(),
+        );
+
         let behaviour = RosNetworkBehaviour {
             gossipsub: gossipsub,
             mdns: mdns,
+            kad: kad,
+            identify: identify,
+            autonat: autonat,
+            dcutr: dcutr,
+            relay_client: relay_client,
+            relay_server: relay_server,
+            request_response: request_response,
+            history: history,
         };
 
         libp2p::Swarm::with_tokio_executor(transport, behaviour, peer_id)
@@ -129,7 +901,9 @@ impl Libp2pCustomNode {
     /// Creates a new instance of the struct.
     ///
     /// This function initializes a new runtime, creates a new swarm, and sets up various queues and callbacks for handling network events.
-    /// It also spawns a new Tokio task that runs an event loop for handling these events.
+    /// In `DriverMode::Managed` it also spawns a new Tokio task that loops over the event loop
+    /// for handling these events; in `DriverMode::External` nothing drives the swarm until the
+    /// host starts calling [`Libp2pCustomNode::step`].
     ///
     /// # Returns
     ///
@@ -138,15 +912,49 @@ impl Libp2pCustomNode {
     /// # Panics
     ///
     /// This function will panic if it fails to create a new runtime or if it fails to make the swarm listen on the specified address.
-    fn new() -> Self {
+    fn new(driver_mode: DriverMode, queue_capacity: usize) -> Self {
+        Self::new_with_peerstore_state(driver_mode, queue_capacity, HashSet::new(), None)
+    }
+
+    /// Creates a new node whose peer set is persisted to `peerstore_path` on drop and reloaded
+    /// from it here, so a restarted node reconnects to previously-known peers instead of relying
+    /// solely on mDNS or a fresh DHT bootstrap to rediscover them.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if it fails to create a new runtime or if it fails to make the
+    /// swarm listen on the specified address.
+    fn new_with_peerstore(driver_mode: DriverMode, queue_capacity: usize, peerstore_path: PathBuf) -> Self {
+        let known_peers = load_known_peers(&peerstore_path);
+        let node = Self::new_with_peerstore_state(
+            driver_mode,
+            queue_capacity,
+            known_peers.clone(),
+            Some(peerstore_path),
+        );
+        for addr in known_peers {
+            node.add_bootstrap_peer(addr);
+        }
+        node
+    }
+
+    fn new_with_peerstore_state(
+        driver_mode: DriverMode,
+        queue_capacity: usize,
+        known_peers: HashSet<Multiaddr>,
+        peerstore_path: Option<PathBuf>,
+    ) -> Self {
+        let known_peers = Arc::new(SyncMutex::new(known_peers));
+        let listen_addrs = Arc::new(SyncMutex::new(HashSet::new()));
+        let external_addrs = Arc::new(SyncMutex::new(HashSet::new()));
         let reactor = Runtime::new().unwrap();
         let _guard = reactor.enter();
 
         let stop_notify = Arc::new(Notify::new());
-        let outgoing_queue = Arc::new(deadqueue::unlimited::Queue::<(
+        let outgoing_queue = Arc::new(BoundedQueue::<(
             gossipsub::IdentTopic,
             Vec<u8>,
-        )>::new());
+        )>::new(queue_capacity, OverflowPolicy::Block));
 
         let mut swarm = Self::create_swarm();
 
@@ -154,116 +962,224 @@ impl Libp2pCustomNode {
             .listen_on("/ip4/0.0.0.0/tcp/0".parse().unwrap())
             .unwrap();
 
-        let stop_notify_clone = Arc::clone(&stop_notify);
-        let outgoing_queue_clone = Arc::clone(&outgoing_queue);
-        let incoming_queue = Queue::<(
+        let new_subscribers_queue = Arc::new(BoundedQueue::<(gossipsub::IdentTopic,
+            CustomSubscriptionHandle,
+            SubscriptionMessageCallback,
+            IncomingQueue,
+        )>::new(queue_capacity, OverflowPolicy::Block));
+        let relay_addrs_queue = Arc::new(deadqueue::unlimited::Queue::<Multiaddr>::new());
+        let reachability = Arc::new(std::sync::atomic::AtomicU8::new(REACHABILITY_UNKNOWN));
+        let bootstrap_peers_queue = Arc::new(deadqueue::unlimited::Queue::<Multiaddr>::new());
+        let local_subscribers = Arc::new(SyncMutex::new(HashMap::new()));
+        let new_services_queue = Arc::new(deadqueue::unlimited::Queue::<(
+            String,
+            CustomSubscriptionHandle,
+            ServiceCallback,
+        )>::new());
+        let outgoing_requests_queue = Arc::new(deadqueue::unlimited::Queue::<(
+            PeerId,
             String,
-            unsafe extern "C" fn(CustomSubscriptionHandle, *mut u8, len: usize),
             Vec<u8>,
-        )>::new();
-        let new_subscribers_queue = Arc::new(deadqueue::unlimited::Queue::<(gossipsub::IdentTopic,
             CustomSubscriptionHandle,
-            unsafe extern "C" fn(&CustomSubscriptionHandle, *mut u8, len: usize),
+            ClientCallback,
         )>::new());
-        let new_subscribers_queue_clone = Arc::clone(&new_subscribers_queue);
-        let thread_handle = tokio::spawn(async move {
-            let mut subscription_callback = HashMap::<String, (CustomSubscriptionHandle, unsafe extern "C" fn(&CustomSubscriptionHandle, *mut u8, len: usize))>::new();
-            loop {
-                select! {
-                    // use a Notify that will be triggered to stop the swarm
-                    // select! will wait on any future
-                    _ = stop_notify_clone.notified() => {
-                        println!("Exit loop");
-                        break;
-                    },
-
-                    (topic, obj, callback) = new_subscribers_queue_clone.pop() => {
-                        // println!("Subscribing to topic: {}", topic);
-                        swarm.behaviour_mut().gossipsub.subscribe(&topic).unwrap();
-                        subscription_callback.insert(topic.hash().into_string(), (obj, callback));
-                    },
-
-                    // pop messages from the queue and publish them to the network
-                    (topic, buffer) = outgoing_queue_clone.pop() => {
-                        // TODO(esteve): use some sort of debug log
-                        // println!("Publishing message on topic {} : {:?}", topic, buffer);
-                        if let Err(e) = swarm.behaviour_mut().gossipsub.publish(topic.clone(), buffer.clone()) {
-                            println!("Publish error: {e:?}");
-                        }
-                    },
-
-                    event = swarm.select_next_some() => match event {
-                        SwarmEvent::Behaviour(OutEvent::Gossipsub(gossipsub::Event::Message {
-                            propagation_source: peer_id,
-                            message_id: id,
-                            message,
-                        })) => {
-                            // TODO(esteve): use some sort of debug log
-                            // println!(
-                            //     "Got message: {:?} with id: {} from peer: {:?} topic: {}",
-                            //     message.data,
-                            //     id,
-                            //     peer_id,
-                            //     message.topic.as_str(),
-                            // );
-                            let mut vec = message.data;
-                            vec.shrink_to_fit();
-                            let ptr: *mut u8 = vec.as_mut_ptr();
-                            let len: usize = vec.len();
-                            std::mem::forget(vec);
-                            let (obj, callback) = subscription_callback.get(&message.topic.into_string()).unwrap();
-                            unsafe {
-                                callback(&obj, ptr, len);
-                            }
-                        }
-                        SwarmEvent::NewListenAddr { address, .. } => {
-                            println!("Listening on {:?}", address);
-                        }
-                        SwarmEvent::Behaviour(OutEvent::Mdns(
-                            mdns::Event::Discovered(list)
-                        )) => {
-                            println!("Discovered peers: {:?}", list);
-                            for (peer, _) in list {
-                                swarm
-                                    .behaviour_mut()
-                                    .gossipsub
-                                    .add_explicit_peer(&peer);
-                            }
-                        }
-                        SwarmEvent::Behaviour(OutEvent::Mdns(mdns::Event::Expired(
-                            list
-                        ))) => {
-                            for (peer, _) in list {
-                                if !swarm.behaviour_mut().mdns.has_node(&peer) {
-                                    swarm
-                                        .behaviour_mut()
-                                        .gossipsub
-                                        .remove_explicit_peer(&peer);
-                                }
-                            }
-                        },
-                        _ => {
-                            // TODO(esteve): use some sort of debug log
-                            // println!("UNKNOWN EVENT");
-                        }
-                    },
-                }
+        let history_queries_queue = Arc::new(deadqueue::unlimited::Queue::<(
+            gossipsub::IdentTopic,
+            IncomingQueue,
+        )>::new());
+        let publisher_history = Arc::new(SyncMutex::new(HashMap::new()));
+
+        let state = EventLoopState {
+            swarm,
+            subscription_callback: HashMap::new(),
+            service_callback: HashMap::new(),
+            pending_requests: HashMap::new(),
+            pending_history_requests: HashMap::new(),
+        };
+
+        let (thread_handle, event_loop_state) = match driver_mode {
+            DriverMode::Managed => {
+                let mut state = state;
+                let stop_notify_clone = Arc::clone(&stop_notify);
+                let outgoing_queue_clone = Arc::clone(&outgoing_queue);
+                let new_subscribers_queue_clone = Arc::clone(&new_subscribers_queue);
+                let relay_addrs_queue_clone = Arc::clone(&relay_addrs_queue);
+                let reachability_clone = Arc::clone(&reachability);
+                let bootstrap_peers_queue_clone = Arc::clone(&bootstrap_peers_queue);
+                let new_services_queue_clone = Arc::clone(&new_services_queue);
+                let outgoing_requests_queue_clone = Arc::clone(&outgoing_requests_queue);
+                let known_peers_clone = Arc::clone(&known_peers);
+                let listen_addrs_clone = Arc::clone(&listen_addrs);
+                let external_addrs_clone = Arc::clone(&external_addrs);
+                let history_queries_queue_clone = Arc::clone(&history_queries_queue);
+                let publisher_history_clone = Arc::clone(&publisher_history);
+                let thread_handle = tokio::spawn(async move {
+                    while drive_swarm_once(
+                        &mut state,
+                        &stop_notify_clone,
+                        &outgoing_queue_clone,
+                        &new_subscribers_queue_clone,
+                        &relay_addrs_queue_clone,
+                        &reachability_clone,
+                        &bootstrap_peers_queue_clone,
+                        &new_services_queue_clone,
+                        &outgoing_requests_queue_clone,
+                        &known_peers_clone,
+                        &listen_addrs_clone,
+                        &external_addrs_clone,
+                        &history_queries_queue_clone,
+                        &publisher_history_clone,
+                    )
+                    .await
+                    {}
+                });
+                (Some(thread_handle), None)
             }
-        });
+            DriverMode::External => (None, Some(Arc::new(Mutex::new(state)))),
+        };
 
         Self {
-            thread_handle: Some(thread_handle),
+            thread_handle: thread_handle,
             stop_notify: stop_notify,
             outgoing_queue: outgoing_queue,
             new_subscribers_queue: new_subscribers_queue,
+            queue_capacity: queue_capacity,
+            local_subscribers: local_subscribers,
             reactor: reactor,
+            security_protocol: SecurityProtocol::NEGOTIATED,
+            relay_addrs_queue: relay_addrs_queue,
+            reachability: reachability,
+            bootstrap_peers_queue: bootstrap_peers_queue,
+            new_services_queue: new_services_queue,
+            outgoing_requests_queue: outgoing_requests_queue,
+            endianness: crate::cdr_codec::Endianness::Little,
+            driver_mode: driver_mode,
+            event_loop_state: event_loop_state,
+            known_peers: known_peers,
+            peerstore_path: peerstore_path,
+            listen_addrs: listen_addrs,
+            external_addrs: external_addrs,
+            history_queries_queue: history_queries_queue,
+            publisher_history: publisher_history,
+        }
+    }
+
+    /// Drives the swarm through a single step, for hosts created with `DriverMode::External`
+    /// that own their own executor and poll this node from their own loop instead of letting it
+    /// spawn a private tokio task.
+    ///
+    /// Returns `true` if the node is still running, `false` once it has been asked to stop (at
+    /// which point further calls are a no-op).
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on a node created with `DriverMode::Managed`, which already drives
+    /// itself.
+    pub(crate) fn step(&self) -> bool {
+        let state = self
+            .event_loop_state
+            .as_ref()
+            .expect("step() requires a node created with DriverMode::External");
+        self.reactor.block_on(async {
+            let mut state = state.lock().await;
+            drive_swarm_once(
+                &mut state,
+                &self.stop_notify,
+                &self.outgoing_queue,
+                &self.new_subscribers_queue,
+                &self.relay_addrs_queue,
+                &self.reachability,
+                &self.bootstrap_peers_queue,
+                &self.new_services_queue,
+                &self.outgoing_requests_queue,
+                &self.known_peers,
+                &self.listen_addrs,
+                &self.external_addrs,
+                &self.history_queries_queue,
+                &self.publisher_history,
+            )
+            .await
+        })
+    }
+
+    /// Returns the driver mode this node was created with.
+    pub(crate) fn driver_mode(&self) -> DriverMode {
+        self.driver_mode
+    }
+
+    /// Returns the transport-security protocol this node's swarm was built with.
+    pub(crate) fn security_protocol(&self) -> SecurityProtocol {
+        self.security_protocol
+    }
+
+    /// Registers a relay multiaddr, making a Circuit Relay v2 reservation on it so this node
+    /// can be reached with a `/p2p-circuit` address while it believes itself unreachable.
+    pub(crate) fn add_relay(&self, relay_addr: Multiaddr) {
+        self.relay_addrs_queue.push(relay_addr);
+    }
+
+    /// Registers a Kademlia bootstrap peer (e.g. `/ip4/.../tcp/.../p2p/<peer-id>`) and kicks off
+    /// a DHT bootstrap query, so this node can find peers beyond its local subnet.
+    pub(crate) fn add_bootstrap_peer(&self, bootstrap_addr: Multiaddr) {
+        self.bootstrap_peers_queue.push(bootstrap_addr);
+    }
+
+    /// Returns the node's current view of its own reachability, as reported by `autonat`.
+    pub(crate) fn reachability(&self) -> Reachability {
+        match self.reachability.load(std::sync::atomic::Ordering::SeqCst) {
+            REACHABILITY_PUBLIC => Reachability::Public,
+            REACHABILITY_PRIVATE => Reachability::Private,
+            _ => Reachability::Unknown,
+        }
+    }
+
+    /// Records a sample a `Durability::TransientLocal` publisher just sent, for the event loop
+    /// to answer a late-joining subscriber's history query with. Evicts
+    /// the oldest sample once `history_depth` is reached.
+    pub(crate) fn record_history_sample(&self, topic_hash: String, history_depth: usize, sample: Vec<u8>) {
+        let mut publisher_history = self.publisher_history.lock().unwrap();
+        let history = publisher_history.entry(topic_hash).or_default();
+        if history.len() >= history_depth.max(1) {
+            history.pop_front();
         }
+        history.push_back(sample);
+    }
+
+    /// Queues a `TRANSIENT_LOCAL` subscription's request for `topic`'s buffered history. The
+    /// event loop sends it to the topic's current mesh peers and pushes whatever a publisher
+    /// replies with into `incoming_queue`, the same queue live gossipsub messages are delivered
+    /// through.
+    pub(crate) fn query_history(&self, topic: gossipsub::IdentTopic, incoming_queue: IncomingQueue) {
+        self.history_queries_queue.push((topic, incoming_queue));
+    }
+
+    /// Decomposes this node's current listen addresses into network-flow endpoints, for
+    /// `rs_libp2p_custom_node_get_listen_endpoint`.
+    pub(crate) fn listen_endpoints(&self) -> Vec<Endpoint> {
+        self.listen_addrs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(network_flow::decompose)
+            .collect()
+    }
+
+    /// Decomposes the addresses at which peers have observed this node (via `identify`) into
+    /// network-flow endpoints, for `rs_libp2p_custom_node_get_external_endpoint`.
+    pub(crate) fn external_endpoints(&self) -> Vec<Endpoint> {
+        self.external_addrs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(network_flow::decompose)
+            .collect()
     }
 
     /// Publishes a message to a specific topic.
     ///
-    /// This function serializes the current system time and a provided buffer into a new buffer,
-    /// then pushes the new buffer and the topic into the outgoing queue.
+    /// This function prefixes the message with a CDR encapsulation header (representation id +
+    /// options) followed by the current system time, encoded with this node's configured
+    /// endianness, then pushes the resulting buffer and the topic into the outgoing queue.
     ///
     /// # Arguments
     ///
@@ -274,46 +1190,196 @@ impl Libp2pCustomNode {
     ///
     /// This function will panic if the system time is before the UNIX_EPOCH.
     pub(crate) fn publish_message(&self, topic: gossipsub::IdentTopic, buffer: Vec<u8>) -> () {
-        let mut out_buffer = Vec::<u8>::new();
+        self.publish_message_local_override(topic, buffer, None)
+    }
 
+    /// Publishes a message to a specific topic, same as `publish_message`, but hands the local
+    /// same-process subscriber (if any) `local_buffer` instead of `buffer`.
+    ///
+    /// Used by [`Libp2pCustomPublisher::publish_loaned_message`](crate::publisher::Libp2pCustomPublisher::publish_loaned_message)
+    /// so the real serialized message (`buffer`) is always what goes out over gossipsub and into
+    /// `TRANSIENT_LOCAL` history, while a same-process subscriber instead gets a
+    /// [`LoanHandle`](crate::loaned_message::LoanHandle) it can map zero-copy — remote peers and
+    /// late-joining history replay never see the loan-handle bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic` - The topic to publish the message to.
+    /// * `buffer` - The real serialized message, published to the network and history.
+    /// * `local_buffer` - What the local subscriber (if any) is notified with instead of `buffer`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the system time is before the UNIX_EPOCH.
+    pub(crate) fn publish_message_with_local_override(
+        &self,
+        topic: gossipsub::IdentTopic,
+        buffer: Vec<u8>,
+        local_buffer: Vec<u8>,
+    ) -> () {
+        self.publish_message_local_override(topic, buffer, Some(local_buffer))
+    }
+
+    /// Shared implementation backing `publish_message` and
+    /// `publish_message_with_local_override`; see those for behavior.
+    fn publish_message_local_override(
+        &self,
+        topic: gossipsub::IdentTopic,
+        buffer: Vec<u8>,
+        local_override: Option<Vec<u8>>,
+    ) -> () {
         let start = SystemTime::now();
         let since_the_epoch = start
             .duration_since(UNIX_EPOCH)
             .expect("Time went backwards");
 
         let secs = since_the_epoch.as_secs();
-        cdr::serialize_into::<_, _, _, cdr::CdrBe>(&mut out_buffer, &secs, cdr::Infinite).unwrap();
-
         let usecs = since_the_epoch.subsec_micros();
-        cdr::serialize_into::<_, _, _, cdr::CdrBe>(&mut out_buffer, &usecs, cdr::Infinite).unwrap();
 
-        out_buffer.extend(buffer);
-        self.outgoing_queue.push((topic, out_buffer));
+        let out_buffer = crate::cdr_codec::encode_message(self.endianness, secs, usecs, &buffer);
+        // Blocks the FFI caller when the outgoing queue is already at capacity, applying
+        // backpressure instead of letting a slow network path grow memory without bound.
+        self.reactor
+            .block_on(self.outgoing_queue.push((topic.clone(), out_buffer)));
+
+        // Gossipsub never echoes a message back to its own publisher, but ROS semantics expect
+        // a subscription on this node to observe messages this node itself publishes. Only
+        // self-notify when the topic is actually subscribed to, so publish-only topics don't
+        // pay for a lookup that never hits.
+        if let Some((obj, callback, incoming_queue)) = self
+            .local_subscribers
+            .lock()
+            .unwrap()
+            .get(&topic.hash().into_string())
+        {
+            let local_payload = local_override.unwrap_or(buffer);
+            self.reactor.block_on(
+                incoming_queue.push((topic.clone(), local_payload.clone(), MessageOrigin::Local)),
+            );
+            let mut vec = local_payload;
+            vec.shrink_to_fit();
+            let ptr: *mut u8 = vec.as_mut_ptr();
+            let len: usize = vec.len();
+            std::mem::forget(vec);
+            unsafe {
+                callback(obj, ptr, len);
+            }
+        }
+    }
+
+    /// Whether this process itself has a subscriber registered for `topic`, i.e. whether a
+    /// message published to it would be delivered locally by `publish_message` without ever
+    /// going out over gossipsub. Used by `Libp2pCustomPublisher::publish_loaned_message` to gate
+    /// the zero-copy loan path, which is scoped to exactly this: this crate has no discovery
+    /// signal for "a subscriber exists in a different process on the same host", so that case is
+    /// indistinguishable from a remote peer and falls back to a serialized copy. See
+    /// `loaned_message.rs`'s module doc comment.
+    pub(crate) fn has_local_subscriber(&self, topic: &gossipsub::IdentTopic) -> bool {
+        self.local_subscribers
+            .lock()
+            .unwrap()
+            .contains_key(&topic.hash().into_string())
     }
 
     /// Notifies about a new subscriber to a specific topic.
     ///
-    /// This function pushes the topic, a `CustomSubscriptionHandle`, and a callback function into the `new_subscribers_queue`.
+    /// This function pushes the topic, a `CustomSubscriptionHandle`, a callback function, and
+    /// the subscription's poll-based `incoming_queue` into the `new_subscribers_queue`.
     ///
     /// # Arguments
     ///
     /// * `topic` - The topic the new subscriber is interested in.
     /// * `obj` - A `CustomSubscriptionHandle` associated with the new subscriber.
     /// * `callback` - A callback function to be called when a new message is published to the topic.
+    /// * `incoming_queue` - The subscription's queue, fed alongside `callback` so poll-based
+    ///   consumers (`rs_libp2p_custom_subscription_take_message`/`_wait`) can drain messages
+    ///   without being invoked from inside the swarm task.
     ///
     /// # Safety
     ///
     /// This function is unsafe because it uses a raw pointer in the callback function.
     pub(crate) fn notify_new_subscriber(&self, topic: gossipsub::IdentTopic,
         obj: CustomSubscriptionHandle,
-        callback: unsafe extern "C" fn(&CustomSubscriptionHandle, *mut u8, len: usize),
+        callback: SubscriptionMessageCallback,
+        incoming_queue: IncomingQueue,
+    ) -> () {
+        self.local_subscribers
+            .lock()
+            .unwrap()
+            .insert(topic.hash().into_string(), (obj, callback, incoming_queue.clone()));
+        self.reactor
+            .block_on(self.new_subscribers_queue.push((topic, obj, callback, incoming_queue)));
+    }
+
+    /// The capacity new bounded queues are created with, so dependent components (e.g. a
+    /// subscription's incoming queue) can size themselves consistently with this node.
+    pub(crate) fn queue_capacity(&self) -> usize {
+        self.queue_capacity
+    }
+
+    /// Runs `fut` to completion on this node's tokio runtime. Lets FFI entry points that are
+    /// not themselves `async` (e.g. a subscription's `take_message`/`wait`) await a queue owned
+    /// by this node without each needing its own runtime.
+    pub(crate) fn block_on<F: std::future::Future>(&self, fut: F) -> F::Output {
+        self.reactor.block_on(fut)
+    }
+
+    /// The number of items currently buffered in the outgoing publish queue.
+    pub(crate) fn outgoing_queue_depth(&self) -> usize {
+        self.reactor.block_on(self.outgoing_queue.len())
+    }
+
+    /// The largest number of items the outgoing publish queue has held at once, for detecting
+    /// sustained lag against RMW's QoS `depth` setting.
+    pub(crate) fn outgoing_queue_high_water_mark(&self) -> usize {
+        self.outgoing_queue.high_water_mark()
+    }
+
+    /// Registers a ROS service/action server under `service_name`, so that requests targeting
+    /// it are dispatched to `callback`.
+    ///
+    /// # Safety
+    ///
+    /// This function is unsafe because it uses a raw pointer in the callback function.
+    pub(crate) fn register_service(
+        &self,
+        service_name: String,
+        obj: CustomSubscriptionHandle,
+        callback: ServiceCallback,
+    ) -> () {
+        self.new_services_queue.push((service_name, obj, callback));
+    }
+
+    /// Sends a ROS service/action request to `peer_id`, invoking `callback` once the
+    /// corresponding response arrives.
+    ///
+    /// # Safety
+    ///
+    /// This function is unsafe because it uses a raw pointer in the callback function.
+    pub(crate) fn send_request(
+        &self,
+        peer_id: PeerId,
+        service_name: String,
+        payload: Vec<u8>,
+        obj: CustomSubscriptionHandle,
+        callback: ClientCallback,
     ) -> () {
-        self.new_subscribers_queue.push((topic, obj, callback));
+        self.outgoing_requests_queue
+            .push((peer_id, service_name, payload, obj, callback));
+    }
+
+    /// Persists the node's current peer set (as learned from Kademlia's routing table) to
+    /// `path`, in the same format `new_with_peerstore` loads.
+    pub(crate) fn save_peers(&self, path: &Path) {
+        save_known_peers(path, &self.known_peers.lock().unwrap());
     }
 }
 
 impl Drop for Libp2pCustomNode {
     fn drop(&mut self) {
+        if let Some(path) = &self.peerstore_path {
+            save_known_peers(path, &self.known_peers.lock().unwrap());
+        }
         self.stop_notify.notify_waiters();
         self.reactor.block_on(async {
             if let Some(thread_handle) = self.thread_handle.take() {
@@ -336,7 +1402,125 @@ impl Drop for Libp2pCustomNode {
 /// A raw pointer to a `Libp2pCustomNode`.
 #[no_mangle]
 pub extern "C" fn rs_libp2p_custom_node_new() -> *mut Libp2pCustomNode {
-    Box::into_raw(Box::new(Libp2pCustomNode::new()))
+    Box::into_raw(Box::new(Libp2pCustomNode::new(
+        DriverMode::Managed,
+        DEFAULT_QUEUE_CAPACITY,
+    )))
+}
+
+/// Creates a new `Libp2pCustomNode` with a configurable bounded-queue capacity.
+///
+/// `queue_capacity` bounds the outgoing publish queue and the new-subscriber registration
+/// queue, so a slow consumer (or a gossipsub IWANT burst) can't grow this node's memory without
+/// bound; the FFI publisher blocks once the outgoing queue is full. This maps naturally onto
+/// RMW's QoS `depth` setting.
+///
+/// # Safety
+///
+/// This function is unsafe because it returns a raw pointer to a heap-allocated object. The caller is responsible for freeing this memory.
+///
+/// # Returns
+///
+/// A raw pointer to a `Libp2pCustomNode`.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_new_with_config(
+    queue_capacity: usize,
+) -> *mut Libp2pCustomNode {
+    Box::into_raw(Box::new(Libp2pCustomNode::new(
+        DriverMode::Managed,
+        queue_capacity,
+    )))
+}
+
+/// Creates a new `Libp2pCustomNode`, preloading its Kademlia routing table and gossipsub
+/// explicit-peer list from a peer set previously saved to `path_cstr`, either by
+/// `rs_libp2p_custom_node_save_peers` or by dropping an earlier node created with this function.
+///
+/// If no file exists yet at `path_cstr`, the node starts with an empty peer set, the same as
+/// `rs_libp2p_custom_node_new`. On drop, the node's current peer set (as learned from Kademlia)
+/// is written back to `path_cstr`, so repeated warm restarts accumulate previously-seen peers
+/// instead of re-discovering everything via mDNS or a DHT bootstrap query each time.
+///
+/// # Safety
+///
+/// This function is unsafe because it returns a raw pointer to a heap-allocated object and
+/// dereferences a raw pointer. The caller is responsible for freeing the returned node.
+///
+/// # Arguments
+///
+/// * `path_cstr` - A raw pointer to a C string naming the peerstore file.
+///
+/// # Returns
+///
+/// A raw pointer to a `Libp2pCustomNode`.
+///
+/// # Panics
+///
+/// This function will panic if `path_cstr` is null or does not point to a valid null-terminated
+/// UTF-8 string.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_new_with_peerstore(
+    path_cstr: *const c_char,
+) -> *mut Libp2pCustomNode {
+    let path_str = unsafe {
+        assert!(!path_cstr.is_null());
+        std::ffi::CStr::from_ptr(path_cstr)
+    };
+    let path = PathBuf::from(path_str.to_str().expect("Valid UTF-8 path"));
+    Box::into_raw(Box::new(Libp2pCustomNode::new_with_peerstore(
+        DriverMode::Managed,
+        DEFAULT_QUEUE_CAPACITY,
+        path,
+    )))
+}
+
+/// Creates a new `Libp2pCustomNode` whose swarm is not driven by an internal tokio task.
+///
+/// Use this when embedding this RMW inside a host that already owns an executor: the host
+/// must repeatedly call `rs_libp2p_custom_node_step` from its own loop (poll-and-dispatch) to
+/// make any progress at all, instead of relying on a private background thread.
+///
+/// # Safety
+///
+/// This function is unsafe because it returns a raw pointer to a heap-allocated object. The caller is responsible for freeing this memory.
+///
+/// # Returns
+///
+/// A raw pointer to a `Libp2pCustomNode`.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_new_externally_driven() -> *mut Libp2pCustomNode {
+    Box::into_raw(Box::new(Libp2pCustomNode::new(
+        DriverMode::External,
+        DEFAULT_QUEUE_CAPACITY,
+    )))
+}
+
+/// Drives the swarm of an externally-driven `Libp2pCustomNode` through a single step.
+///
+/// A node created with `rs_libp2p_custom_node_new` already drives itself; calling this on one
+/// of those panics.
+///
+/// # Safety
+///
+/// This function is unsafe because it dereferences a raw pointer.
+///
+/// # Arguments
+///
+/// * `ptr` - A raw pointer to a `Libp2pCustomNode` created with `rs_libp2p_custom_node_new_externally_driven`.
+///
+/// # Returns
+///
+/// `true` if the node is still running, `false` once it has stopped.
+///
+/// # Panics
+///
+/// This function will panic if `ptr` is null, or if the node was not created with
+/// `rs_libp2p_custom_node_new_externally_driven`.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_step(ptr: *mut Libp2pCustomNode) -> bool {
+    assert!(!ptr.is_null());
+    let node = unsafe { &*ptr };
+    node.step()
 }
 
 /// Frees a `Libp2pCustomNode` from memory.
@@ -357,3 +1541,355 @@ pub extern "C" fn rs_libp2p_custom_node_free(ptr: *mut Libp2pCustomNode) {
     }
     let _ = unsafe { Box::from_raw(ptr) };
 }
+
+/// Queries the transport-security protocol negotiated by a `Libp2pCustomNode`'s swarm.
+///
+/// This mirrors `rmw_get_serialization_format`: the result is a `'static` C string owned by
+/// the library, so the caller must not free it.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Panics
+///
+/// This function will panic if `ptr` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_get_security_protocol(
+    ptr: *mut Libp2pCustomNode,
+) -> *const c_char {
+    let libp2p_custom_node = unsafe {
+        assert!(!ptr.is_null());
+        &*ptr
+    };
+    match libp2p_custom_node.security_protocol() {
+        SecurityProtocol::Noise => c"noise".as_ptr(),
+        SecurityProtocol::Tls => c"tls".as_ptr(),
+    }
+}
+
+/// Registers a relay multiaddr (e.g. `/ip4/.../tcp/.../p2p/<relay-id>`) for NAT traversal.
+///
+/// The node makes a Circuit Relay v2 reservation on the relay so it can be dialed via
+/// `/…/p2p-circuit/p2p/<id>` while it is not publicly reachable.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Panics
+///
+/// This function will panic if `ptr` or `multiaddr_str_ptr` is null, or if `multiaddr_str_ptr`
+/// does not point to a valid null-terminated string containing a parseable multiaddr.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_add_relay(
+    ptr: *mut Libp2pCustomNode,
+    multiaddr_str_ptr: *const c_char,
+) {
+    let libp2p_custom_node = unsafe {
+        assert!(!ptr.is_null());
+        &*ptr
+    };
+    let multiaddr_str = unsafe {
+        assert!(!multiaddr_str_ptr.is_null());
+        std::ffi::CStr::from_ptr(multiaddr_str_ptr)
+    };
+    let multiaddr: Multiaddr = multiaddr_str
+        .to_str()
+        .expect("Valid UTF-8 multiaddr")
+        .parse()
+        .expect("Valid multiaddr");
+    libp2p_custom_node.add_relay(multiaddr);
+}
+
+/// Registers a Kademlia DHT bootstrap peer (e.g. `/ip4/.../tcp/.../p2p/<peer-id>`).
+///
+/// The node adds the address to its routing table and triggers a `bootstrap()` query, letting
+/// it discover peers beyond its local subnet instead of relying solely on mDNS.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Panics
+///
+/// This function will panic if `ptr` or `multiaddr_str_ptr` is null, or if `multiaddr_str_ptr`
+/// does not point to a valid null-terminated string containing a parseable multiaddr.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_add_bootstrap_peer(
+    ptr: *mut Libp2pCustomNode,
+    multiaddr_str_ptr: *const c_char,
+) {
+    let libp2p_custom_node = unsafe {
+        assert!(!ptr.is_null());
+        &*ptr
+    };
+    let multiaddr_str = unsafe {
+        assert!(!multiaddr_str_ptr.is_null());
+        std::ffi::CStr::from_ptr(multiaddr_str_ptr)
+    };
+    let multiaddr: Multiaddr = multiaddr_str
+        .to_str()
+        .expect("Valid UTF-8 multiaddr")
+        .parse()
+        .expect("Valid multiaddr");
+    libp2p_custom_node.add_bootstrap_peer(multiaddr);
+}
+
+/// Immediately persists the node's current peer set to `path_cstr`, in the format
+/// `rs_libp2p_custom_node_new_with_peerstore` loads.
+///
+/// A node created with `rs_libp2p_custom_node_new_with_peerstore` already does this on drop;
+/// this function exists for checkpointing the peer set outside of a clean shutdown, since the
+/// automatic save on drop never runs if the process is killed.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Panics
+///
+/// This function will panic if `ptr` or `path_cstr` is null, or if `path_cstr` does not point
+/// to a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_save_peers(
+    ptr: *mut Libp2pCustomNode,
+    path_cstr: *const c_char,
+) {
+    let libp2p_custom_node = unsafe {
+        assert!(!ptr.is_null());
+        &*ptr
+    };
+    let path_str = unsafe {
+        assert!(!path_cstr.is_null());
+        std::ffi::CStr::from_ptr(path_cstr)
+    };
+    let path = PathBuf::from(path_str.to_str().expect("Valid UTF-8 path"));
+    libp2p_custom_node.save_peers(&path);
+}
+
+/// Reports the node's current connectivity/reachability state as learned from `autonat`.
+///
+/// Returns 0 for unknown, 1 for publicly reachable, 2 for reachable only through a relay.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Panics
+///
+/// This function will panic if `ptr` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_get_reachability(ptr: *mut Libp2pCustomNode) -> u8 {
+    let libp2p_custom_node = unsafe {
+        assert!(!ptr.is_null());
+        &*ptr
+    };
+    match libp2p_custom_node.reachability() {
+        Reachability::Unknown => 0,
+        Reachability::Public => 1,
+        Reachability::Private => 2,
+    }
+}
+
+/// Reports how many listen addresses this node currently has, for sizing the buffer passed to
+/// `rs_libp2p_custom_node_get_listen_endpoint`.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Panics
+///
+/// This function will panic if `ptr` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_get_listen_endpoint_count(ptr: *mut Libp2pCustomNode) -> usize {
+    let libp2p_custom_node = unsafe {
+        assert!(!ptr.is_null());
+        &*ptr
+    };
+    libp2p_custom_node.listen_endpoints().len()
+}
+
+/// Decomposes the `index`-th listen address into the transport/internet-protocol/port/address
+/// shape `rmw_network_flow_endpoint_t` wants, so a caller can populate an
+/// `rmw_network_flow_endpoint_array_t` entry from it.
+///
+/// `transport_protocol` and `internet_protocol` follow `network_flow::TransportProtocol` and
+/// `network_flow::InternetProtocol`'s numbering: transport `0` = TCP, `1` = UDP (including QUIC),
+/// `2` = unknown; internet protocol `0` = IPv4, `1` = IPv6.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Arguments
+///
+/// * `ptr` - A raw pointer to a `Libp2pCustomNode`.
+/// * `index` - Which listen address to decompose, in `[0, count)` as reported by
+///   `rs_libp2p_custom_node_get_listen_endpoint_count`.
+/// * `out_transport_protocol` - Written with the transport protocol code.
+/// * `out_internet_protocol` - Written with the internet protocol code.
+/// * `out_port` - Written with the transport port.
+/// * `address_buf` - A buffer the textual internet address is copied into (truncated to
+///   `address_cap` if it doesn't fit).
+/// * `address_cap` - The capacity of `address_buf`, in bytes.
+///
+/// # Returns
+///
+/// The number of bytes copied into `address_buf`, or `0` if `index` is out of range.
+///
+/// # Panics
+///
+/// This function will panic if `ptr`, `out_transport_protocol`, `out_internet_protocol`, or
+/// `out_port` is null, or if `address_buf` is null while `address_cap` is nonzero.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_get_listen_endpoint(
+    ptr: *mut Libp2pCustomNode,
+    index: usize,
+    out_transport_protocol: *mut u8,
+    out_internet_protocol: *mut u8,
+    out_port: *mut u16,
+    address_buf: *mut u8,
+    address_cap: usize,
+) -> usize {
+    let libp2p_custom_node = unsafe {
+        assert!(!ptr.is_null());
+        &*ptr
+    };
+    write_endpoint(
+        libp2p_custom_node.listen_endpoints().get(index),
+        out_transport_protocol,
+        out_internet_protocol,
+        out_port,
+        address_buf,
+        address_cap,
+    )
+}
+
+/// Reports how many addresses peers have observed this node at (via `identify`), for sizing the
+/// buffer passed to `rs_libp2p_custom_node_get_external_endpoint`.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Panics
+///
+/// This function will panic if `ptr` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_get_external_endpoint_count(ptr: *mut Libp2pCustomNode) -> usize {
+    let libp2p_custom_node = unsafe {
+        assert!(!ptr.is_null());
+        &*ptr
+    };
+    libp2p_custom_node.external_endpoints().len()
+}
+
+/// Decomposes the `index`-th peer-observed external address the same way
+/// `rs_libp2p_custom_node_get_listen_endpoint` does for listen addresses. Reporting both lets a
+/// caller populate `rmw_network_flow_endpoint_array_t` with this node's reachable endpoints the
+/// way `ros2 doctor` expects, covering both the locally-bound and NAT/AutoNAT-observed cases.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Panics
+///
+/// This function will panic if `ptr`, `out_transport_protocol`, `out_internet_protocol`, or
+/// `out_port` is null, or if `address_buf` is null while `address_cap` is nonzero.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_get_external_endpoint(
+    ptr: *mut Libp2pCustomNode,
+    index: usize,
+    out_transport_protocol: *mut u8,
+    out_internet_protocol: *mut u8,
+    out_port: *mut u16,
+    address_buf: *mut u8,
+    address_cap: usize,
+) -> usize {
+    let libp2p_custom_node = unsafe {
+        assert!(!ptr.is_null());
+        &*ptr
+    };
+    write_endpoint(
+        libp2p_custom_node.external_endpoints().get(index),
+        out_transport_protocol,
+        out_internet_protocol,
+        out_port,
+        address_buf,
+        address_cap,
+    )
+}
+
+/// Shared by `rs_libp2p_custom_node_get_listen_endpoint` and `_get_external_endpoint`: writes
+/// `endpoint`'s fields into the caller's out-parameters, or does nothing and returns `0` if
+/// `endpoint` is `None` (an out-of-range index).
+fn write_endpoint(
+    endpoint: Option<&Endpoint>,
+    out_transport_protocol: *mut u8,
+    out_internet_protocol: *mut u8,
+    out_port: *mut u16,
+    address_buf: *mut u8,
+    address_cap: usize,
+) -> usize {
+    let Some(endpoint) = endpoint else {
+        return 0;
+    };
+    unsafe {
+        assert!(!out_transport_protocol.is_null());
+        assert!(!out_internet_protocol.is_null());
+        assert!(!out_port.is_null());
+        assert!(!address_buf.is_null() || address_cap == 0);
+        *out_transport_protocol = endpoint.transport_protocol as u8;
+        *out_internet_protocol = endpoint.internet_protocol as u8;
+        *out_port = endpoint.transport_port;
+    }
+    let address_bytes = endpoint.internet_address.as_bytes();
+    let len = address_bytes.len().min(address_cap);
+    unsafe {
+        std::ptr::copy_nonoverlapping(address_bytes.as_ptr(), address_buf, len);
+    }
+    len
+}
+
+/// Reports how many messages are currently buffered in the node's outgoing publish queue.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Panics
+///
+/// This function will panic if `ptr` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_get_outgoing_queue_depth(ptr: *mut Libp2pCustomNode) -> usize {
+    let libp2p_custom_node = unsafe {
+        assert!(!ptr.is_null());
+        &*ptr
+    };
+    libp2p_custom_node.outgoing_queue_depth()
+}
+
+/// Reports the largest number of messages the node's outgoing publish queue has held at once,
+/// so RMW's QoS `depth` setting can be checked against sustained lag rather than an instantaneous
+/// snapshot.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Panics
+///
+/// This function will panic if `ptr` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_node_get_outgoing_queue_high_water_mark(
+    ptr: *mut Libp2pCustomNode,
+) -> usize {
+    let libp2p_custom_node = unsafe {
+        assert!(!ptr.is_null());
+        &*ptr
+    };
+    libp2p_custom_node.outgoing_queue_high_water_mark()
+}
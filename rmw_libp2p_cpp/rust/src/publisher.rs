@@ -12,11 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::c_types::{checked_mut, checked_ref, checked_str, Libp2pRetT};
+use crate::loaned_message::{LoanHandle, ShmRing, DEFAULT_LOAN_SLOT_COUNT, DEFAULT_LOAN_SLOT_SIZE};
+use crate::qos::{Durability, Libp2pQos, Reliability};
+use crate::qos_event::{DeadlineTracker, IncompatibleQosTracker, LivelinessTracker};
 use crate::Libp2pCustomNode;
 
-use std::ffi::CStr;
+use std::collections::{HashMap, VecDeque};
 use std::io::Cursor;
 use std::os::raw::c_char;
+use std::time::Duration;
 
 use uuid::Uuid;
 
@@ -28,6 +33,24 @@ pub struct Libp2pCustomPublisher {
     node: *mut Libp2pCustomNode, // We need to store the Node here to have access to the outgoing queue
     topic: gossipsub::IdentTopic,
     sequence_number: u64,
+    qos: Libp2pQos,
+    /// `Reliability::Reliable` history of unacknowledged messages, oldest first, bounded to
+    /// `qos.history_depth`. Retransmitted on demand via `retransmit_unacknowledged` until a real
+    /// ack/nack channel exists to gate retransmission automatically.
+    history: VecDeque<(u64, Vec<u8>)>,
+    dropped_count: u64,
+    retransmitted_count: u64,
+    /// Lazily created on the first `borrow_loaned_message` call; see `loaned_message.rs`.
+    loan_ring: Option<ShmRing>,
+    /// Maps a slot pointer currently on loan to the host back to its slot index, so
+    /// `publish_loaned_message`/`return_loaned_message` know which slot to release.
+    outstanding_loans: HashMap<usize, usize>,
+    /// Feeds `rs_libp2p_custom_publisher_get_offered_deadline_missed_event`; see `qos_event.rs`.
+    deadline_tracker: DeadlineTracker,
+    /// Feeds `rs_libp2p_custom_publisher_get_liveliness_lost_event`; see `qos_event.rs`.
+    liveliness_tracker: LivelinessTracker,
+    /// Feeds `rs_libp2p_custom_publisher_get_offered_incompatible_qos_event`; see `qos_event.rs`.
+    incompatible_qos: IncompatibleQosTracker,
 }
 
 /// Represents a custom publisher for the Libp2p network.
@@ -40,38 +63,216 @@ impl Libp2pCustomPublisher {
     ///
     /// * `libp2p_custom_node` - A pointer to the Libp2p custom node.
     /// * `topic_str` - The string representation of the topic to publish to.
+    /// * `qos` - The quality-of-service profile this publisher should honor.
+    /// * `deadline` - The maximum expected period between published messages, or `None` for no
+    ///   deadline. Feeds `rs_libp2p_custom_publisher_get_offered_deadline_missed_event`.
+    /// * `liveliness_lease_duration` - The maximum period without a publish or an explicit
+    ///   `rs_libp2p_custom_publisher_assert_liveliness` call before this publisher is considered
+    ///   not alive, or `None` for no lease. Feeds
+    ///   `rs_libp2p_custom_publisher_get_liveliness_lost_event`.
     ///
     /// # Returns
     ///
     /// A new instance of `Libp2pCustomPublisher`.
-    fn new(libp2p_custom_node: *mut Libp2pCustomNode, topic_str: &str) -> Self {
+    fn new(
+        libp2p_custom_node: *mut Libp2pCustomNode,
+        topic_str: &str,
+        qos: Libp2pQos,
+        deadline: Option<Duration>,
+        liveliness_lease_duration: Option<Duration>,
+    ) -> Self {
         Self {
             gid: Uuid::new_v4(),
             node: libp2p_custom_node,
             topic: gossipsub::IdentTopic::new(topic_str),
             sequence_number: 0,
+            qos,
+            history: VecDeque::with_capacity(qos.history_depth),
+            dropped_count: 0,
+            retransmitted_count: 0,
+            loan_ring: None,
+            outstanding_loans: HashMap::new(),
+            deadline_tracker: DeadlineTracker::new(deadline),
+            liveliness_tracker: LivelinessTracker::new(liveliness_lease_duration),
+            incompatible_qos: IncompatibleQosTracker::default(),
         }
     }
 
     /// Publishes a message to the Libp2p network.
     ///
+    /// For `Reliability::Reliable` publishers, the message is also kept in a bounded history
+    /// buffer so it can be retransmitted with `retransmit_unacknowledged`; nothing acknowledges
+    /// an entry, and once the buffer reaches `qos.history_depth`, the oldest entry is dropped.
+    ///
     /// # Arguments
     ///
     /// * `buffer` - The buffer containing the message to be published.
-    fn publish(&mut self, buffer: Vec<u8>) -> () {
-        let libp2p_custom_node = unsafe {
-            assert!(!self.node.is_null());
-            &mut *self.node
-        };
-        libp2p_custom_node.publish_message(self.topic.clone(), buffer);
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes published on success, or `Libp2pRetT::InvalidArgument` if this
+    /// publisher's node pointer is null.
+    fn publish(&mut self, buffer: Vec<u8>) -> Result<usize, Libp2pRetT> {
+        self.publish_with_local_override(buffer, None)
+    }
+
+    /// Same as `publish`, but hands a same-process local subscriber (if any) `local_override`
+    /// instead of `buffer`. Used by `publish_loaned_message` so the local subscriber can be
+    /// handed a zero-copy [`LoanHandle`] while the network/history path still sees the real
+    /// serialized message.
+    fn publish_with_local_override(
+        &mut self,
+        buffer: Vec<u8>,
+        local_override: Option<Vec<u8>>,
+    ) -> Result<usize, Libp2pRetT> {
+        let libp2p_custom_node = unsafe { checked_mut(self.node)? };
+        self.deadline_tracker.poll();
+        self.deadline_tracker.note_activity();
+        self.liveliness_tracker.poll();
+        self.liveliness_tracker.note_assertion();
+        let bytes_written = buffer.len();
+        match local_override {
+            Some(local_buffer) => libp2p_custom_node.publish_message_with_local_override(
+                self.topic.clone(),
+                buffer.clone(),
+                local_buffer,
+            ),
+            None => libp2p_custom_node.publish_message(self.topic.clone(), buffer.clone()),
+        }
+
+        if self.qos.durability == Durability::TransientLocal {
+            libp2p_custom_node.record_history_sample(
+                self.topic.hash().into_string(),
+                self.qos.history_depth,
+                buffer.clone(),
+            );
+        }
+
+        if self.qos.reliability == Reliability::Reliable {
+            if self.history.len() >= self.qos.history_depth.max(1) {
+                self.history.pop_front();
+                self.dropped_count += 1;
+            }
+            self.history.push_back((self.sequence_number, buffer));
+        }
+
         self.sequence_number += 1;
+        Ok(bytes_written)
+    }
+
+    /// Retransmits every message currently held in the `Reliability::Reliable` history buffer.
+    ///
+    /// # Returns
+    ///
+    /// The number of messages retransmitted on success, or `Libp2pRetT::InvalidArgument` if
+    /// this publisher's node pointer is null.
+    fn retransmit_unacknowledged(&mut self) -> Result<usize, Libp2pRetT> {
+        let libp2p_custom_node = unsafe { checked_mut(self.node)? };
+        for (_sequence_number, buffer) in self.history.iter() {
+            libp2p_custom_node.publish_message(self.topic.clone(), buffer.clone());
+            self.retransmitted_count += 1;
+        }
+        Ok(self.history.len())
+    }
+
+    /// Hands out a pointer into a free slot of this publisher's shared-memory ring, creating the
+    /// ring on the first call. See `loaned_message.rs` for the zero-copy mechanism this backs.
+    ///
+    /// # Returns
+    ///
+    /// A pointer valid for `loaned_message::DEFAULT_LOAN_SLOT_SIZE` bytes on success, or
+    /// `Libp2pRetT::Error` if the ring couldn't be created (e.g. `shm_open`/`mmap` failed) or
+    /// every slot is currently on loan.
+    fn borrow_loaned_message(&mut self) -> Result<*mut u8, Libp2pRetT> {
+        if self.loan_ring.is_none() {
+            let segment_name = format!("/rmw_libp2p_{}", self.gid.simple());
+            let ring = ShmRing::create(&segment_name, DEFAULT_LOAN_SLOT_SIZE, DEFAULT_LOAN_SLOT_COUNT)
+                .map_err(|_| Libp2pRetT::Error)?;
+            self.loan_ring = Some(ring);
+        }
+        let ring = self.loan_ring.as_ref().expect("just created above");
+        let (index, ptr) = ring.borrow().ok_or(Libp2pRetT::Error)?;
+        self.outstanding_loans.insert(ptr as usize, index);
+        Ok(ptr)
+    }
+
+    /// Publishes a message previously borrowed from `borrow_loaned_message`.
+    ///
+    /// The real serialized contents of the slot are always what gets published over gossipsub
+    /// and (for `Durability::TransientLocal`) into history — remote peers and late-joining
+    /// history replay must never see anything but the real message. If this process already has
+    /// a subscriber for this topic, that subscriber is separately handed a [`LoanHandle`]
+    /// instead, so it can map the ring and read the slot directly with no copy. This is a
+    /// same-*process* loan, not a same-host one: this crate has no discovery signal for a
+    /// matched subscriber living in a different process, even one on the same host sharing the
+    /// same `/dev/shm`, so that case just releases the slot once the copy is taken, same as a
+    /// genuinely remote subscriber. See `loaned_message.rs`'s module doc comment.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes published on success, or `Libp2pRetT::InvalidArgument` if `ptr` was
+    /// not the result of a still-outstanding `borrow_loaned_message` call on this publisher.
+    fn publish_loaned_message(&mut self, ptr: *mut u8) -> Result<usize, Libp2pRetT> {
+        let index = self
+            .outstanding_loans
+            .remove(&(ptr as usize))
+            .ok_or(Libp2pRetT::InvalidArgument)?;
+        let ring = self.loan_ring.as_ref().ok_or(Libp2pRetT::InvalidArgument)?;
+
+        let copy = unsafe { std::slice::from_raw_parts(ptr, DEFAULT_LOAN_SLOT_SIZE) }.to_vec();
+
+        let libp2p_custom_node = unsafe { checked_ref(self.node)? };
+        if libp2p_custom_node.has_local_subscriber(&self.topic) {
+            let generation = ring.publish(index);
+            let handle = LoanHandle {
+                segment_name: ring.name().to_string(),
+                slot_index: index as u32,
+                slot_size: DEFAULT_LOAN_SLOT_SIZE as u32,
+                slot_count: DEFAULT_LOAN_SLOT_COUNT as u32,
+                generation,
+            };
+            self.publish_with_local_override(copy, Some(handle.encode()))
+        } else {
+            ring.release(index);
+            self.publish(copy)
+        }
+    }
+
+    /// Releases a loaned slot back to the free list without publishing it.
+    ///
+    /// # Returns
+    ///
+    /// `Libp2pRetT::InvalidArgument` if `ptr` was not the result of a still-outstanding
+    /// `borrow_loaned_message` call on this publisher.
+    fn return_loaned_message(&mut self, ptr: *mut u8) -> Result<(), Libp2pRetT> {
+        let index = self
+            .outstanding_loans
+            .remove(&(ptr as usize))
+            .ok_or(Libp2pRetT::InvalidArgument)?;
+        let ring = self.loan_ring.as_ref().ok_or(Libp2pRetT::InvalidArgument)?;
+        ring.release(index);
+        Ok(())
+    }
+
+    /// Resets this publisher's liveliness lease window without publishing a message. Mirrors
+    /// `rmw_publisher_assert_liveliness`.
+    fn assert_liveliness(&mut self) {
+        self.liveliness_tracker.poll();
+        self.liveliness_tracker.note_assertion();
+    }
+
+    /// Notes that an independently-checked matched subscription's QoS turned out to be
+    /// incompatible with this publisher's, e.g. via `rs_libp2p_custom_qos_check_compatible`.
+    fn record_incompatible_qos(&mut self, policy_kind: u32) {
+        self.incompatible_qos.record(policy_kind);
     }
 }
 
 /// Creates a new `Libp2pCustomPublisher`.
 ///
-/// This function takes a raw pointer to a `Libp2pCustomNode` and a raw pointer to a C string representing the topic.
-/// It then creates a new `Libp2pCustomPublisher` for the given node and topic, and returns a raw pointer to the heap-allocated publisher.
+/// This function takes a raw pointer to a `Libp2pCustomNode` and a raw pointer to a C string
+/// representing the topic. On success, it creates a new `Libp2pCustomPublisher` for the given
+/// node and topic and writes a raw pointer to the heap-allocated publisher into `out_publisher`.
 ///
 /// # Safety
 ///
@@ -81,27 +282,77 @@ impl Libp2pCustomPublisher {
 ///
 /// * `ptr_node` - A raw pointer to a `Libp2pCustomNode`.
 /// * `topic_str_ptr` - A raw pointer to a C string representing the topic.
+/// * `reliability` - `0` for `Reliability::Reliable`, `1` for `Reliability::BestEffort`.
+/// * `history_depth` - `KEEP_LAST` history depth; the maximum number of unacknowledged messages
+///   a `Reliable` publisher keeps buffered for retransmission.
+/// * `lifespan_ms` - Stored as the QoS lifespan, in milliseconds, or `0` for none. Buffered
+///   messages are not expired by age.
+/// * `durability` - `0` for `Durability::TransientLocal`, `1` for `Durability::Volatile`.
+/// * `deadline_ms` - The maximum expected period between published messages, in milliseconds, or
+///   `0` for no deadline. Feeds `rs_libp2p_custom_publisher_get_offered_deadline_missed_event`.
+/// * `liveliness_lease_duration_ms` - The maximum period without a publish or an explicit
+///   `rs_libp2p_custom_publisher_assert_liveliness` call before this publisher is considered not
+///   alive, in milliseconds, or `0` for no lease. Feeds
+///   `rs_libp2p_custom_publisher_get_liveliness_lost_event`.
+/// * `out_publisher` - Out parameter receiving a raw pointer to the new `Libp2pCustomPublisher`.
 ///
 /// # Returns
 ///
-/// A raw pointer to a `Libp2pCustomPublisher`.
-///
-/// # Panics
-///
-/// This function will panic if `topic_str_ptr` is null or if it does not point to a valid null-terminated string.
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if `topic_str_ptr` or
+/// `out_publisher` is null, or if `topic_str_ptr` does not point to valid UTF-8.
 #[no_mangle]
+#[allow(clippy::too_many_arguments)]
 pub extern "C" fn rs_libp2p_custom_publisher_new(
     ptr_node: *mut Libp2pCustomNode,
     topic_str_ptr: *const c_char,
-) -> *mut Libp2pCustomPublisher {
-    let topic_str = unsafe {
-        assert!(!topic_str_ptr.is_null());
-        CStr::from_ptr(topic_str_ptr)
+    reliability: u8,
+    history_depth: usize,
+    lifespan_ms: u64,
+    durability: u8,
+    deadline_ms: u64,
+    liveliness_lease_duration_ms: u64,
+    out_publisher: *mut *mut Libp2pCustomPublisher,
+) -> Libp2pRetT {
+    let topic_str = match unsafe { checked_str(topic_str_ptr) } {
+        Ok(topic_str) => topic_str,
+        Err(ret) => return ret,
+    };
+    let out_publisher = match unsafe { checked_mut(out_publisher) } {
+        Ok(out_publisher) => out_publisher,
+        Err(ret) => return ret,
+    };
+
+    let reliability = if reliability == 0 {
+        Reliability::Reliable
+    } else {
+        Reliability::BestEffort
+    };
+    let lifespan = if lifespan_ms == 0 {
+        None
+    } else {
+        Some(std::time::Duration::from_millis(lifespan_ms))
+    };
+    let durability = if durability == 0 {
+        Durability::TransientLocal
+    } else {
+        Durability::Volatile
+    };
+    let qos = Libp2pQos::new(reliability, history_depth, lifespan, durability);
+    let deadline = if deadline_ms == 0 {
+        None
+    } else {
+        Some(Duration::from_millis(deadline_ms))
+    };
+    let liveliness_lease_duration = if liveliness_lease_duration_ms == 0 {
+        None
+    } else {
+        Some(Duration::from_millis(liveliness_lease_duration_ms))
     };
 
     let libp2p_custom_publisher =
-        Libp2pCustomPublisher::new(ptr_node, topic_str.to_str().unwrap());
-    Box::into_raw(Box::new(libp2p_custom_publisher))
+        Libp2pCustomPublisher::new(ptr_node, topic_str, qos, deadline, liveliness_lease_duration);
+    *out_publisher = Box::into_raw(Box::new(libp2p_custom_publisher));
+    Libp2pRetT::Ok
 }
 
 /// Frees a `Libp2pCustomPublisher` from memory.
@@ -127,45 +378,55 @@ pub extern "C" fn rs_libp2p_custom_publisher_free(ptr: *mut Libp2pCustomPublishe
 /// Gets the GID of a `Libp2pCustomPublisher`.
 ///
 /// This function takes a raw pointer to a `Libp2pCustomPublisher` and a raw pointer to a buffer.
-/// It then copies the bytes of the GID of the publisher into the buffer and returns the number of bytes copied.
+/// On success, it copies the bytes of the publisher's GID into the buffer and writes the number
+/// of bytes copied into `out_len`.
 ///
 /// # Safety
 ///
-/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+/// This function is unsafe because it uses raw pointers and calls unsafe functions. `buf` must
+/// point to a buffer at least 16 bytes long.
 ///
 /// # Arguments
 ///
 /// * `ptr` - A raw pointer to a `Libp2pCustomPublisher`.
 /// * `buf` - A raw pointer to a buffer where the GID bytes will be copied.
+/// * `out_len` - Out parameter receiving the number of bytes copied into `buf`.
 ///
 /// # Returns
 ///
-/// The number of bytes copied into the buffer.
-///
-/// # Panics
-///
-/// This function will panic if `ptr` is null.
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if `ptr`, `buf`, or `out_len`
+/// is null.
 #[no_mangle]
 pub extern "C" fn rs_libp2p_custom_publisher_get_gid(
     ptr: *mut Libp2pCustomPublisher,
     buf: *mut std::os::raw::c_uchar,
-) -> usize {
-    let libp2p_custom_publisher = unsafe {
-        assert!(!ptr.is_null());
-        &mut *ptr
+    out_len: *mut usize,
+) -> Libp2pRetT {
+    let libp2p_custom_publisher = match unsafe { checked_ref(ptr) } {
+        Ok(publisher) => publisher,
+        Err(ret) => return ret,
+    };
+    if buf.is_null() {
+        return Libp2pRetT::InvalidArgument;
+    }
+    let out_len = match unsafe { checked_mut(out_len) } {
+        Ok(out_len) => out_len,
+        Err(ret) => return ret,
     };
+
     let gid_bytes = libp2p_custom_publisher.gid.as_bytes();
-    let count = gid_bytes.len();
     unsafe {
-        std::ptr::copy_nonoverlapping(gid_bytes.as_ptr(), buf as *mut u8, count);
+        std::ptr::copy_nonoverlapping(gid_bytes.as_ptr(), buf as *mut u8, gid_bytes.len());
     }
-    count
+    *out_len = gid_bytes.len();
+    Libp2pRetT::Ok
 }
 
 /// Publishes a message using a `Libp2pCustomPublisher`.
 ///
-/// This function takes raw pointers to a `Libp2pCustomPublisher` and a `Cursor<Vec<u8>>`.
-/// It then publishes the contents of the `Cursor<Vec<u8>>` using the `Libp2pCustomPublisher`.
+/// This function takes raw pointers to a `Libp2pCustomPublisher` and a `Cursor<Vec<u8>>`. On
+/// success, it publishes the contents of the `Cursor<Vec<u8>>` and writes the number of bytes
+/// published into `out_bytes_written`.
 ///
 /// # Safety
 ///
@@ -175,30 +436,38 @@ pub extern "C" fn rs_libp2p_custom_publisher_get_gid(
 ///
 /// * `ptr_publisher` - A raw pointer to a `Libp2pCustomPublisher`.
 /// * `ptr_buffer` - A raw pointer to a `Cursor<Vec<u8>>` containing the message to publish.
+/// * `out_bytes_written` - Out parameter receiving the number of bytes published.
 ///
 /// # Returns
 ///
-/// Currently, this function always returns 0.
-///
-/// # Panics
-///
-/// This function will panic if either `ptr_publisher` or `ptr_buffer` is null.
+/// `Libp2pRetT::Ok` on success, `Libp2pRetT::InvalidArgument` if `ptr_publisher`, `ptr_buffer`,
+/// or `out_bytes_written` is null, or if the publisher's node pointer is null.
 #[no_mangle]
 pub extern "C" fn rs_libp2p_custom_publisher_publish(
     ptr_publisher: *mut Libp2pCustomPublisher,
     ptr_buffer: *const Cursor<Vec<u8>>,
-) -> usize {
-    let libp2p_custom_publisher = unsafe {
-        assert!(!ptr_publisher.is_null());
-        &mut *ptr_publisher
+    out_bytes_written: *mut usize,
+) -> Libp2pRetT {
+    let libp2p_custom_publisher = match unsafe { checked_mut(ptr_publisher) } {
+        Ok(publisher) => publisher,
+        Err(ret) => return ret,
+    };
+    let buffer = match unsafe { checked_ref(ptr_buffer) } {
+        Ok(buffer) => buffer,
+        Err(ret) => return ret,
     };
-    let buffer = unsafe {
-        assert!(!ptr_buffer.is_null());
-        &*ptr_buffer
+    let out_bytes_written = match unsafe { checked_mut(out_bytes_written) } {
+        Ok(out_bytes_written) => out_bytes_written,
+        Err(ret) => return ret,
     };
-    libp2p_custom_publisher.publish(buffer.get_ref().to_vec());
-    // TODO(esteve): return the number of bytes published
-    0
+
+    match libp2p_custom_publisher.publish(buffer.get_ref().to_vec()) {
+        Ok(bytes_written) => {
+            *out_bytes_written = bytes_written;
+            Libp2pRetT::Ok
+        }
+        Err(ret) => ret,
+    }
 }
 
 #[no_mangle]
@@ -212,3 +481,435 @@ pub extern "C" fn rs_libp2p_custom_publisher_get_sequence_number(
     libp2p_custom_publisher.sequence_number
 }
 
+/// Retransmits every message a `Reliability::Reliable` publisher is still holding in its
+/// history buffer, writing the number of messages retransmitted into `out_count`.
+///
+/// This is a no-op for `Reliability::BestEffort` publishers, since they never populate the
+/// history buffer in the first place.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+///
+/// # Arguments
+///
+/// * `ptr` - A raw pointer to a `Libp2pCustomPublisher`.
+/// * `out_count` - Out parameter receiving the number of messages retransmitted.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if `ptr` or `out_count` is
+/// null, or if this publisher's node is invalid.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_publisher_retransmit_unacknowledged(
+    ptr: *mut Libp2pCustomPublisher,
+    out_count: *mut usize,
+) -> Libp2pRetT {
+    let libp2p_custom_publisher = match unsafe { checked_mut(ptr) } {
+        Ok(publisher) => publisher,
+        Err(ret) => return ret,
+    };
+    let out_count = match unsafe { checked_mut(out_count) } {
+        Ok(out_count) => out_count,
+        Err(ret) => return ret,
+    };
+
+    match libp2p_custom_publisher.retransmit_unacknowledged() {
+        Ok(count) => {
+            *out_count = count;
+            Libp2pRetT::Ok
+        }
+        Err(ret) => ret,
+    }
+}
+
+/// Returns the number of history-buffer entries a `Reliability::Reliable` publisher has had to
+/// drop because it reached `history_depth` before being retransmitted.
+///
+/// # Safety
+///
+/// This function is unsafe because it dereferences a raw pointer.
+///
+/// # Arguments
+///
+/// * `ptr` - A raw pointer to a `Libp2pCustomPublisher`.
+///
+/// # Panics
+///
+/// This function will panic if `ptr` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_publisher_get_dropped_count(
+    ptr: *mut Libp2pCustomPublisher,
+) -> u64 {
+    let libp2p_custom_publisher = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    libp2p_custom_publisher.dropped_count
+}
+
+/// Returns the number of messages a `Reliability::Reliable` publisher has retransmitted via
+/// `rs_libp2p_custom_publisher_retransmit_unacknowledged`.
+///
+/// # Safety
+///
+/// This function is unsafe because it dereferences a raw pointer.
+///
+/// # Arguments
+///
+/// * `ptr` - A raw pointer to a `Libp2pCustomPublisher`.
+///
+/// # Panics
+///
+/// This function will panic if `ptr` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_publisher_get_retransmitted_count(
+    ptr: *mut Libp2pCustomPublisher,
+) -> u64 {
+    let libp2p_custom_publisher = unsafe {
+        assert!(!ptr.is_null());
+        &mut *ptr
+    };
+    libp2p_custom_publisher.retransmitted_count
+}
+
+/// Borrows a loaned message from a `Libp2pCustomPublisher`'s shared-memory ring, creating the
+/// ring on the first call for this publisher.
+///
+/// See `loaned_message.rs` for why this only achieves true zero-copy for a same-process
+/// subscriber, and why there is no `rosidl` message to loan into here the way a real
+/// `libp2p_c__rmw_borrow_loaned_message` would.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+///
+/// # Arguments
+///
+/// * `ptr_publisher` - A raw pointer to a `Libp2pCustomPublisher`.
+/// * `out_loaned_message` - Out parameter receiving a pointer valid for
+///   `loaned_message::DEFAULT_LOAN_SLOT_SIZE` bytes.
+/// * `out_len` - Out parameter receiving the size of the loaned slot, in bytes.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, `Libp2pRetT::InvalidArgument` if any pointer argument is null, or
+/// `Libp2pRetT::Error` if the ring couldn't be created or every slot is currently on loan.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_publisher_borrow_loaned_message(
+    ptr_publisher: *mut Libp2pCustomPublisher,
+    out_loaned_message: *mut *mut u8,
+    out_len: *mut usize,
+) -> Libp2pRetT {
+    let libp2p_custom_publisher = match unsafe { checked_mut(ptr_publisher) } {
+        Ok(publisher) => publisher,
+        Err(ret) => return ret,
+    };
+    let out_loaned_message = match unsafe { checked_mut(out_loaned_message) } {
+        Ok(out_loaned_message) => out_loaned_message,
+        Err(ret) => return ret,
+    };
+    let out_len = match unsafe { checked_mut(out_len) } {
+        Ok(out_len) => out_len,
+        Err(ret) => return ret,
+    };
+
+    match libp2p_custom_publisher.borrow_loaned_message() {
+        Ok(ptr) => {
+            *out_loaned_message = ptr;
+            *out_len = crate::loaned_message::DEFAULT_LOAN_SLOT_SIZE;
+            Libp2pRetT::Ok
+        }
+        Err(ret) => ret,
+    }
+}
+
+/// Publishes a message previously borrowed with
+/// `rs_libp2p_custom_publisher_borrow_loaned_message`, writing the number of bytes published
+/// into `out_bytes_written`.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+///
+/// # Arguments
+///
+/// * `ptr_publisher` - A raw pointer to a `Libp2pCustomPublisher`.
+/// * `loaned_message` - A pointer previously returned by
+///   `rs_libp2p_custom_publisher_borrow_loaned_message` on this same publisher.
+/// * `out_bytes_written` - Out parameter receiving the number of bytes published.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if any pointer argument is null
+/// or `loaned_message` is not currently on loan from this publisher.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_publisher_publish_loaned_message(
+    ptr_publisher: *mut Libp2pCustomPublisher,
+    loaned_message: *mut u8,
+    out_bytes_written: *mut usize,
+) -> Libp2pRetT {
+    let libp2p_custom_publisher = match unsafe { checked_mut(ptr_publisher) } {
+        Ok(publisher) => publisher,
+        Err(ret) => return ret,
+    };
+    let out_bytes_written = match unsafe { checked_mut(out_bytes_written) } {
+        Ok(out_bytes_written) => out_bytes_written,
+        Err(ret) => return ret,
+    };
+    if loaned_message.is_null() {
+        return Libp2pRetT::InvalidArgument;
+    }
+
+    match libp2p_custom_publisher.publish_loaned_message(loaned_message) {
+        Ok(bytes_written) => {
+            *out_bytes_written = bytes_written;
+            Libp2pRetT::Ok
+        }
+        Err(ret) => ret,
+    }
+}
+
+/// Releases a message borrowed with `rs_libp2p_custom_publisher_borrow_loaned_message` back to
+/// the publisher's free list without publishing it.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+///
+/// # Arguments
+///
+/// * `ptr_publisher` - A raw pointer to a `Libp2pCustomPublisher`.
+/// * `loaned_message` - A pointer previously returned by
+///   `rs_libp2p_custom_publisher_borrow_loaned_message` on this same publisher.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if `ptr_publisher` is null or
+/// `loaned_message` is not currently on loan from this publisher.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_publisher_return_loaned_message(
+    ptr_publisher: *mut Libp2pCustomPublisher,
+    loaned_message: *mut u8,
+) -> Libp2pRetT {
+    let libp2p_custom_publisher = match unsafe { checked_mut(ptr_publisher) } {
+        Ok(publisher) => publisher,
+        Err(ret) => return ret,
+    };
+    if loaned_message.is_null() {
+        return Libp2pRetT::InvalidArgument;
+    }
+
+    match libp2p_custom_publisher.return_loaned_message(loaned_message) {
+        Ok(()) => Libp2pRetT::Ok,
+        Err(ret) => ret,
+    }
+}
+
+/// Whether this node supports zero-copy loaned messages at all.
+///
+/// A hypothetical `libp2p_c__rmw_feature_supported(RMW_FEATURE_MESSAGE_LOANING)` would return
+/// this. Always `true`: the shared-memory ring mechanism is implemented, though it only ever
+/// achieves true zero-copy for a same-process subscriber (see
+/// `Libp2pCustomPublisher::publish_loaned_message`); every other match transparently falls back
+/// to a serialized copy instead of failing.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_loaned_messages_supported() -> bool {
+    true
+}
+
+/// Resets a publisher's liveliness lease window without publishing a message. If the lease had
+/// already lapsed before this call, the loss is first recorded in the counters that
+/// `rs_libp2p_custom_publisher_get_liveliness_lost_event` reports. Mirrors
+/// `rmw_publisher_assert_liveliness`.
+///
+/// # Safety
+///
+/// This function is unsafe because it dereferences a raw pointer.
+///
+/// # Arguments
+///
+/// * `ptr` - A raw pointer to a `Libp2pCustomPublisher`.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if `ptr` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_publisher_assert_liveliness(
+    ptr: *mut Libp2pCustomPublisher,
+) -> Libp2pRetT {
+    let libp2p_custom_publisher = match unsafe { checked_mut(ptr) } {
+        Ok(publisher) => publisher,
+        Err(ret) => return ret,
+    };
+    libp2p_custom_publisher.assert_liveliness();
+    Libp2pRetT::Ok
+}
+
+/// Reads and clears this publisher's `OfferedDeadlineMissed` event status, mirroring
+/// `rmw_offered_deadline_missed_status_t`. See `qos_event.rs`.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+///
+/// # Arguments
+///
+/// * `ptr` - A raw pointer to a `Libp2pCustomPublisher`.
+/// * `out_total_count` - Out parameter receiving the cumulative number of missed deadlines.
+/// * `out_total_count_change` - Out parameter receiving the number of misses since the last read.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if any pointer argument is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_publisher_get_offered_deadline_missed_event(
+    ptr: *mut Libp2pCustomPublisher,
+    out_total_count: *mut u32,
+    out_total_count_change: *mut u32,
+) -> Libp2pRetT {
+    let libp2p_custom_publisher = match unsafe { checked_mut(ptr) } {
+        Ok(publisher) => publisher,
+        Err(ret) => return ret,
+    };
+    let out_total_count = match unsafe { checked_mut(out_total_count) } {
+        Ok(out_total_count) => out_total_count,
+        Err(ret) => return ret,
+    };
+    let out_total_count_change = match unsafe { checked_mut(out_total_count_change) } {
+        Ok(out_total_count_change) => out_total_count_change,
+        Err(ret) => return ret,
+    };
+
+    libp2p_custom_publisher.deadline_tracker.poll();
+    let (total_count, total_count_change) = libp2p_custom_publisher.deadline_tracker.take_status();
+    *out_total_count = total_count;
+    *out_total_count_change = total_count_change;
+    Libp2pRetT::Ok
+}
+
+/// Reads and clears this publisher's `LivelinessLost` event status, mirroring
+/// `rmw_liveliness_lost_status_t`. See `qos_event.rs`.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+///
+/// # Arguments
+///
+/// * `ptr` - A raw pointer to a `Libp2pCustomPublisher`.
+/// * `out_total_count` - Out parameter receiving the cumulative number of liveliness losses.
+/// * `out_total_count_change` - Out parameter receiving the number of losses since the last read.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if any pointer argument is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_publisher_get_liveliness_lost_event(
+    ptr: *mut Libp2pCustomPublisher,
+    out_total_count: *mut u32,
+    out_total_count_change: *mut u32,
+) -> Libp2pRetT {
+    let libp2p_custom_publisher = match unsafe { checked_mut(ptr) } {
+        Ok(publisher) => publisher,
+        Err(ret) => return ret,
+    };
+    let out_total_count = match unsafe { checked_mut(out_total_count) } {
+        Ok(out_total_count) => out_total_count,
+        Err(ret) => return ret,
+    };
+    let out_total_count_change = match unsafe { checked_mut(out_total_count_change) } {
+        Ok(out_total_count_change) => out_total_count_change,
+        Err(ret) => return ret,
+    };
+
+    libp2p_custom_publisher.liveliness_tracker.poll();
+    let (total_count, total_count_change) =
+        libp2p_custom_publisher.liveliness_tracker.take_status();
+    *out_total_count = total_count;
+    *out_total_count_change = total_count_change;
+    Libp2pRetT::Ok
+}
+
+/// Records that a matched subscription's QoS was found incompatible with this publisher's, for
+/// example via `rs_libp2p_custom_qos_check_compatible`. There is no discovery-time QoS
+/// negotiation in this crate to call this automatically; a caller that performs its own
+/// compatibility check is expected to call this when it returns `QosCompatibility::Error`.
+///
+/// # Safety
+///
+/// This function is unsafe because it dereferences a raw pointer.
+///
+/// # Arguments
+///
+/// * `ptr` - A raw pointer to a `Libp2pCustomPublisher`.
+/// * `policy_kind` - `0` = reliability, `1` = durability, `2` = deadline, `3` = liveliness.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if `ptr` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_publisher_record_offered_incompatible_qos(
+    ptr: *mut Libp2pCustomPublisher,
+    policy_kind: u32,
+) -> Libp2pRetT {
+    let libp2p_custom_publisher = match unsafe { checked_mut(ptr) } {
+        Ok(publisher) => publisher,
+        Err(ret) => return ret,
+    };
+    libp2p_custom_publisher.record_incompatible_qos(policy_kind);
+    Libp2pRetT::Ok
+}
+
+/// Reads and clears this publisher's `OfferedIncompatibleQos` event status, mirroring
+/// `rmw_offered_qos_incompatible_event_status_t`. See `qos_event.rs`.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+///
+/// # Arguments
+///
+/// * `ptr` - A raw pointer to a `Libp2pCustomPublisher`.
+/// * `out_total_count` - Out parameter receiving the cumulative number of incompatible-QoS
+///   detections.
+/// * `out_total_count_change` - Out parameter receiving the number of detections since the last
+///   read.
+/// * `out_last_policy_kind` - Out parameter receiving the policy kind of the most recent
+///   mismatch (see `rs_libp2p_custom_publisher_record_offered_incompatible_qos`).
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if any pointer argument is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_publisher_get_offered_incompatible_qos_event(
+    ptr: *mut Libp2pCustomPublisher,
+    out_total_count: *mut u32,
+    out_total_count_change: *mut u32,
+    out_last_policy_kind: *mut u32,
+) -> Libp2pRetT {
+    let libp2p_custom_publisher = match unsafe { checked_mut(ptr) } {
+        Ok(publisher) => publisher,
+        Err(ret) => return ret,
+    };
+    let out_total_count = match unsafe { checked_mut(out_total_count) } {
+        Ok(out_total_count) => out_total_count,
+        Err(ret) => return ret,
+    };
+    let out_total_count_change = match unsafe { checked_mut(out_total_count_change) } {
+        Ok(out_total_count_change) => out_total_count_change,
+        Err(ret) => return ret,
+    };
+    let out_last_policy_kind = match unsafe { checked_mut(out_last_policy_kind) } {
+        Ok(out_last_policy_kind) => out_last_policy_kind,
+        Err(ret) => return ret,
+    };
+
+    let (total_count, total_count_change, last_policy_kind) =
+        libp2p_custom_publisher.incompatible_qos.take_status();
+    *out_total_count = total_count;
+    *out_total_count_change = total_count_change;
+    *out_last_policy_kind = last_policy_kind;
+    Libp2pRetT::Ok
+}
+
@@ -0,0 +1,85 @@
+// Copyright 2024 Esteve Fernandez
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ROS 2-style quality-of-service policy for a `Libp2pCustomPublisher`.
+
+use std::time::Duration;
+
+/// Mirrors `rmw_qos_reliability_policy_t`'s two data-carrying variants.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reliability {
+    /// Messages are kept in a bounded history and can be retransmitted on demand via
+    /// `retransmit_unacknowledged`; no acknowledgement removes an entry, only eviction of the
+    /// oldest once the history is full.
+    Reliable = 0,
+    /// Messages are sent once and never retransmitted.
+    BestEffort = 1,
+}
+
+/// Mirrors `rmw_qos_durability_policy_t`'s two data-carrying variants.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Durability {
+    /// Late-joining subscribers receive up to `history_depth` samples published before they
+    /// subscribed, via a history query over `/rmw-libp2p/history/1.0.0`.
+    TransientLocal = 0,
+    /// Late-joining subscribers only receive samples published after they subscribed.
+    Volatile = 1,
+}
+
+/// Quality-of-service policy applied to a single publisher.
+#[derive(Debug, Clone, Copy)]
+pub struct Libp2pQos {
+    pub reliability: Reliability,
+    /// `KEEP_LAST` history depth: the maximum number of unacknowledged messages the publisher
+    /// keeps around for retransmission before dropping the oldest one, and (for
+    /// `Durability::TransientLocal`) the maximum number of samples kept for late-joining
+    /// subscribers.
+    pub history_depth: usize,
+    /// The lifespan this publisher was created with, or `None` for none. It is stored only:
+    /// history entries are not expired by age, and `retransmit_unacknowledged` resends every
+    /// buffered message regardless of this value.
+    pub lifespan: Option<Duration>,
+    pub durability: Durability,
+}
+
+impl Libp2pQos {
+    pub fn new(
+        reliability: Reliability,
+        history_depth: usize,
+        lifespan: Option<Duration>,
+        durability: Durability,
+    ) -> Self {
+        Self {
+            reliability,
+            history_depth,
+            lifespan,
+            durability,
+        }
+    }
+}
+
+impl Default for Libp2pQos {
+    /// Matches the default `rmw_qos_profile_t`: best-effort, volatile delivery with a depth-1
+    /// history.
+    fn default() -> Self {
+        Self {
+            reliability: Reliability::BestEffort,
+            history_depth: 1,
+            lifespan: None,
+            durability: Durability::Volatile,
+        }
+    }
+}
@@ -0,0 +1,376 @@
+// Copyright 2024 Esteve Fernandez
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! QoS compatibility evaluation between a publisher and a subscription.
+//!
+//! Mirrors `libp2p_c__rmw_qos_profile_check_compatible` (see `bindings.rs`), which like the rest
+//! of that file names a C++ rmw entry point this tree does not implement. What's implemented
+//! here is the compatibility matrix itself, exposed via
+//! `rs_libp2p_custom_qos_check_compatible` for a hypothetical C++ implementation to call with
+//! the two `rmw_qos_profile_t`s it already has in hand.
+//!
+//! This operates on its own [`ReliabilityPolicy`]/[`DurabilityPolicy`] rather than
+//! `crate::qos::{Reliability, Durability}`, because `rmw_qos_profile_check_compatible` also has
+//! to reason about `SYSTEM_DEFAULT`/`UNKNOWN`, which this crate's publisher/subscription QoS
+//! never actually carries (a publisher or subscription always resolves to a concrete policy
+//! before this crate touches it). Deadline and liveliness lease duration likewise aren't fields
+//! of `Libp2pQos`: a publisher takes them as separate arguments and only tracks missed deadlines
+//! and lost liveliness with the counters in `qos_event.rs`, enforcing neither.
+
+use std::os::raw::c_char;
+use std::time::Duration;
+
+use crate::c_types::{checked_mut, Libp2pRetT};
+
+/// Mirrors `rmw_qos_compatibility_type_t`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QosCompatibility {
+    Ok = 0,
+    Warning = 1,
+    Error = 2,
+}
+
+/// Mirrors `rmw_qos_reliability_policy_t` in full, unlike `crate::qos::Reliability` which only
+/// models the two variants this crate actually enforces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReliabilityPolicy {
+    Reliable,
+    BestEffort,
+    SystemDefault,
+    Unknown,
+}
+
+impl ReliabilityPolicy {
+    fn from_code(code: u8) -> Self {
+        match code {
+            0 => Self::Reliable,
+            1 => Self::BestEffort,
+            2 => Self::SystemDefault,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Mirrors `rmw_qos_durability_policy_t` in full, unlike `crate::qos::Durability` which only
+/// models the two variants this crate actually enforces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DurabilityPolicy {
+    TransientLocal,
+    Volatile,
+    SystemDefault,
+    Unknown,
+}
+
+impl DurabilityPolicy {
+    fn from_code(code: u8) -> Self {
+        match code {
+            0 => Self::TransientLocal,
+            1 => Self::Volatile,
+            2 => Self::SystemDefault,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// One side (publisher or subscription) of a QoS compatibility check.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct QosSummary {
+    pub(crate) reliability: ReliabilityPolicy,
+    pub(crate) durability: DurabilityPolicy,
+    /// `None` means no deadline.
+    pub(crate) deadline: Option<Duration>,
+    /// `None` means liveliness is never asserted to lapse.
+    pub(crate) liveliness_lease_duration: Option<Duration>,
+}
+
+/// Evaluates whether `publisher` and `subscription` can actually exchange data over this
+/// transport, returning the compatibility level and (for `Warning`/`Error`) a human-readable
+/// explanation. An empty reason accompanies `QosCompatibility::Ok`.
+pub(crate) fn check_compatible(publisher: QosSummary, subscription: QosSummary) -> (QosCompatibility, String) {
+    if subscription.reliability == ReliabilityPolicy::Reliable
+        && publisher.reliability == ReliabilityPolicy::BestEffort
+    {
+        return (
+            QosCompatibility::Error,
+            "ERROR: Best effort publisher offered, Reliable subscription requested;".to_string(),
+        );
+    }
+
+    if subscription.durability == DurabilityPolicy::TransientLocal
+        && publisher.durability == DurabilityPolicy::Volatile
+    {
+        return (
+            QosCompatibility::Error,
+            "ERROR: Volatile publisher offered, Transient Local subscription requested;"
+                .to_string(),
+        );
+    }
+
+    let deadline_incompatible = match (subscription.deadline, publisher.deadline) {
+        (Some(sub), Some(publ)) => sub < publ,
+        (Some(_), None) => true,
+        _ => false,
+    };
+    if deadline_incompatible {
+        return (
+            QosCompatibility::Error,
+            "ERROR: Subscription requested a deadline that is stricter than the deadline \
+             offered by the publisher;"
+                .to_string(),
+        );
+    }
+
+    let liveliness_incompatible = match (
+        subscription.liveliness_lease_duration,
+        publisher.liveliness_lease_duration,
+    ) {
+        (Some(sub), Some(publ)) => sub < publ,
+        (Some(_), None) => true,
+        _ => false,
+    };
+    if liveliness_incompatible {
+        return (
+            QosCompatibility::Error,
+            "ERROR: Subscription requested a liveliness lease duration that is shorter than \
+             the one offered by the publisher;"
+                .to_string(),
+        );
+    }
+
+    let mut warnings = Vec::new();
+
+    if subscription.reliability == ReliabilityPolicy::Reliable
+        && publisher.reliability == ReliabilityPolicy::Reliable
+    {
+        warnings.push(
+            "WARNING: Both ends requested Reliable, but this transport only approximates it \
+             with a bounded retransmission buffer rather than a true acknowledged protocol;"
+                .to_string(),
+        );
+    }
+
+    if matches!(
+        subscription.reliability,
+        ReliabilityPolicy::SystemDefault | ReliabilityPolicy::Unknown
+    ) || matches!(
+        publisher.reliability,
+        ReliabilityPolicy::SystemDefault | ReliabilityPolicy::Unknown
+    ) {
+        warnings.push(
+            "WARNING: Reliability policy is System Default or Unknown, compatibility cannot \
+             be guaranteed;"
+                .to_string(),
+        );
+    }
+
+    if matches!(
+        subscription.durability,
+        DurabilityPolicy::SystemDefault | DurabilityPolicy::Unknown
+    ) || matches!(
+        publisher.durability,
+        DurabilityPolicy::SystemDefault | DurabilityPolicy::Unknown
+    ) {
+        warnings.push(
+            "WARNING: Durability policy is System Default or Unknown, compatibility cannot \
+             be guaranteed;"
+                .to_string(),
+        );
+    }
+
+    if warnings.is_empty() {
+        (QosCompatibility::Ok, String::new())
+    } else {
+        (QosCompatibility::Warning, warnings.join(" "))
+    }
+}
+
+fn duration_from_ms(ms: u64) -> Option<Duration> {
+    if ms == 0 {
+        None
+    } else {
+        Some(Duration::from_millis(ms))
+    }
+}
+
+/// Copies `message` into `out`, truncating to fit `capacity` bytes including the null
+/// terminator. No-op if `capacity` is `0`.
+fn write_reason_truncated(message: &str, out: *mut c_char, capacity: usize) {
+    if capacity == 0 {
+        return;
+    }
+    let bytes = message.as_bytes();
+    let copy_len = bytes.len().min(capacity - 1);
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), out as *mut u8, copy_len);
+        *out.add(copy_len) = 0;
+    }
+}
+
+/// Evaluates QoS compatibility between a publisher and a subscription, writing the result into
+/// `out_compatibility` and a human-readable explanation into `reason` (truncated, always
+/// null-terminated, safely skipped if `reason` is null or `reason_size` is `0`).
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Arguments
+///
+/// * `publisher_reliability`/`subscription_reliability` - `0` = Reliable, `1` = BestEffort,
+///   `2` = SystemDefault, anything else = Unknown.
+/// * `publisher_durability`/`subscription_durability` - `0` = TransientLocal, `1` = Volatile,
+///   `2` = SystemDefault, anything else = Unknown.
+/// * `publisher_deadline_ms`/`subscription_deadline_ms` - The deadline in milliseconds, or `0`
+///   for no deadline.
+/// * `publisher_liveliness_lease_duration_ms`/`subscription_liveliness_lease_duration_ms` - The
+///   liveliness lease duration in milliseconds, or `0` for no lease duration.
+/// * `out_compatibility` - Out parameter receiving a `QosCompatibility` as a `u8`.
+/// * `reason` - A buffer to write the explanation into, or null to skip it.
+/// * `reason_size` - The capacity of `reason`, in bytes, including the null terminator.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if `out_compatibility` is null.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn rs_libp2p_custom_qos_check_compatible(
+    publisher_reliability: u8,
+    publisher_durability: u8,
+    publisher_deadline_ms: u64,
+    publisher_liveliness_lease_duration_ms: u64,
+    subscription_reliability: u8,
+    subscription_durability: u8,
+    subscription_deadline_ms: u64,
+    subscription_liveliness_lease_duration_ms: u64,
+    out_compatibility: *mut u8,
+    reason: *mut c_char,
+    reason_size: usize,
+) -> Libp2pRetT {
+    let out_compatibility = match unsafe { checked_mut(out_compatibility) } {
+        Ok(out_compatibility) => out_compatibility,
+        Err(ret) => return ret,
+    };
+
+    let publisher = QosSummary {
+        reliability: ReliabilityPolicy::from_code(publisher_reliability),
+        durability: DurabilityPolicy::from_code(publisher_durability),
+        deadline: duration_from_ms(publisher_deadline_ms),
+        liveliness_lease_duration: duration_from_ms(publisher_liveliness_lease_duration_ms),
+    };
+    let subscription = QosSummary {
+        reliability: ReliabilityPolicy::from_code(subscription_reliability),
+        durability: DurabilityPolicy::from_code(subscription_durability),
+        deadline: duration_from_ms(subscription_deadline_ms),
+        liveliness_lease_duration: duration_from_ms(
+            subscription_liveliness_lease_duration_ms,
+        ),
+    };
+
+    let (compatibility, message) = check_compatible(publisher, subscription);
+    *out_compatibility = compatibility as u8;
+
+    if !reason.is_null() {
+        write_reason_truncated(&message, reason, reason_size);
+    }
+
+    Libp2pRetT::Ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(reliability: ReliabilityPolicy, durability: DurabilityPolicy) -> QosSummary {
+        QosSummary {
+            reliability,
+            durability,
+            deadline: None,
+            liveliness_lease_duration: None,
+        }
+    }
+
+    #[test]
+    fn reliable_subscription_against_best_effort_publisher_is_an_error() {
+        let publisher = summary(ReliabilityPolicy::BestEffort, DurabilityPolicy::Volatile);
+        let subscription = summary(ReliabilityPolicy::Reliable, DurabilityPolicy::Volatile);
+        let (compatibility, reason) = check_compatible(publisher, subscription);
+        assert_eq!(compatibility, QosCompatibility::Error);
+        assert!(reason.contains("Best effort"));
+    }
+
+    #[test]
+    fn transient_local_subscription_against_volatile_publisher_is_an_error() {
+        let publisher = summary(ReliabilityPolicy::BestEffort, DurabilityPolicy::Volatile);
+        let subscription = summary(ReliabilityPolicy::BestEffort, DurabilityPolicy::TransientLocal);
+        let (compatibility, reason) = check_compatible(publisher, subscription);
+        assert_eq!(compatibility, QosCompatibility::Error);
+        assert!(reason.contains("Transient Local"));
+    }
+
+    #[test]
+    fn stricter_subscription_deadline_than_publisher_is_an_error() {
+        let mut publisher = summary(ReliabilityPolicy::BestEffort, DurabilityPolicy::Volatile);
+        publisher.deadline = Some(Duration::from_millis(100));
+        let mut subscription = summary(ReliabilityPolicy::BestEffort, DurabilityPolicy::Volatile);
+        subscription.deadline = Some(Duration::from_millis(50));
+        let (compatibility, _) = check_compatible(publisher, subscription);
+        assert_eq!(compatibility, QosCompatibility::Error);
+    }
+
+    #[test]
+    fn shorter_subscription_liveliness_lease_than_publisher_is_an_error() {
+        let mut publisher = summary(ReliabilityPolicy::BestEffort, DurabilityPolicy::Volatile);
+        publisher.liveliness_lease_duration = Some(Duration::from_millis(1000));
+        let mut subscription = summary(ReliabilityPolicy::BestEffort, DurabilityPolicy::Volatile);
+        subscription.liveliness_lease_duration = Some(Duration::from_millis(500));
+        let (compatibility, _) = check_compatible(publisher, subscription);
+        assert_eq!(compatibility, QosCompatibility::Error);
+    }
+
+    #[test]
+    fn both_reliable_is_a_warning_not_an_error() {
+        let publisher = summary(ReliabilityPolicy::Reliable, DurabilityPolicy::Volatile);
+        let subscription = summary(ReliabilityPolicy::Reliable, DurabilityPolicy::Volatile);
+        let (compatibility, reason) = check_compatible(publisher, subscription);
+        assert_eq!(compatibility, QosCompatibility::Warning);
+        assert!(reason.contains("retransmission buffer"));
+    }
+
+    #[test]
+    fn system_default_durability_is_a_warning() {
+        let publisher = summary(ReliabilityPolicy::BestEffort, DurabilityPolicy::SystemDefault);
+        let subscription = summary(ReliabilityPolicy::BestEffort, DurabilityPolicy::Volatile);
+        let (compatibility, reason) = check_compatible(publisher, subscription);
+        assert_eq!(compatibility, QosCompatibility::Warning);
+        assert!(reason.contains("Durability policy"));
+    }
+
+    #[test]
+    fn fully_matched_best_effort_volatile_is_ok_with_an_empty_reason() {
+        let publisher = summary(ReliabilityPolicy::BestEffort, DurabilityPolicy::Volatile);
+        let subscription = summary(ReliabilityPolicy::BestEffort, DurabilityPolicy::Volatile);
+        let (compatibility, reason) = check_compatible(publisher, subscription);
+        assert_eq!(compatibility, QosCompatibility::Ok);
+        assert!(reason.is_empty());
+    }
+
+    #[test]
+    fn reason_is_truncated_to_fit_the_caller_buffer_and_stays_null_terminated() {
+        let mut buf = [0xffu8; 8];
+        write_reason_truncated("a message longer than the buffer", buf.as_mut_ptr() as *mut c_char, buf.len());
+        assert_eq!(&buf[..7], b"a messa");
+        assert_eq!(buf[7], 0);
+    }
+}
@@ -0,0 +1,216 @@
+// Copyright 2024 Esteve Fernandez
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Counters behind the QoS events `libp2p_c__rmw_take_event` would report.
+//!
+//! `libp2p_c__rmw_take_event` (see `bindings.rs`) takes an opaque `*mut c_void` that it fills in
+//! with one of several `rmw_*_status_t` structs depending on the event type, none of which this
+//! tree defines (there is no `rosidl`/`rmw` struct layout here to write into). What's implemented
+//! here is the bookkeeping those statuses report: deadline-missed and liveliness-lost are
+//! computed from real local activity (a publisher/subscription's own message traffic), while
+//! incompatible-QoS counters are bumped by an explicit `record_*` call since this crate has no
+//! discovery-time QoS negotiation to trigger them automatically. A hypothetical C++
+//! `libp2p_c__rmw_take_event` would read one of the `rs_libp2p_custom_*_get_*_event` getters in
+//! `publisher.rs`/`subscription.rs` and copy the fields into the real status struct.
+//!
+//! `RequestedIncompatibleQos` is implemented the same way on the subscription side;
+//! `LivelinessChanged` (whether a *matched publisher's* liveliness lapsed, as observed by a
+//! subscription) is not: this crate has no presence/heartbeat protocol a subscription could use
+//! to notice that, only a publisher's own view of its own liveliness.
+
+use std::time::{Duration, Instant};
+
+/// Mirrors the `total_count`/`total_count_change` pair common to every `rmw` event status.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct EventCount {
+    total_count: u32,
+    total_count_change: u32,
+}
+
+impl EventCount {
+    fn record(&mut self) {
+        self.total_count += 1;
+        self.total_count_change += 1;
+    }
+
+    /// Reads the pair, resetting `total_count_change` to `0`: `rmw` event statuses report the
+    /// change since the *last read*, not since the last event.
+    fn take(&mut self) -> (u32, u32) {
+        let change = self.total_count_change;
+        self.total_count_change = 0;
+        (self.total_count, change)
+    }
+}
+
+/// Tracks whether a deadline (the maximum expected period between messages) has lapsed, given
+/// notifications of actual message activity.
+#[derive(Debug)]
+pub(crate) struct DeadlineTracker {
+    deadline: Option<Duration>,
+    last_activity: Instant,
+    missed: EventCount,
+}
+
+impl DeadlineTracker {
+    pub(crate) fn new(deadline: Option<Duration>) -> Self {
+        Self {
+            deadline,
+            last_activity: Instant::now(),
+            missed: EventCount::default(),
+        }
+    }
+
+    /// Resets the deadline window; call whenever a message is actually published/taken.
+    pub(crate) fn note_activity(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    /// Checks whether the deadline has lapsed since the last activity, recording a miss and
+    /// starting a new window if so. Call this from points that are reached regularly even when
+    /// no message activity is happening (e.g. every `publish`/`take_message`/`wait` call), since
+    /// nothing in this crate polls on a background timer.
+    pub(crate) fn poll(&mut self) {
+        let Some(deadline) = self.deadline else {
+            return;
+        };
+        if self.last_activity.elapsed() >= deadline {
+            self.missed.record();
+            self.last_activity = Instant::now();
+        }
+    }
+
+    pub(crate) fn take_status(&mut self) -> (u32, u32) {
+        self.missed.take()
+    }
+}
+
+/// Tracks whether a `Durability`-independent liveliness lease has lapsed without being
+/// reasserted, mirroring `rmw_liveliness_lost_status_t` on the publisher side.
+#[derive(Debug)]
+pub(crate) struct LivelinessTracker {
+    lease_duration: Option<Duration>,
+    last_assertion: Instant,
+    lost: EventCount,
+}
+
+impl LivelinessTracker {
+    pub(crate) fn new(lease_duration: Option<Duration>) -> Self {
+        Self {
+            lease_duration,
+            last_assertion: Instant::now(),
+            lost: EventCount::default(),
+        }
+    }
+
+    /// Resets the lease window; call on every publish and on an explicit liveliness assertion.
+    pub(crate) fn note_assertion(&mut self) {
+        self.last_assertion = Instant::now();
+    }
+
+    /// Checks whether the lease has lapsed since the last assertion, recording a loss and
+    /// starting a new window if so.
+    pub(crate) fn poll(&mut self) {
+        let Some(lease_duration) = self.lease_duration else {
+            return;
+        };
+        if self.last_assertion.elapsed() >= lease_duration {
+            self.lost.record();
+            self.last_assertion = Instant::now();
+        }
+    }
+
+    pub(crate) fn take_status(&mut self) -> (u32, u32) {
+        self.lost.take()
+    }
+}
+
+/// Mirrors `rmw_requested_qos_incompatible_event_status_t`/`rmw_offered_qos_incompatible_event_status_t`.
+#[derive(Debug, Default)]
+pub(crate) struct IncompatibleQosTracker {
+    count: EventCount,
+    /// The policy kind of the most recent mismatch: `0` = reliability, `1` = durability,
+    /// `2` = deadline, `3` = liveliness. Matches the ordering `qos_compatibility::check_compatible`
+    /// evaluates them in.
+    last_policy_kind: u32,
+}
+
+impl IncompatibleQosTracker {
+    /// Called by a host that has independently determined (e.g. via
+    /// `rs_libp2p_custom_qos_check_compatible`) that a matched peer's QoS is incompatible.
+    pub(crate) fn record(&mut self, policy_kind: u32) {
+        self.count.record();
+        self.last_policy_kind = policy_kind;
+    }
+
+    pub(crate) fn take_status(&mut self) -> (u32, u32, u32) {
+        let (total_count, total_count_change) = self.count.take();
+        (total_count, total_count_change, self.last_policy_kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+
+    #[test]
+    fn deadline_tracker_reports_no_miss_before_the_deadline_lapses() {
+        let mut tracker = DeadlineTracker::new(Some(Duration::from_secs(60)));
+        tracker.poll();
+        assert_eq!(tracker.take_status(), (0, 0));
+    }
+
+    #[test]
+    fn deadline_tracker_records_a_miss_once_the_deadline_lapses() {
+        let mut tracker = DeadlineTracker::new(Some(Duration::from_millis(1)));
+        sleep(Duration::from_millis(5));
+        tracker.poll();
+        assert_eq!(tracker.take_status(), (1, 1));
+        // total_count_change resets on read; total_count stays cumulative.
+        assert_eq!(tracker.take_status(), (1, 0));
+    }
+
+    #[test]
+    fn deadline_tracker_with_no_deadline_never_records_a_miss() {
+        let mut tracker = DeadlineTracker::new(None);
+        sleep(Duration::from_millis(5));
+        tracker.poll();
+        assert_eq!(tracker.take_status(), (0, 0));
+    }
+
+    #[test]
+    fn note_activity_prevents_a_miss_within_the_deadline_window() {
+        let mut tracker = DeadlineTracker::new(Some(Duration::from_millis(20)));
+        sleep(Duration::from_millis(5));
+        tracker.note_activity();
+        tracker.poll();
+        assert_eq!(tracker.take_status(), (0, 0));
+    }
+
+    #[test]
+    fn liveliness_tracker_records_a_loss_once_the_lease_lapses() {
+        let mut tracker = LivelinessTracker::new(Some(Duration::from_millis(1)));
+        sleep(Duration::from_millis(5));
+        tracker.poll();
+        assert_eq!(tracker.take_status(), (1, 1));
+    }
+
+    #[test]
+    fn incompatible_qos_tracker_remembers_the_last_policy_kind() {
+        let mut tracker = IncompatibleQosTracker::default();
+        tracker.record(1);
+        tracker.record(3);
+        assert_eq!(tracker.take_status(), (2, 2, 3));
+    }
+}
@@ -0,0 +1,328 @@
+// Copyright 2024 Esteve Fernandez
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A safe, thread-aware Rust layer over this crate's `rs_libp2p_*` FFI entry points.
+//!
+//! `bindings.rs` declares the `libp2p_c__rmw_*` surface a real C++ `rmw_libp2p_cpp`
+//! implementation would call through `bindings_types`, but that module doesn't exist in this
+//! tree, so `bindings.rs` cannot compile and there is no `rmw_ret_t`/`rmw_node_t` etc. to wrap.
+//! What's wrapped here instead is the surface this crate actually owns and exposes to any C
+//! caller: the `rs_libp2p_custom_*` functions in `node.rs`/`publisher.rs`/`subscription.rs`. Each
+//! raw entry point there already returns `Libp2pRetT` or a raw pointer and must be paired by hand
+//! with its `_free` counterpart; this module turns that into `Result<_, RmwError>` and RAII
+//! guards so ordinary Rust callers (and, if linked in, a C++ side written directly against this
+//! crate rather than through `bindings.rs`) don't have to.
+//!
+//! Node/publisher/subscription creation and destruction are not internally synchronized against
+//! each other the way a single publisher's or subscription's own methods are (each of those is
+//! guarded by its own fields/mutexes) — `libp2p_custom_node_get_*`-style bookkeeping and the
+//! swarm task's peer/topic maps are not safe to mutate concurrently with a creation or
+//! destruction call. [`Node::new`], [`Node::create_publisher`], [`Node::create_subscription`],
+//! and every `Drop` impl in this module take [`CREATION_LOCK`] for that reason.
+
+use std::ffi::CString;
+use std::io::Cursor;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::c_types::Libp2pRetT;
+use crate::node::{CustomSubscriptionHandle, MessageAcceptance};
+use crate::qos::{Durability, Reliability};
+use crate::{
+    rs_libp2p_custom_node_free, rs_libp2p_custom_node_new,
+    rs_libp2p_custom_publisher_assert_liveliness, rs_libp2p_custom_publisher_free,
+    rs_libp2p_custom_publisher_get_offered_deadline_missed_event,
+    rs_libp2p_custom_publisher_get_sequence_number, rs_libp2p_custom_publisher_new,
+    rs_libp2p_custom_publisher_publish, rs_libp2p_custom_subscription_free,
+    rs_libp2p_custom_subscription_get_requested_deadline_missed_event,
+    rs_libp2p_custom_subscription_new, rs_libp2p_custom_subscription_take_message,
+    rs_libp2p_custom_subscription_wait, Libp2pCustomNode, Libp2pCustomPublisher,
+    Libp2pCustomSubscription,
+};
+
+/// Guards every `rs_libp2p_custom_*_new`/`_free` call in this module, mirroring the real `rmw`
+/// requirement that node/publisher/subscription creation and destruction (unlike publish/take)
+/// are not safe to call concurrently with each other.
+static CREATION_LOCK: Mutex<()> = Mutex::new(());
+
+/// Mirrors the outcomes of `rmw_ret_t`, the C return type this wrapper's raw counterparts stand
+/// in for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RmwError {
+    /// `RMW_RET_INVALID_ARGUMENT` / `Libp2pRetT::InvalidArgument`: a null pointer or malformed
+    /// argument reached the FFI boundary.
+    InvalidArgument,
+    /// `RMW_RET_ERROR` / `Libp2pRetT::Error`: the underlying libp2p operation failed.
+    Error,
+    /// `RMW_RET_TIMEOUT`. Produced by [`Subscription::wait`] when no message arrives in time;
+    /// `Libp2pRetT` has no code for this since the raw `rs_libp2p_custom_subscription_wait`
+    /// reports it as a plain `bool` instead.
+    Timeout,
+    /// `RMW_RET_UNSUPPORTED`. Never produced by this wrapper: nothing in the `rs_libp2p_custom_*`
+    /// surface distinguishes "not supported" from "failed". Kept so callers can match on the
+    /// full `rmw_ret_t` outcome set without this wrapper's absence of the code being surprising.
+    Unsupported,
+    /// `RMW_RET_INCORRECT_RMW_IMPLEMENTATION`. Never produced by this wrapper, for the same
+    /// reason as `Unsupported`.
+    IncorrectRmwImplementation,
+}
+
+fn result_from(ret: Libp2pRetT) -> Result<(), RmwError> {
+    match ret {
+        Libp2pRetT::Ok => Ok(()),
+        Libp2pRetT::InvalidArgument => Err(RmwError::InvalidArgument),
+        Libp2pRetT::Error => Err(RmwError::Error),
+    }
+}
+
+fn duration_to_ms(duration: Option<Duration>) -> u64 {
+    duration.map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+/// Owns a `Libp2pCustomNode` and frees it on `Drop`.
+pub struct Node {
+    ptr: *mut Libp2pCustomNode,
+}
+
+unsafe impl Send for Node {}
+unsafe impl Sync for Node {}
+
+impl Node {
+    /// Creates a new node with this crate's default bounded-queue capacity. See
+    /// `rs_libp2p_custom_node_new` for the raw entry point this wraps.
+    pub fn new() -> Self {
+        let _guard = CREATION_LOCK.lock().unwrap();
+        Self {
+            ptr: rs_libp2p_custom_node_new(),
+        }
+    }
+
+    /// Creates a publisher for `topic` on this node. See `rs_libp2p_custom_publisher_new`.
+    ///
+    /// # Arguments
+    ///
+    /// * `deadline` - The maximum expected period between published messages, or `None` for no
+    ///   deadline.
+    /// * `liveliness_lease_duration` - The maximum period without a publish or an explicit
+    ///   `Publisher::assert_liveliness` call before this publisher is considered not alive, or
+    ///   `None` for no lease.
+    ///
+    /// # Returns
+    ///
+    /// `RmwError::InvalidArgument` if `topic` contains an interior nul byte.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_publisher(
+        &self,
+        topic: &str,
+        reliability: Reliability,
+        history_depth: usize,
+        lifespan: Option<Duration>,
+        durability: Durability,
+        deadline: Option<Duration>,
+        liveliness_lease_duration: Option<Duration>,
+    ) -> Result<Publisher, RmwError> {
+        let topic_cstring = CString::new(topic).map_err(|_| RmwError::InvalidArgument)?;
+        let reliability_code = match reliability {
+            Reliability::Reliable => 0,
+            Reliability::BestEffort => 1,
+        };
+        let durability_code = match durability {
+            Durability::TransientLocal => 0,
+            Durability::Volatile => 1,
+        };
+
+        let _guard = CREATION_LOCK.lock().unwrap();
+        let mut out_publisher: *mut Libp2pCustomPublisher = std::ptr::null_mut();
+        let ret = rs_libp2p_custom_publisher_new(
+            self.ptr,
+            topic_cstring.as_ptr(),
+            reliability_code,
+            history_depth,
+            duration_to_ms(lifespan),
+            durability_code,
+            duration_to_ms(deadline),
+            duration_to_ms(liveliness_lease_duration),
+            &mut out_publisher,
+        );
+        result_from(ret)?;
+        Ok(Publisher { ptr: out_publisher })
+    }
+
+    /// Creates a subscription for `topic` on this node. See `rs_libp2p_custom_subscription_new`.
+    ///
+    /// The raw entry point also takes a synchronous callback invoked from inside the swarm task;
+    /// this wrapper only exposes the poll-based [`Subscription::take`]/[`Subscription::wait`]
+    /// queue it additionally feeds, so it registers a callback that always accepts the message
+    /// for gossipsub forwarding and otherwise does nothing.
+    ///
+    /// # Returns
+    ///
+    /// `RmwError::InvalidArgument` if `topic` contains an interior nul byte, or `RmwError::Error`
+    /// if subscription creation unexpectedly returned a null pointer.
+    pub fn create_subscription(
+        &self,
+        topic: &str,
+        durability: Durability,
+        deadline: Option<Duration>,
+    ) -> Result<Subscription, RmwError> {
+        let topic_cstring = CString::new(topic).map_err(|_| RmwError::InvalidArgument)?;
+        let durability_code = match durability {
+            Durability::TransientLocal => 0,
+            Durability::Volatile => 1,
+        };
+        let handle = CustomSubscriptionHandle {
+            ptr: std::ptr::null(),
+        };
+
+        let _guard = CREATION_LOCK.lock().unwrap();
+        let ptr = rs_libp2p_custom_subscription_new(
+            self.ptr,
+            topic_cstring.as_ptr(),
+            handle,
+            accept_all,
+            durability_code,
+            duration_to_ms(deadline),
+        );
+        if ptr.is_null() {
+            return Err(RmwError::Error);
+        }
+        Ok(Subscription { ptr })
+    }
+}
+
+impl Default for Node {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        let _guard = CREATION_LOCK.lock().unwrap();
+        rs_libp2p_custom_node_free(self.ptr);
+    }
+}
+
+/// Always accepts the message for gossipsub forwarding. Registered by [`Node::create_subscription`]
+/// in place of a caller-supplied callback; see that method's doc comment for why.
+unsafe extern "C" fn accept_all(
+    _handle: &CustomSubscriptionHandle,
+    _data: *mut u8,
+    _len: usize,
+) -> MessageAcceptance {
+    MessageAcceptance::Accept
+}
+
+/// Owns a `Libp2pCustomPublisher` and frees it on `Drop`.
+pub struct Publisher {
+    ptr: *mut Libp2pCustomPublisher,
+}
+
+unsafe impl Send for Publisher {}
+unsafe impl Sync for Publisher {}
+
+impl Publisher {
+    /// Publishes `payload`, returning the number of bytes published. See
+    /// `rs_libp2p_custom_publisher_publish`.
+    pub fn publish(&self, payload: &[u8]) -> Result<usize, RmwError> {
+        let buffer = Cursor::new(payload.to_vec());
+        let mut bytes_written: usize = 0;
+        let ret = rs_libp2p_custom_publisher_publish(self.ptr, &buffer, &mut bytes_written);
+        result_from(ret).map(|_| bytes_written)
+    }
+
+    /// Returns the number of messages published through this publisher so far.
+    pub fn sequence_number(&self) -> u64 {
+        rs_libp2p_custom_publisher_get_sequence_number(self.ptr)
+    }
+
+    /// Resets this publisher's liveliness lease window without publishing a message. See
+    /// `rs_libp2p_custom_publisher_assert_liveliness`.
+    pub fn assert_liveliness(&self) -> Result<(), RmwError> {
+        result_from(rs_libp2p_custom_publisher_assert_liveliness(self.ptr))
+    }
+
+    /// Reads and clears this publisher's `OfferedDeadlineMissed` event status as
+    /// `(total_count, total_count_change)`. See `qos_event.rs`.
+    pub fn offered_deadline_missed_event(&self) -> Result<(u32, u32), RmwError> {
+        let mut total_count = 0u32;
+        let mut total_count_change = 0u32;
+        let ret = rs_libp2p_custom_publisher_get_offered_deadline_missed_event(
+            self.ptr,
+            &mut total_count,
+            &mut total_count_change,
+        );
+        result_from(ret).map(|_| (total_count, total_count_change))
+    }
+}
+
+impl Drop for Publisher {
+    fn drop(&mut self) {
+        let _guard = CREATION_LOCK.lock().unwrap();
+        rs_libp2p_custom_publisher_free(self.ptr);
+    }
+}
+
+/// Owns a `Libp2pCustomSubscription` and frees it on `Drop`.
+pub struct Subscription {
+    ptr: *mut Libp2pCustomSubscription,
+}
+
+unsafe impl Send for Subscription {}
+unsafe impl Sync for Subscription {}
+
+impl Subscription {
+    /// Pops the oldest buffered message without blocking, copying it into `buf` (truncated to
+    /// `buf.len()` if it doesn't fit). Returns the number of bytes copied, or `0` if none was
+    /// queued. See `rs_libp2p_custom_subscription_take_message`.
+    ///
+    /// This crate has no `rmw_message_info_t`-equivalent metadata (publisher GID, timestamp) to
+    /// attach to a taken message, so unlike the real `rmw_take_with_info` there is no
+    /// `take_with_info` variant here — only this plain `take`.
+    pub fn take(&self, buf: &mut [u8]) -> usize {
+        rs_libp2p_custom_subscription_take_message(self.ptr, buf.as_mut_ptr(), buf.len())
+    }
+
+    /// Blocks the calling thread until a message is buffered, or until `timeout` elapses. See
+    /// `rs_libp2p_custom_subscription_wait`.
+    pub fn wait(&self, timeout: Duration) -> Result<(), RmwError> {
+        let became_available =
+            rs_libp2p_custom_subscription_wait(self.ptr, timeout.as_millis() as u64);
+        if became_available {
+            Ok(())
+        } else {
+            Err(RmwError::Timeout)
+        }
+    }
+
+    /// Reads and clears this subscription's `RequestedDeadlineMissed` event status as
+    /// `(total_count, total_count_change)`. See `qos_event.rs`.
+    pub fn requested_deadline_missed_event(&self) -> Result<(u32, u32), RmwError> {
+        let mut total_count = 0u32;
+        let mut total_count_change = 0u32;
+        let ret = rs_libp2p_custom_subscription_get_requested_deadline_missed_event(
+            self.ptr,
+            &mut total_count,
+            &mut total_count_change,
+        );
+        result_from(ret).map(|_| (total_count, total_count_change))
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let _guard = CREATION_LOCK.lock().unwrap();
+        rs_libp2p_custom_subscription_free(self.ptr);
+    }
+}
@@ -0,0 +1,261 @@
+// Copyright 2024 Esteve Fernandez
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::c_types::{checked_mut, checked_str, Libp2pRetT};
+use crate::node::{ClientCallback, ServiceCallback};
+use crate::CustomSubscriptionHandle;
+use crate::Libp2pCustomNode;
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use libp2p::PeerId;
+
+/// Represents a custom ROS service/action server in the Libp2p network.
+///
+/// This struct holds a unique identifier (UUID), a pointer to the associated
+/// `Libp2pCustomNode`, and the name of the service.
+///
+/// # Fields
+///
+/// * `gid` - A unique identifier for this service.
+/// * `node` - A raw pointer to the `Libp2pCustomNode` associated with this service. This is
+///   needed to access the node's request/response queues.
+/// * `service_name` - The name of the service.
+///
+/// # Safety
+///
+/// This struct is unsafe because it uses raw pointers.
+pub struct Libp2pCustomService {
+    gid: Uuid,
+    node: *mut Libp2pCustomNode, // We need to store the Node here to have access to the request/response queues
+    service_name: String,
+}
+
+impl Libp2pCustomService {
+    /// Creates a new instance of `Libp2pCustomService`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ptr_node` - A raw pointer to the `Libp2pCustomNode` instance.
+    /// * `service_name` - The name of the service.
+    /// * `obj` - The custom subscription handle object.
+    /// * `callback` - The callback function to be called when a request is received.
+    ///
+    /// # Safety
+    ///
+    /// This function is marked as unsafe because it deals with raw pointers and
+    /// an unsafe extern "C" function pointer.
+    fn new(
+        ptr_node: *mut Libp2pCustomNode,
+        service_name: &str,
+        obj: CustomSubscriptionHandle,
+        callback: ServiceCallback,
+    ) -> Self {
+        let libp2p2_custom_node = unsafe {
+            assert!(!ptr_node.is_null());
+            &mut *ptr_node
+        };
+
+        libp2p2_custom_node.register_service(service_name.to_string(), obj, callback);
+
+        Self {
+            gid: Uuid::new_v4(),
+            node: ptr_node,
+            service_name: service_name.to_string(),
+        }
+    }
+}
+
+/// Creates a new `Libp2pCustomService`.
+///
+/// This function takes a raw pointer to a `Libp2pCustomNode`, a raw pointer to a C string
+/// representing the service name, a `CustomSubscriptionHandle`, and a callback function. It
+/// then registers a new service server for the given node and name, and returns a raw pointer
+/// to the heap-allocated service.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+///
+/// # Arguments
+///
+/// * `ptr_node` - A raw pointer to a `Libp2pCustomNode`.
+/// * `service_name_ptr` - A raw pointer to a C string representing the service name.
+/// * `obj` - A `CustomSubscriptionHandle` associated with the new service.
+/// * `callback` - A callback function to be called when a request is made to the service.
+///
+/// # Returns
+///
+/// A raw pointer to a `Libp2pCustomService`.
+///
+/// # Panics
+///
+/// This function will panic if `service_name_ptr` is null or if it does not point to a valid
+/// null-terminated string.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_service_new(
+    ptr_node: *mut Libp2pCustomNode,
+    service_name_ptr: *const c_char,
+    obj: CustomSubscriptionHandle,
+    callback: ServiceCallback,
+) -> *mut Libp2pCustomService {
+    let service_name_str = unsafe {
+        assert!(!service_name_ptr.is_null());
+        CStr::from_ptr(service_name_ptr)
+    };
+
+    let libp2p2_custom_service =
+        Libp2pCustomService::new(ptr_node, service_name_str.to_str().unwrap(), obj, callback);
+    Box::into_raw(Box::new(libp2p2_custom_service))
+}
+
+/// Frees a `Libp2pCustomService` from memory.
+///
+/// This function takes a raw pointer to a `Libp2pCustomService`, converts it back into a
+/// `Box`, and then drops the `Box`, freeing the memory. If the provided pointer is null, the
+/// function returns immediately.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Arguments
+///
+/// * `ptr_service` - A raw pointer to a `Libp2pCustomService`.
+///
+/// # Panics
+///
+/// This function will panic if the provided pointer has been previously deallocated or was
+/// not returned by `rs_libp2p_custom_service_new`.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_service_free(ptr_service: *mut Libp2pCustomService) {
+    if ptr_service.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(ptr_service) };
+}
+
+/// Gets the GID of a `Libp2pCustomService`.
+///
+/// This function takes a raw pointer to a `Libp2pCustomService` and a raw pointer to a
+/// buffer. It then copies the bytes of the GID of the service into the buffer and returns the
+/// number of bytes copied.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+///
+/// # Arguments
+///
+/// * `ptr_service` - A raw pointer to a `Libp2pCustomService`.
+/// * `buf` - A raw pointer to a buffer where the GID bytes will be copied.
+///
+/// # Returns
+///
+/// The number of bytes copied into the buffer.
+///
+/// # Panics
+///
+/// This function will panic if `ptr_service` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_service_get_gid(
+    ptr_service: *mut Libp2pCustomService,
+    buf: *mut std::os::raw::c_uchar,
+) -> usize {
+    let libp2p2_custom_service = unsafe {
+        assert!(!ptr_service.is_null());
+        &mut *ptr_service
+    };
+    let gid_bytes = libp2p2_custom_service.gid.as_bytes();
+    let count = gid_bytes.len();
+    unsafe {
+        std::ptr::copy_nonoverlapping(gid_bytes.as_ptr(), buf as *mut u8, count);
+    }
+    count
+}
+
+/// Sends a ROS service/action request from a client to `peer_id_str_ptr`, targeting the
+/// service named `service_name_ptr`.
+///
+/// `callback` is invoked with the response bytes once the server replies; there is no
+/// `Libp2pCustomClient` type to create or free, since a client does not need to register
+/// anything up front the way a service server or topic subscriber does.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+///
+/// # Arguments
+///
+/// * `ptr_node` - A raw pointer to a `Libp2pCustomNode`.
+/// * `peer_id_str_ptr` - A raw pointer to a C string representing the target peer's ID.
+/// * `service_name_ptr` - A raw pointer to a C string representing the service name.
+/// * `buffer_ptr` - A raw pointer to the request payload bytes.
+/// * `buffer_len` - The length of the request payload.
+/// * `obj` - A `CustomSubscriptionHandle` associated with the request.
+/// * `callback` - A callback function to be called when the response is received.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if `ptr_node` is null,
+/// `peer_id_str_ptr` or `service_name_ptr` is null or does not point to a valid null-terminated
+/// UTF-8 string, `peer_id_str_ptr` does not parse as a valid peer ID, or `buffer_ptr` is null
+/// while `buffer_len` is non-zero.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_client_send_request(
+    ptr_node: *mut Libp2pCustomNode,
+    peer_id_str_ptr: *const c_char,
+    service_name_ptr: *const c_char,
+    buffer_ptr: *const u8,
+    buffer_len: usize,
+    obj: CustomSubscriptionHandle,
+    callback: ClientCallback,
+) -> Libp2pRetT {
+    let libp2p2_custom_node = match unsafe { checked_mut(ptr_node) } {
+        Ok(node) => node,
+        Err(ret) => return ret,
+    };
+
+    let peer_id_str = match unsafe { checked_str(peer_id_str_ptr) } {
+        Ok(peer_id_str) => peer_id_str,
+        Err(ret) => return ret,
+    };
+    let peer_id = match PeerId::from_str(peer_id_str) {
+        Ok(peer_id) => peer_id,
+        Err(_) => return Libp2pRetT::InvalidArgument,
+    };
+
+    let service_name_str = match unsafe { checked_str(service_name_ptr) } {
+        Ok(service_name_str) => service_name_str,
+        Err(ret) => return ret,
+    };
+
+    if buffer_ptr.is_null() && buffer_len != 0 {
+        return Libp2pRetT::InvalidArgument;
+    }
+    let payload = unsafe { std::slice::from_raw_parts(buffer_ptr, buffer_len).to_vec() };
+
+    libp2p2_custom_node.send_request(
+        peer_id,
+        service_name_str.to_string(),
+        payload,
+        obj,
+        callback,
+    );
+    Libp2pRetT::Ok
+}
@@ -12,13 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::bounded_queue::{BoundedQueue, OverflowPolicy};
+use crate::c_types::{checked_mut, checked_str, Libp2pRetT};
+use crate::content_filter::{ContentFilter, FfiFieldValue, FieldResolver, FieldValue};
+use crate::loaned_message::{LoanHandle, ShmRing};
+use crate::node::{FieldResolveCallback, MessageOrigin, SubscriptionMessageCallback};
+use crate::qos::Durability;
+use crate::qos_event::{DeadlineTracker, IncompatibleQosTracker};
 use crate::CustomSubscriptionHandle;
 use crate::Libp2pCustomNode;
 
+use std::collections::HashMap;
 use std::ffi::CStr;
 use std::io::Cursor;
 use std::os::raw::c_char;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex as SyncMutex};
+use std::time::Duration;
 
 use uuid::Uuid;
 
@@ -34,7 +43,18 @@ use libp2p::gossipsub;
 /// * `gid` - A unique identifier for this subscription.
 /// * `node` - A raw pointer to the `Libp2pCustomNode` associated with this subscription. This is needed to access the outgoing queue.
 /// * `topic` - The topic of the subscription.
-/// * `incoming_queue` - A thread-safe, unlimited queue for incoming messages. Each message is a tuple of the topic and the message data.
+/// * `incoming_queue` - A bounded queue for incoming messages, sized to match the node's queue
+///   capacity and evicting the oldest buffered message on overflow rather than growing without
+///   bound. Each message is a tuple of the topic, the message data and its `MessageOrigin`.
+/// * `content_filter` - An optional compiled content filter, as installed by
+///   `rs_libp2p_custom_subscription_set_content_filter`, consulted by `take_message`/
+///   `take_loaned_message` before a message is handed to the application. Evaluating it requires
+///   a `field_resolver`; see that field's doc comment for what happens when one isn't installed.
+/// * `field_resolver` - An optional host-registered callback that resolves a field path against a
+///   message's raw bytes, installed by `rs_libp2p_custom_subscription_set_field_resolver`. This
+///   crate has no `rosidl` type-support introspection of its own to walk an arbitrary message's
+///   fields, so `content_filter` has no effect on delivery until a resolver is registered; with
+///   neither installed, every message is delivered exactly as before this feature existed.
 ///
 /// # Safety
 ///
@@ -43,7 +63,65 @@ pub struct Libp2pCustomSubscription {
     gid: Uuid,
     node: *mut Libp2pCustomNode, // We need to store the Node here to have access to the outgoing queue
     topic: gossipsub::IdentTopic,
-    incoming_queue: Arc<deadqueue::unlimited::Queue<(gossipsub::IdentTopic, Vec<u8>)>>,
+    incoming_queue: Arc<BoundedQueue<(gossipsub::IdentTopic, Vec<u8>, MessageOrigin)>>,
+    content_filter: Option<ContentFilter>,
+    field_resolver: Option<(CustomSubscriptionHandle, FieldResolveCallback)>,
+    /// Slots currently handed out by `take_loaned_message`, keyed by the pointer returned to the
+    /// caller, released by `return_loaned_message`. See `loaned_message.rs`.
+    outstanding_takes: SyncMutex<HashMap<usize, TakenLoan>>,
+    /// Feeds `rs_libp2p_custom_subscription_get_requested_deadline_missed_event`; see
+    /// `qos_event.rs`. Wrapped in a mutex since `take_message`/`wait` only hold `&self`.
+    deadline_tracker: SyncMutex<DeadlineTracker>,
+    /// Feeds `rs_libp2p_custom_subscription_get_requested_incompatible_qos_event`; see
+    /// `qos_event.rs`.
+    incompatible_qos: SyncMutex<IncompatibleQosTracker>,
+}
+
+/// What backs a pointer returned by `take_loaned_message`: either a read-only mapping of a
+/// publisher's shared-memory ring (the true zero-copy path), or an owned copy of a message that
+/// arrived as an ordinary serialized payload (every other case, per
+/// `Libp2pCustomPublisher::publish_loaned_message`'s fallback).
+enum TakenLoan {
+    Shm(ShmRing),
+    Copy(Box<[u8]>),
+}
+
+/// Adapts a subscription's `field_resolver` callback to [`FieldResolver`], so
+/// `ContentFilter::evaluate` can consult it without knowing this is an FFI round-trip.
+struct CallbackFieldResolver<'a> {
+    obj: CustomSubscriptionHandle,
+    callback: FieldResolveCallback,
+    payload: &'a [u8],
+}
+
+impl FieldResolver for CallbackFieldResolver<'_> {
+    fn resolve(&self, path: &[String]) -> Option<FieldValue> {
+        let c_path = std::ffi::CString::new(path.join(".")).ok()?;
+        let mut out = FfiFieldValue::default();
+        let resolved = unsafe {
+            (self.callback)(
+                &self.obj,
+                self.payload.as_ptr(),
+                self.payload.len(),
+                c_path.as_ptr(),
+                &mut out,
+            )
+        };
+        if !resolved {
+            return None;
+        }
+        if out.is_text {
+            if out.text_ptr.is_null() {
+                return None;
+            }
+            let bytes = unsafe {
+                std::slice::from_raw_parts(out.text_ptr as *const u8, out.text_len)
+            };
+            Some(FieldValue::Text(String::from_utf8_lossy(bytes).into_owned()))
+        } else {
+            Some(FieldValue::Number(out.number))
+        }
+    }
 }
 
 /// Represents a custom subscription in the Libp2p network.
@@ -61,6 +139,10 @@ impl Libp2pCustomSubscription {
     /// * `topic_str` - The topic string for the subscription.
     /// * `obj` - The custom subscription handle object.
     /// * `callback` - The callback function to be called when a message is received.
+    /// * `durability` - `TransientLocal` issues a history query for `topic_str` to replay any
+    ///   samples buffered by a matching publisher before this subscription was created.
+    /// * `deadline` - The maximum expected period between received messages, or `None` for no
+    ///   deadline. Feeds `rs_libp2p_custom_subscription_get_requested_deadline_missed_event`.
     ///
     /// # Safety
     ///
@@ -72,8 +154,9 @@ impl Libp2pCustomSubscription {
     /// ```
     /// use std::os::raw::c_void;
     ///
-    /// unsafe extern "C" fn callback_fn(handle: *const CustomSubscriptionHandle, data: *mut u8, len: usize) {
-    ///     // Handle the received message
+    /// unsafe extern "C" fn callback_fn(handle: &CustomSubscriptionHandle, data: *mut u8, len: usize) -> MessageAcceptance {
+    ///     // Handle the received message, then decide whether gossipsub should forward it.
+    ///     MessageAcceptance::Accept
     /// }
     ///
     /// let ptr_node = /* obtain the raw pointer */;
@@ -86,26 +169,190 @@ impl Libp2pCustomSubscription {
         ptr_node: *mut Libp2pCustomNode,
         topic_str: &str,
         obj: CustomSubscriptionHandle,
-        callback: unsafe extern "C" fn(&CustomSubscriptionHandle, *mut u8, len: usize),
+        callback: SubscriptionMessageCallback,
+        durability: Durability,
+        deadline: Option<Duration>,
     ) -> Self {
         let libp2p2_custom_node = unsafe {
             assert!(!ptr_node.is_null());
             &mut *ptr_node
         };
 
+        let incoming_queue = Arc::new(BoundedQueue::new(
+            libp2p2_custom_node.queue_capacity(),
+            OverflowPolicy::DropOldest,
+        ));
+
         libp2p2_custom_node.notify_new_subscriber(
             gossipsub::IdentTopic::new(topic_str),
             obj,
             callback,
+            Arc::clone(&incoming_queue),
         );
 
+        if durability == Durability::TransientLocal {
+            // Ask the topic's current mesh peers to replay whatever they have buffered from
+            // before this subscriber joined; see `Libp2pCustomNode::query_history`.
+            libp2p2_custom_node
+                .query_history(gossipsub::IdentTopic::new(topic_str), Arc::clone(&incoming_queue));
+        }
+
         Self {
             gid: Uuid::new_v4(),
             node: ptr_node,
             topic: gossipsub::IdentTopic::new(topic_str),
-            incoming_queue: Arc::new(deadqueue::unlimited::Queue::new()),
+            incoming_queue,
+            content_filter: None,
+            field_resolver: None,
+            outstanding_takes: SyncMutex::new(HashMap::new()),
+            deadline_tracker: SyncMutex::new(DeadlineTracker::new(deadline)),
+            incompatible_qos: SyncMutex::new(IncompatibleQosTracker::default()),
+        }
+    }
+
+    /// Whether `payload` should be delivered to the application, per this subscription's
+    /// installed content filter (if any). Fails open (returns `true`) when no filter is
+    /// installed, or a filter is installed but no `field_resolver` has been registered to pull
+    /// field values out of `payload` — see that field's doc comment for why.
+    fn passes_content_filter(&self, payload: &[u8]) -> bool {
+        let Some(filter) = &self.content_filter else {
+            return true;
+        };
+        let Some((obj, callback)) = &self.field_resolver else {
+            return true;
+        };
+        let resolver = CallbackFieldResolver { obj: *obj, callback: *callback, payload };
+        filter.evaluate(&resolver)
+    }
+
+    /// Pops the oldest buffered message that passes `passes_content_filter` without blocking,
+    /// copying its payload into `buf` (truncated to `buf.len()` if it doesn't fit) and returning
+    /// the number of bytes copied, or `0` if no matching message is currently queued. Messages
+    /// that fail the filter are dropped, not returned on a later call.
+    fn take_message(&self, buf: &mut [u8]) -> usize {
+        let libp2p2_custom_node = unsafe { &*self.node };
+        let mut deadline_tracker = self.deadline_tracker.lock().unwrap();
+        deadline_tracker.poll();
+        loop {
+            match libp2p2_custom_node.block_on(self.incoming_queue.try_pop()) {
+                Some((_topic, payload, _origin)) => {
+                    if !self.passes_content_filter(&payload) {
+                        continue;
+                    }
+                    deadline_tracker.note_activity();
+                    let len = payload.len().min(buf.len());
+                    buf[..len].copy_from_slice(&payload[..len]);
+                    return len;
+                }
+                None => return 0,
+            }
         }
     }
+
+    /// Blocks the calling thread until a message is buffered, or until `timeout` elapses.
+    /// Returns `true` if a message became available, `false` on timeout.
+    fn wait(&self, timeout: std::time::Duration) -> bool {
+        let libp2p2_custom_node = unsafe { &*self.node };
+        self.deadline_tracker.lock().unwrap().poll();
+        let became_available = libp2p2_custom_node.block_on(self.incoming_queue.wait_for_item(timeout));
+        if became_available {
+            self.deadline_tracker.lock().unwrap().note_activity();
+        }
+        became_available
+    }
+
+    /// Pops the oldest buffered message without blocking, returning a pointer directly into it
+    /// rather than copying it into a caller-supplied buffer like `take_message` does.
+    ///
+    /// If the popped message arrived via this process's own publish loopback (see
+    /// `MessageOrigin::Local`) and decodes as a [`LoanHandle`] (see `loaned_message.rs`), this
+    /// maps its shared-memory ring read-only and hands back a pointer straight into it: true
+    /// zero-copy. Otherwise the message is boxed up and its pointer returned instead; not
+    /// zero-copy, but still a single allocation rather than a caller-managed buffer.
+    ///
+    /// A message delivered over the network is never treated as a `LoanHandle`, no matter what
+    /// its bytes look like: the magic prefix `LoanHandle::decode` checks for is not a real framing
+    /// guarantee (see its doc comment), and a remote peer could otherwise forge a payload naming
+    /// an arbitrary shared-memory segment for this process to `mmap`.
+    ///
+    /// Returns `None` once no deliverable message is left in the queue. A popped `LoanHandle`
+    /// whose generation no longer matches the ring (the publisher already recycled the slot
+    /// before this call mapped it) or whose segment can no longer be mapped at all is dropped,
+    /// as is a message that fails `passes_content_filter` (just like in `take_message`); in
+    /// each case the next queued message (if any) is tried in its place.
+    fn take_loaned_message(&self) -> Option<(*const u8, usize)> {
+        let libp2p2_custom_node = unsafe { &*self.node };
+        loop {
+            self.deadline_tracker.lock().unwrap().poll();
+            let (_topic, payload, origin) =
+                libp2p2_custom_node.block_on(self.incoming_queue.try_pop())?;
+            self.deadline_tracker.lock().unwrap().note_activity();
+
+            let decoded = match origin {
+                MessageOrigin::Local => LoanHandle::decode(&payload),
+                MessageOrigin::Remote => None,
+            };
+            let (ptr, len, taken) = match decoded {
+                Some(handle) => {
+                    let Ok(ring) = ShmRing::open_read_only(
+                        &handle.segment_name,
+                        handle.slot_size as usize,
+                        handle.slot_count as usize,
+                    ) else {
+                        continue;
+                    };
+                    if ring.generation(handle.slot_index as usize) != Some(handle.generation) {
+                        continue;
+                    }
+                    let Some(ptr) = ring.slot(handle.slot_index as usize) else {
+                        continue;
+                    };
+                    // The slot holds the real message bytes the publisher wrote before handing
+                    // off the loan, so the filter can be evaluated against it exactly like any
+                    // other payload, even though `payload` itself was just the handle encoding.
+                    let message =
+                        unsafe { std::slice::from_raw_parts(ptr, handle.slot_size as usize) };
+                    if !self.passes_content_filter(message) {
+                        continue;
+                    }
+                    (ptr, handle.slot_size as usize, TakenLoan::Shm(ring))
+                }
+                None => {
+                    if !self.passes_content_filter(&payload) {
+                        continue;
+                    }
+                    let boxed: Box<[u8]> = payload.into_boxed_slice();
+                    let ptr = boxed.as_ptr();
+                    let len = boxed.len();
+                    (ptr, len, TakenLoan::Copy(boxed))
+                }
+            };
+
+            self.outstanding_takes
+                .lock()
+                .unwrap()
+                .insert(ptr as usize, taken);
+            return Some((ptr, len));
+        }
+    }
+
+    /// Releases a pointer previously returned by `take_loaned_message`, unmapping its ring (if
+    /// it was a zero-copy loan) or freeing its backing allocation (if it was a fallback copy).
+    ///
+    /// Returns `false` if `ptr` was not currently on loan from this subscription.
+    fn return_loaned_message(&self, ptr: *const u8) -> bool {
+        self.outstanding_takes
+            .lock()
+            .unwrap()
+            .remove(&(ptr as usize))
+            .is_some()
+    }
+
+    /// Notes that an independently-checked matched publisher's QoS turned out to be incompatible
+    /// with this subscription's, e.g. via `rs_libp2p_custom_qos_check_compatible`.
+    fn record_incompatible_qos(&self, policy_kind: u32) {
+        self.incompatible_qos.lock().unwrap().record(policy_kind);
+    }
 }
 
 /// Creates a new `Libp2pCustomSubscription`.
@@ -122,7 +369,13 @@ impl Libp2pCustomSubscription {
 /// * `ptr_node` - A raw pointer to a `Libp2pCustomNode`.
 /// * `topic_str_ptr` - A raw pointer to a C string representing the topic.
 /// * `obj` - A `CustomSubscriptionHandle` associated with the new subscription.
-/// * `callback` - A callback function to be called when a new message is published to the topic.
+/// * `callback` - A callback function to be called when a new message is published to the topic. Its return value is reported back to gossipsub as the message's validation verdict, gating whether the message is forwarded to other peers.
+/// * `durability` - `0` for `Durability::TransientLocal`, `1` for `Durability::Volatile`. A
+///   `TransientLocal` subscription issues a history query for this topic to replay samples
+///   buffered by a matching publisher before this subscription was created.
+/// * `deadline_ms` - The maximum expected period between received messages, in milliseconds, or
+///   `0` for no deadline. Feeds
+///   `rs_libp2p_custom_subscription_get_requested_deadline_missed_event`.
 ///
 /// # Returns
 ///
@@ -136,15 +389,33 @@ pub extern "C" fn rs_libp2p_custom_subscription_new(
     ptr_node: *mut Libp2pCustomNode,
     topic_str_ptr: *const c_char,
     obj: CustomSubscriptionHandle,
-    callback: unsafe extern "C" fn(&CustomSubscriptionHandle, *mut u8, len: usize),
+    callback: SubscriptionMessageCallback,
+    durability: u8,
+    deadline_ms: u64,
 ) -> *mut Libp2pCustomSubscription {
     let topic_str = unsafe {
         assert!(!topic_str_ptr.is_null());
         CStr::from_ptr(topic_str_ptr)
     };
+    let durability = if durability == 0 {
+        Durability::TransientLocal
+    } else {
+        Durability::Volatile
+    };
+    let deadline = if deadline_ms == 0 {
+        None
+    } else {
+        Some(Duration::from_millis(deadline_ms))
+    };
 
-    let libp2p2_custom_subscription =
-        Libp2pCustomSubscription::new(ptr_node, topic_str.to_str().unwrap(), obj, callback);
+    let libp2p2_custom_subscription = Libp2pCustomSubscription::new(
+        ptr_node,
+        topic_str.to_str().unwrap(),
+        obj,
+        callback,
+        durability,
+        deadline,
+    );
     Box::into_raw(Box::new(libp2p2_custom_subscription))
 }
 
@@ -211,3 +482,497 @@ pub extern "C" fn rs_libp2p_custom_subscription_get_gid(
     }
     count
 }
+
+/// Pops the oldest message buffered for a `Libp2pCustomSubscription`, without blocking.
+///
+/// This is the poll-based alternative to `SubscriptionMessageCallback`: it lets a host
+/// implement `rmw_take` by draining this subscription's queue from its own thread, instead of
+/// being invoked synchronously from inside the swarm task (which would stall gossipsub's
+/// heartbeat while the host's handler runs). The callback passed to
+/// `rs_libp2p_custom_subscription_new` still fires for every message and still gates gossipsub
+/// forwarding; this queue is fed in addition to it, not instead of it.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Arguments
+///
+/// * `ptr_subscription` - A raw pointer to a `Libp2pCustomSubscription`.
+/// * `buf` - A raw pointer to a buffer to copy the message payload into.
+/// * `cap` - The capacity of `buf`, in bytes. The payload is truncated to this length if it is
+///   larger.
+///
+/// # Returns
+///
+/// The number of bytes copied into `buf`, or `0` if no message was queued.
+///
+/// # Panics
+///
+/// This function will panic if `ptr_subscription` is null, or if `buf` is null while `cap` is
+/// nonzero.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_subscription_take_message(
+    ptr_subscription: *mut Libp2pCustomSubscription,
+    buf: *mut u8,
+    cap: usize,
+) -> usize {
+    let libp2p2_custom_subscription = unsafe {
+        assert!(!ptr_subscription.is_null());
+        &*ptr_subscription
+    };
+    let out = unsafe {
+        assert!(!buf.is_null() || cap == 0);
+        std::slice::from_raw_parts_mut(buf, cap)
+    };
+    libp2p2_custom_subscription.take_message(out)
+}
+
+/// Blocks the calling thread until a message is buffered for a `Libp2pCustomSubscription`, or
+/// until `timeout_ms` elapses.
+///
+/// This lets a host implement `rmw_wait` by waiting on a subscription directly rather than
+/// polling `rs_libp2p_custom_subscription_take_message` in a busy loop. The message itself is
+/// not consumed; call `rs_libp2p_custom_subscription_take_message` afterwards to retrieve it.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Arguments
+///
+/// * `ptr_subscription` - A raw pointer to a `Libp2pCustomSubscription`.
+/// * `timeout_ms` - The maximum time to wait, in milliseconds.
+///
+/// # Returns
+///
+/// `true` if a message became available, `false` on timeout.
+///
+/// # Panics
+///
+/// This function will panic if `ptr_subscription` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_subscription_wait(
+    ptr_subscription: *mut Libp2pCustomSubscription,
+    timeout_ms: u64,
+) -> bool {
+    let libp2p2_custom_subscription = unsafe {
+        assert!(!ptr_subscription.is_null());
+        &*ptr_subscription
+    };
+    libp2p2_custom_subscription.wait(std::time::Duration::from_millis(timeout_ms))
+}
+
+/// Compiles and installs a DDS-SQL-like content filter on a `Libp2pCustomSubscription`, parsing
+/// `filter_str` (and, if `parameters` is non-null, its `parameter_count` `%n` substitution
+/// values) via [`crate::content_filter::ContentFilter::compile`].
+///
+/// This corresponds to `libp2p_c__rmw_subscription_set_content_filter` in `bindings.rs`, but that
+/// declaration names a C rmw entry point whose implementation is on the C++ side of this RMW,
+/// which does not exist in this tree. Once installed, the filter is consulted by
+/// `rs_libp2p_custom_subscription_take_message` and `_take_loaned_message` before a message is
+/// handed to the application — but only once a field resolver is also installed via
+/// `rs_libp2p_custom_subscription_set_field_resolver`, since this crate has no `rosidl`
+/// type-support introspection of its own to walk an arbitrary message's fields. With no resolver
+/// registered, a filter can still be set and read back via `_get_content_filter`, but has no
+/// effect on delivery.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Arguments
+///
+/// * `ptr_subscription` - A raw pointer to a `Libp2pCustomSubscription`.
+/// * `filter_str_ptr` - A raw pointer to a null-terminated C string holding the filter
+///   expression. Passing null clears any previously installed filter.
+/// * `parameters_ptr` - A raw pointer to an array of `parameter_count` null-terminated C strings,
+///   the `%n` positional parameter values. May be null if `parameter_count` is `0`.
+/// * `parameter_count` - The number of entries in `parameters_ptr`.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if `ptr_subscription` is null,
+/// `filter_str_ptr` is non-null but does not point to a valid null-terminated UTF-8 string, any
+/// entry of `parameters_ptr` is null or not valid UTF-8, or `filter_str_ptr` does not parse as a
+/// well-formed filter expression.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_subscription_set_content_filter(
+    ptr_subscription: *mut Libp2pCustomSubscription,
+    filter_str_ptr: *const c_char,
+    parameters_ptr: *const *const c_char,
+    parameter_count: usize,
+) -> Libp2pRetT {
+    let libp2p2_custom_subscription = match unsafe { checked_mut(ptr_subscription) } {
+        Ok(subscription) => subscription,
+        Err(ret) => return ret,
+    };
+
+    if filter_str_ptr.is_null() {
+        libp2p2_custom_subscription.content_filter = None;
+        return Libp2pRetT::Ok;
+    }
+
+    let filter_str = match unsafe { checked_str(filter_str_ptr) } {
+        Ok(filter_str) => filter_str,
+        Err(ret) => return ret,
+    };
+
+    let mut parameters = Vec::with_capacity(parameter_count);
+    for i in 0..parameter_count {
+        let entry = unsafe { *parameters_ptr.add(i) };
+        match unsafe { checked_str(entry) } {
+            Ok(param) => parameters.push(param.to_string()),
+            Err(ret) => return ret,
+        }
+    }
+
+    match ContentFilter::compile(filter_str, &parameters) {
+        Ok(filter) => {
+            libp2p2_custom_subscription.content_filter = Some(filter);
+            Libp2pRetT::Ok
+        }
+        Err(ret) => ret,
+    }
+}
+
+/// Reads back the filter expression most recently installed by
+/// `rs_libp2p_custom_subscription_set_content_filter`, copying it (without a null terminator)
+/// into `buf` and returning the number of bytes copied, truncated to `buf.len()` if it doesn't
+/// fit. Returns `0` if no filter is installed.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Arguments
+///
+/// * `ptr_subscription` - A raw pointer to a `Libp2pCustomSubscription`.
+/// * `buf` - A raw pointer to a buffer to copy the filter expression into.
+/// * `cap` - The capacity of `buf`, in bytes.
+///
+/// # Returns
+///
+/// The number of bytes copied into `buf`.
+///
+/// # Panics
+///
+/// This function will panic if `ptr_subscription` is null, or if `buf` is null while `cap` is
+/// nonzero.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_subscription_get_content_filter(
+    ptr_subscription: *mut Libp2pCustomSubscription,
+    buf: *mut u8,
+    cap: usize,
+) -> usize {
+    let libp2p2_custom_subscription = unsafe {
+        assert!(!ptr_subscription.is_null());
+        &*ptr_subscription
+    };
+    let out = unsafe {
+        assert!(!buf.is_null() || cap == 0);
+        std::slice::from_raw_parts_mut(buf, cap)
+    };
+
+    let Some(filter) = &libp2p2_custom_subscription.content_filter else {
+        return 0;
+    };
+    let expression = filter.expression().as_bytes();
+    let len = expression.len().min(out.len());
+    out[..len].copy_from_slice(&expression[..len]);
+    len
+}
+
+/// Installs the field resolver a content filter needs to be evaluated against real messages:
+/// `callback` is invoked by `take_message`/`take_loaned_message` with a message's raw bytes and
+/// a dotted field path, and is expected to resolve it the same way the host's `rosidl`
+/// type-support would, since this crate has no introspection of its own.
+///
+/// Passing a null `callback` clears any previously installed resolver, after which an installed
+/// content filter goes back to having no effect on delivery, per
+/// `rs_libp2p_custom_subscription_set_content_filter`.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls an unsafe extern "C" function
+/// pointer.
+///
+/// # Arguments
+///
+/// * `ptr_subscription` - A raw pointer to a `Libp2pCustomSubscription`.
+/// * `obj` - The custom subscription handle object, passed back to `callback` on every call.
+/// * `callback` - The field-resolving callback, or null to clear the current one.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if `ptr_subscription` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_subscription_set_field_resolver(
+    ptr_subscription: *mut Libp2pCustomSubscription,
+    obj: CustomSubscriptionHandle,
+    callback: Option<FieldResolveCallback>,
+) -> Libp2pRetT {
+    let libp2p2_custom_subscription = match unsafe { checked_mut(ptr_subscription) } {
+        Ok(subscription) => subscription,
+        Err(ret) => return ret,
+    };
+    libp2p2_custom_subscription.field_resolver = callback.map(|callback| (obj, callback));
+    Libp2pRetT::Ok
+}
+
+/// Pops the oldest buffered message for a `Libp2pCustomSubscription` without blocking, handing
+/// back a pointer directly into it instead of copying it into a caller-supplied buffer. See
+/// `Libp2pCustomSubscription::take_loaned_message` for when this achieves true zero-copy versus
+/// falling back to a single boxed allocation.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Arguments
+///
+/// * `ptr_subscription` - A raw pointer to a `Libp2pCustomSubscription`.
+/// * `out_loaned_message` - Out parameter receiving a pointer to the taken message, valid until
+///   passed to `rs_libp2p_custom_subscription_return_loaned_message`.
+/// * `out_len` - Out parameter receiving the length of the taken message, in bytes.
+///
+/// # Returns
+///
+/// `true` if a message was taken, `false` once no deliverable message is left in the queue
+/// (messages that could not be mapped or fail the content filter are dropped on the way).
+///
+/// # Panics
+///
+/// This function will panic if `ptr_subscription`, `out_loaned_message`, or `out_len` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_subscription_take_loaned_message(
+    ptr_subscription: *mut Libp2pCustomSubscription,
+    out_loaned_message: *mut *mut u8,
+    out_len: *mut usize,
+) -> bool {
+    let libp2p2_custom_subscription = unsafe {
+        assert!(!ptr_subscription.is_null());
+        &*ptr_subscription
+    };
+    let out_loaned_message = unsafe {
+        assert!(!out_loaned_message.is_null());
+        &mut *out_loaned_message
+    };
+    let out_len = unsafe {
+        assert!(!out_len.is_null());
+        &mut *out_len
+    };
+
+    match libp2p2_custom_subscription.take_loaned_message() {
+        Some((ptr, len)) => {
+            *out_loaned_message = ptr as *mut u8;
+            *out_len = len;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Releases a message taken by `rs_libp2p_custom_subscription_take_loaned_message`.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers.
+///
+/// # Arguments
+///
+/// * `ptr_subscription` - A raw pointer to a `Libp2pCustomSubscription`.
+/// * `loaned_message` - A pointer previously returned by
+///   `rs_libp2p_custom_subscription_take_loaned_message` on this same subscription.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if `ptr_subscription` is null or
+/// `loaned_message` is not currently on loan from this subscription.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_subscription_return_loaned_message(
+    ptr_subscription: *mut Libp2pCustomSubscription,
+    loaned_message: *mut u8,
+) -> Libp2pRetT {
+    let libp2p2_custom_subscription = match unsafe { checked_mut(ptr_subscription) } {
+        Ok(subscription) => subscription,
+        Err(ret) => return ret,
+    };
+    if libp2p2_custom_subscription.return_loaned_message(loaned_message as *const u8) {
+        Libp2pRetT::Ok
+    } else {
+        Libp2pRetT::InvalidArgument
+    }
+}
+
+/// Reads and clears this subscription's `RequestedDeadlineMissed` event status, mirroring
+/// `rmw_requested_deadline_missed_status_t`. See `qos_event.rs`.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+///
+/// # Arguments
+///
+/// * `ptr_subscription` - A raw pointer to a `Libp2pCustomSubscription`.
+/// * `out_total_count` - Out parameter receiving the cumulative number of missed deadlines.
+/// * `out_total_count_change` - Out parameter receiving the number of misses since the last read.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if any pointer argument is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_subscription_get_requested_deadline_missed_event(
+    ptr_subscription: *mut Libp2pCustomSubscription,
+    out_total_count: *mut u32,
+    out_total_count_change: *mut u32,
+) -> Libp2pRetT {
+    let libp2p2_custom_subscription = match unsafe { checked_mut(ptr_subscription) } {
+        Ok(subscription) => subscription,
+        Err(ret) => return ret,
+    };
+    let out_total_count = match unsafe { checked_mut(out_total_count) } {
+        Ok(out_total_count) => out_total_count,
+        Err(ret) => return ret,
+    };
+    let out_total_count_change = match unsafe { checked_mut(out_total_count_change) } {
+        Ok(out_total_count_change) => out_total_count_change,
+        Err(ret) => return ret,
+    };
+
+    let mut deadline_tracker = libp2p2_custom_subscription.deadline_tracker.lock().unwrap();
+    deadline_tracker.poll();
+    let (total_count, total_count_change) = deadline_tracker.take_status();
+    *out_total_count = total_count;
+    *out_total_count_change = total_count_change;
+    Libp2pRetT::Ok
+}
+
+/// Records that a matched publisher's QoS was found incompatible with this subscription's, for
+/// example via `rs_libp2p_custom_qos_check_compatible`. There is no discovery-time QoS
+/// negotiation in this crate to call this automatically; a caller that performs its own
+/// compatibility check is expected to call this when it returns `QosCompatibility::Error`.
+///
+/// # Safety
+///
+/// This function is unsafe because it dereferences a raw pointer.
+///
+/// # Arguments
+///
+/// * `ptr_subscription` - A raw pointer to a `Libp2pCustomSubscription`.
+/// * `policy_kind` - `0` = reliability, `1` = durability, `2` = deadline, `3` = liveliness.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if `ptr_subscription` is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_subscription_record_requested_incompatible_qos(
+    ptr_subscription: *mut Libp2pCustomSubscription,
+    policy_kind: u32,
+) -> Libp2pRetT {
+    let libp2p2_custom_subscription = match unsafe { checked_mut(ptr_subscription) } {
+        Ok(subscription) => subscription,
+        Err(ret) => return ret,
+    };
+    libp2p2_custom_subscription.record_incompatible_qos(policy_kind);
+    Libp2pRetT::Ok
+}
+
+/// Reads and clears this subscription's `RequestedIncompatibleQos` event status, mirroring
+/// `rmw_requested_qos_incompatible_event_status_t`. See `qos_event.rs`.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+///
+/// # Arguments
+///
+/// * `ptr_subscription` - A raw pointer to a `Libp2pCustomSubscription`.
+/// * `out_total_count` - Out parameter receiving the cumulative number of incompatible-QoS
+///   detections.
+/// * `out_total_count_change` - Out parameter receiving the number of detections since the last
+///   read.
+/// * `out_last_policy_kind` - Out parameter receiving the policy kind of the most recent
+///   mismatch (see `rs_libp2p_custom_subscription_record_requested_incompatible_qos`).
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if any pointer argument is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_subscription_get_requested_incompatible_qos_event(
+    ptr_subscription: *mut Libp2pCustomSubscription,
+    out_total_count: *mut u32,
+    out_total_count_change: *mut u32,
+    out_last_policy_kind: *mut u32,
+) -> Libp2pRetT {
+    let libp2p2_custom_subscription = match unsafe { checked_mut(ptr_subscription) } {
+        Ok(subscription) => subscription,
+        Err(ret) => return ret,
+    };
+    let out_total_count = match unsafe { checked_mut(out_total_count) } {
+        Ok(out_total_count) => out_total_count,
+        Err(ret) => return ret,
+    };
+    let out_total_count_change = match unsafe { checked_mut(out_total_count_change) } {
+        Ok(out_total_count_change) => out_total_count_change,
+        Err(ret) => return ret,
+    };
+    let out_last_policy_kind = match unsafe { checked_mut(out_last_policy_kind) } {
+        Ok(out_last_policy_kind) => out_last_policy_kind,
+        Err(ret) => return ret,
+    };
+
+    let (total_count, total_count_change, last_policy_kind) = libp2p2_custom_subscription
+        .incompatible_qos
+        .lock()
+        .unwrap()
+        .take_status();
+    *out_total_count = total_count;
+    *out_total_count_change = total_count_change;
+    *out_last_policy_kind = last_policy_kind;
+    Libp2pRetT::Ok
+}
+
+/// Reads this subscription's `LivelinessChanged` event status. Always reports zero: this crate
+/// has no presence/heartbeat protocol a subscription could use to observe a matched *publisher's*
+/// liveliness lapsing, only a publisher's own view of itself (see
+/// `rs_libp2p_custom_publisher_get_liveliness_lost_event`). This stub exists so a host can still
+/// call every `rmw_*_event` getter uniformly instead of special-casing this one; it is
+/// intentionally never wired up to anything, unlike every other getter in this file.
+///
+/// # Safety
+///
+/// This function is unsafe because it uses raw pointers and calls unsafe functions.
+///
+/// # Arguments
+///
+/// * `ptr_subscription` - A raw pointer to a `Libp2pCustomSubscription`.
+/// * `out_alive_count` - Out parameter, always set to `0`.
+/// * `out_not_alive_count` - Out parameter, always set to `0`.
+///
+/// # Returns
+///
+/// `Libp2pRetT::Ok` on success, or `Libp2pRetT::InvalidArgument` if any pointer argument is null.
+#[no_mangle]
+pub extern "C" fn rs_libp2p_custom_subscription_get_liveliness_changed_event(
+    ptr_subscription: *mut Libp2pCustomSubscription,
+    out_alive_count: *mut u32,
+    out_not_alive_count: *mut u32,
+) -> Libp2pRetT {
+    let _libp2p2_custom_subscription = match unsafe { checked_mut(ptr_subscription) } {
+        Ok(subscription) => subscription,
+        Err(ret) => return ret,
+    };
+    let out_alive_count = match unsafe { checked_mut(out_alive_count) } {
+        Ok(out_alive_count) => out_alive_count,
+        Err(ret) => return ret,
+    };
+    let out_not_alive_count = match unsafe { checked_mut(out_not_alive_count) } {
+        Ok(out_not_alive_count) => out_not_alive_count,
+        Err(ret) => return ret,
+    };
+
+    *out_alive_count = 0;
+    *out_not_alive_count = 0;
+    Libp2pRetT::Ok
+}
@@ -12,15 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod bounded_queue;
+mod c_types;
 mod cdr_buffer;
+mod cdr_codec;
+mod content_filter;
+mod loaned_message;
+mod network_flow;
 mod node;
 mod publisher;
+mod qos;
+mod qos_compatibility;
+mod qos_event;
+mod safe;
+mod service;
 mod subscription;
 mod rmw_get_serialization_format;
 
+pub use c_types::Libp2pRetT;
 pub use cdr_buffer::*;
 pub use node::*;
 pub use publisher::*;
+pub use qos::{Durability, Libp2pQos, Reliability};
+pub use qos_compatibility::*;
+pub use safe::{Node, Publisher, RmwError, Subscription};
+pub use service::*;
 pub use subscription::*;
 pub use rmw_get_serialization_format::*;
 